@@ -0,0 +1,123 @@
+use asciimath_rs::elements::special::Expression;
+use asciimath_rs::format::mathml::ToMathML;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const LINES: &[&str] = &[
+    "sum_(i=1)^n i^3=((n(n+1))/2)^2",
+    "f(x) = {(x, if x >= 0), (-x, if x < 0):}",
+    "lim_(x->oo) (1 + 1/x)^x = e",
+    "int_0^1 sin^2(2 pi x) dx + oint_C f(z) dz",
+    "[[a, b], [c, d]] xx ((x), (y)) = ((a x + b y), (c x + d y))",
+    "alpha + beta -> gamma iff not (delta <= epsilon)",
+    "hat x + bar(a b) + color(red)(x) + text(some text) + \"quoted\"",
+    "AA x in RR EE y in NN : x |-> y_1 o+ y_2 ox y_3",
+];
+
+/// Creates a single expression of at least the given number of characters
+fn expression(length: usize) -> Expression {
+    let mut document = String::new();
+
+    for line in LINES.iter().cycle() {
+        if document.len() >= length {
+            break;
+        }
+        document.push_str(line);
+        document.push(' ');
+    }
+
+    asciimath_rs::parse(document)
+}
+
+/// Creates a square matrix with the given number of rows and columns
+fn matrix(size: usize) -> Expression {
+    let row = format!("({})", vec!["x_i^2"; size].join(","));
+
+    asciimath_rs::parse(format!("[{}]", vec![row; size].join(",")))
+}
+
+/// Renders long expressions into a new string and into an existing buffer
+fn long_expressions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_mathml/expression");
+
+    for length in [1_000, 10_000, 100_000] {
+        let expression = expression(length);
+        group.throughput(Throughput::Bytes(length as u64));
+        group.bench_with_input(
+            BenchmarkId::new("to_mathml", length),
+            &expression,
+            |b, e| b.iter(|| e.to_mathml()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("write_mathml", length),
+            &expression,
+            |b, e| {
+                let mut output = String::new();
+                b.iter(|| {
+                    output.clear();
+                    e.write_mathml(&mut output).unwrap();
+                    output.len()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Renders matrices like `long_expressions`
+fn matrices(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_mathml/matrix");
+
+    for size in [10, 50, 100] {
+        let matrix = matrix(size);
+        group.throughput(Throughput::Elements((size * size) as u64));
+        group.bench_with_input(BenchmarkId::new("to_mathml", size), &matrix, |b, m| {
+            b.iter(|| m.to_mathml())
+        });
+        group.bench_with_input(BenchmarkId::new("write_mathml", size), &matrix, |b, m| {
+            let mut output = String::new();
+            b.iter(|| {
+                output.clear();
+                m.write_mathml(&mut output).unwrap();
+                output.len()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Renders many formulas like a static site generator, either allocating
+/// a string for each formula or writing all of them into one buffer
+fn batch(c: &mut Criterion) {
+    let expressions: Vec<Expression> = LINES
+        .iter()
+        .cycle()
+        .take(1_000)
+        .map(asciimath_rs::parse)
+        .collect();
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(expressions.len() as u64));
+
+    group.bench_function("to_mathml", |b| {
+        b.iter(|| {
+            let mut page = String::new();
+            for expression in &expressions {
+                page.push_str(&expression.to_mathml());
+            }
+            page
+        })
+    });
+    group.bench_function("write_mathml", |b| {
+        let mut page = String::new();
+        b.iter(|| {
+            page.clear();
+            for expression in &expressions {
+                expression.write_mathml(&mut page).unwrap();
+            }
+            page.len()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, long_expressions, matrices, batch);
+criterion_main!(benches);
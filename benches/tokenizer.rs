@@ -0,0 +1,44 @@
+use asciimath_rs::parsing::tokenizer::Tokenizer;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const LINES: &[&str] = &[
+    "sum_(i=1)^n i^3=((n(n+1))/2)^2",
+    "f(x) = {(x, if x >= 0), (-x, if x < 0):}",
+    "lim_(x->oo) (1 + 1/x)^x = e",
+    "int_0^1 sin^2(2 pi x) dx + oint_C f(z) dz",
+    "[[a, b], [c, d]] xx ((x), (y)) = ((a x + b y), (c x + d y))",
+    "alpha + beta -> gamma iff not (delta <= epsilon)",
+    "hat x + bar(a b) + color(red)(x) + text(some text) + \"quoted\"",
+    "AA x in RR EE y in NN : x |-> y_1 o+ y_2 ox y_3",
+];
+
+/// Creates a document with at least the given number of characters
+fn document(length: usize) -> String {
+    let mut document = String::new();
+
+    for line in LINES.iter().cycle() {
+        if document.len() >= length {
+            break;
+        }
+        document.push_str(line);
+        document.push(' ');
+    }
+
+    document
+}
+
+fn tokenize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenize");
+
+    for length in [1_000, 10_000, 100_000] {
+        let document = document(length);
+        group.throughput(Throughput::Bytes(document.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(length), &document, |b, d| {
+            b.iter(|| Tokenizer::new(d).parse())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tokenize);
+criterion_main!(benches);
@@ -0,0 +1,169 @@
+use asciimath_rs::elements::special::Expression;
+use asciimath_rs::format::asciimath::ToAsciiMath;
+use asciimath_rs::format::latex::ToLatex;
+use asciimath_rs::format::mathml::{DisplayMode, MathMLOptions};
+use asciimath_rs::format::pretty::ToLayout;
+use asciimath_rs::format::unicode::ToUnicode;
+use std::io::{BufRead, BufReader, Read};
+use std::process::exit;
+use std::{env, fs, io};
+
+const USAGE: &str = "\
+Converts AsciiMath expressions into MathML and other formats.
+
+Usage: asciimath [OPTIONS] [EXPRESSION]...
+
+Every expression given as argument is converted. Without arguments the
+expressions are read from the input file or stdin, one expression per line.
+Use -- before expressions that start with '-'.
+
+Options:
+  -f, --format <FORMAT>  Output format: mathml, latex, asciimath, unicode or pretty
+                         [default: mathml]
+  -i, --input <FILE>     Read the expressions from the given file ('-' for stdin)
+  -d, --display          Render the expressions in display (block) mode
+      --inline           Render the expressions in inline mode [default]
+  -a, --annotate         Add the expression as alttext and AsciiMath annotation
+                         to the MathML output
+  -h, --help             Print this help
+
+The exit code is 1 if any expression couldn't be parsed and 2 on invalid arguments.";
+
+#[derive(Clone, Copy)]
+enum Format {
+    MathML,
+    Latex,
+    AsciiMath,
+    Unicode,
+    Pretty,
+}
+
+struct Options {
+    format: Format,
+    display: bool,
+    annotate: bool,
+    input: Option<String>,
+    expressions: Vec<String>,
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            exit(2)
+        }
+    };
+    let lines = match read_expressions(&options) {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("error: failed to read input: {}", e);
+            exit(2)
+        }
+    };
+    let mut failed = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match asciimath_rs::try_parse(line) {
+            Ok(expression) => println!("{}", render(&expression, line, &options)),
+            Err(errors) => {
+                failed = true;
+                for error in errors {
+                    eprintln!("error in expression {}: {}", index + 1, error);
+                }
+            }
+        }
+    }
+    if failed {
+        exit(1)
+    }
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Options, String> {
+    let mut options = Options {
+        format: Format::MathML,
+        display: false,
+        annotate: false,
+        input: None,
+        expressions: Vec::new(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0)
+            }
+            "-d" | "--display" => options.display = true,
+            "--inline" => options.display = false,
+            "-a" | "--annotate" => options.annotate = true,
+            "-f" | "--format" => {
+                let format = args.next().ok_or("missing value for --format")?;
+                options.format = match format.as_str() {
+                    "mathml" => Format::MathML,
+                    "latex" => Format::Latex,
+                    "asciimath" => Format::AsciiMath,
+                    "unicode" => Format::Unicode,
+                    "pretty" => Format::Pretty,
+                    _ => return Err(format!("unknown format '{}'", format)),
+                }
+            }
+            "-i" | "--input" => {
+                options.input = Some(args.next().ok_or("missing value for --input")?);
+            }
+            "--" => options.expressions.extend(&mut args),
+            _ if arg.starts_with('-') && arg.len() > 1 => {
+                return Err(format!("unknown option '{}'", arg))
+            }
+            _ => options.expressions.push(arg),
+        }
+    }
+    if options.input.is_some() && !options.expressions.is_empty() {
+        return Err("expressions can't be combined with --input".to_string());
+    }
+
+    Ok(options)
+}
+
+/// Returns the expressions given as arguments or the lines of the input file or stdin
+fn read_expressions(options: &Options) -> io::Result<Vec<String>> {
+    if !options.expressions.is_empty() {
+        return Ok(options.expressions.clone());
+    }
+    let reader: Box<dyn Read> = match options.input.as_deref() {
+        None | Some("-") => Box::new(io::stdin()),
+        Some(path) => Box::new(fs::File::open(path)?),
+    };
+
+    BufReader::new(reader).lines().collect()
+}
+
+fn render(expression: &Expression, source: &str, options: &Options) -> String {
+    match options.format {
+        Format::MathML => {
+            let display = if options.display {
+                DisplayMode::Block
+            } else {
+                DisplayMode::Inline
+            };
+            let mut mathml = MathMLOptions::default().with_display(display);
+            if options.annotate {
+                mathml = mathml.with_source(source.trim());
+            }
+            mathml.render(expression)
+        }
+        Format::Latex => {
+            if options.display {
+                format!("\\[{}\\]", expression.to_latex())
+            } else {
+                format!("${}$", expression.to_latex())
+            }
+        }
+        Format::AsciiMath => expression.to_asciimath(),
+        Format::Unicode => expression.to_unicode(),
+        Format::Pretty => expression.to_layout().to_string(),
+    }
+}
@@ -1,7 +1,10 @@
 use crate::elements::Element;
+use crate::span::Span;
 use crate::tokens::Accent;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum ExpressionAccent {
     Generic(GenericAccent),
     OverSet(OverSet),
@@ -10,25 +13,55 @@ pub enum ExpressionAccent {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GenericAccent {
     pub inner: Box<Element>,
     pub accent: Accent,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OverSet {
     pub top: Box<Element>,
     pub bottom: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UnderSet {
     pub top: Box<Element>,
     pub bottom: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Color {
     pub color: String,
     pub inner: Box<Element>,
+    pub span: Span,
+}
+
+impl ExpressionAccent {
+    /// Returns the span of the source the accent was parsed from
+    pub fn span(&self) -> Span {
+        match self {
+            ExpressionAccent::Generic(g) => g.span,
+            ExpressionAccent::OverSet(o) => o.span,
+            ExpressionAccent::UnderSet(u) => u.span,
+            ExpressionAccent::Color(c) => c.span,
+        }
+    }
+
+    /// Returns a mutable reference to the span of the element
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            ExpressionAccent::Generic(g) => &mut g.span,
+            ExpressionAccent::OverSet(o) => &mut o.span,
+            ExpressionAccent::UnderSet(u) => &mut u.span,
+            ExpressionAccent::Color(c) => &mut c.span,
+        }
+    }
 }
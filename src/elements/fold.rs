@@ -0,0 +1,258 @@
+use crate::elements::accent::ExpressionAccent;
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::special::{Expression, Special};
+use crate::elements::Element;
+
+/// Rebuilds an expression tree by value, node by node.
+///
+/// Every method defaults to the matching `walk_*` function which folds all children
+/// of the node and keeps the node itself. Override `fold_element` to replace nodes
+/// with nodes of a different kind.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::elements::fold::{walk_element, Fold};
+/// use asciimath_rs::elements::literal::{Literal, Number};
+/// use asciimath_rs::elements::Element;
+/// use asciimath_rs::format::asciimath::ToAsciiMath;
+///
+/// struct ReplaceX;
+///
+/// impl Fold for ReplaceX {
+///     fn fold_element(&mut self, element: Element) -> Element {
+///         match element {
+///             Element::Literal(Literal::Symbol(s)) if s.symbol == "x" => {
+///                 Element::Literal(Literal::Number(Number {
+///                     number: "2".to_string(),
+///                     span: s.span,
+///                 }))
+///             }
+///             element => walk_element(self, element),
+///         }
+///     }
+/// }
+///
+/// let expression = ReplaceX.fold_expression(asciimath_rs::parse("x^2 + y"));
+/// assert_eq!(expression.to_asciimath(), "2 ^ 2 + y");
+/// ```
+pub trait Fold {
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        walk_expression(self, expression)
+    }
+
+    fn fold_element(&mut self, element: Element) -> Element {
+        walk_element(self, element)
+    }
+
+    fn fold_literal(&mut self, literal: Literal) -> Literal {
+        walk_literal(self, literal)
+    }
+
+    fn fold_special(&mut self, special: Special) -> Special {
+        walk_special(self, special)
+    }
+
+    fn fold_group(&mut self, group: Group) -> Group {
+        walk_group(self, group)
+    }
+
+    fn fold_accent(&mut self, accent: ExpressionAccent) -> ExpressionAccent {
+        walk_accent(self, accent)
+    }
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    Expression {
+        children: expression
+            .children
+            .into_iter()
+            .map(|child| folder.fold_element(child))
+            .collect(),
+        span: expression.span,
+    }
+}
+
+pub fn walk_element<F: Fold + ?Sized>(folder: &mut F, element: Element) -> Element {
+    match element {
+        Element::Literal(l) => Element::Literal(folder.fold_literal(l)),
+        Element::Special(s) => Element::Special(folder.fold_special(s)),
+        Element::Group(g) => Element::Group(folder.fold_group(g)),
+        Element::Accent(a) => Element::Accent(folder.fold_accent(a)),
+        Element::Null => Element::Null,
+    }
+}
+
+/// Literals don't have any children and are returned unchanged
+pub fn walk_literal<F: Fold + ?Sized>(_folder: &mut F, literal: Literal) -> Literal {
+    literal
+}
+
+pub fn walk_special<F: Fold + ?Sized>(folder: &mut F, special: Special) -> Special {
+    match special {
+        Special::Sum(mut s) => {
+            s.bottom = fold_limit(folder, s.bottom);
+            s.top = fold_limit(folder, s.top);
+            Special::Sum(s)
+        }
+        Special::Prod(mut p) => {
+            p.bottom = fold_limit(folder, p.bottom);
+            p.top = fold_limit(folder, p.top);
+            Special::Prod(p)
+        }
+        Special::Integral(mut i) => {
+            i.bottom = fold_limit(folder, i.bottom);
+            i.top = fold_limit(folder, i.top);
+            Special::Integral(i)
+        }
+        Special::OIntegral(mut i) => {
+            i.bottom = fold_limit(folder, i.bottom);
+            i.top = fold_limit(folder, i.top);
+            Special::OIntegral(i)
+        }
+        Special::Limit(mut l) => {
+            l.bottom = fold_limit(folder, l.bottom);
+            l.top = fold_limit(folder, l.top);
+            Special::Limit(l)
+        }
+        Special::FunctionApplication(mut f) => {
+            f.subscript = fold_limit(folder, f.subscript);
+            f.exponent = fold_limit(folder, f.exponent);
+            f.argument = fold_boxed(folder, f.argument);
+            Special::FunctionApplication(f)
+        }
+        Special::Frac(mut f) => {
+            f.top = fold_boxed(folder, f.top);
+            f.bottom = fold_boxed(folder, f.bottom);
+            Special::Frac(f)
+        }
+        Special::Pow(mut p) => {
+            p.base = fold_boxed(folder, p.base);
+            p.exp = fold_boxed(folder, p.exp);
+            Special::Pow(p)
+        }
+        Special::Sub(mut s) => {
+            s.base = fold_boxed(folder, s.base);
+            s.lower = fold_boxed(folder, s.lower);
+            Special::Sub(s)
+        }
+        Special::Sqrt(mut s) => {
+            s.inner = fold_boxed(folder, s.inner);
+            Special::Sqrt(s)
+        }
+        Special::Root(mut r) => {
+            r.base = fold_boxed(folder, r.base);
+            r.inner = fold_boxed(folder, r.inner);
+            Special::Root(r)
+        }
+    }
+}
+
+pub fn walk_group<F: Fold + ?Sized>(folder: &mut F, group: Group) -> Group {
+    match group {
+        Group::MSep(span) => Group::MSep(span),
+        Group::Parentheses(mut p) => {
+            p.inner = fold_inner(folder, p.inner);
+            Group::Parentheses(p)
+        }
+        Group::Brackets(mut b) => {
+            b.inner = fold_inner(folder, b.inner);
+            Group::Brackets(b)
+        }
+        Group::Braces(mut b) => {
+            b.inner = fold_inner(folder, b.inner);
+            Group::Braces(b)
+        }
+        Group::Angles(mut a) => {
+            a.inner = fold_inner(folder, a.inner);
+            Group::Angles(a)
+        }
+        Group::XGroup(mut x) => {
+            x.inner = fold_inner(folder, x.inner);
+            Group::XGroup(x)
+        }
+        Group::Abs(mut a) => {
+            a.inner = fold_inner(folder, a.inner);
+            Group::Abs(a)
+        }
+        Group::Floor(mut f) => {
+            f.inner = fold_inner(folder, f.inner);
+            Group::Floor(f)
+        }
+        Group::Ceil(mut c) => {
+            c.inner = fold_inner(folder, c.inner);
+            Group::Ceil(c)
+        }
+        Group::Norm(mut n) => {
+            n.inner = fold_inner(folder, n.inner);
+            Group::Norm(n)
+        }
+        Group::Fenced(mut f) => {
+            f.inner = fold_inner(folder, f.inner);
+            Group::Fenced(f)
+        }
+        Group::NonEnclosed(mut n) => {
+            n.inner = fold_inner(folder, n.inner);
+            Group::NonEnclosed(n)
+        }
+        Group::Matrix(mut m) => {
+            m.inner = fold_rows(folder, m.inner);
+            Group::Matrix(m)
+        }
+        Group::Vector(mut v) => {
+            v.inner = fold_rows(folder, v.inner);
+            Group::Vector(v)
+        }
+        Group::Cases(mut c) => {
+            c.inner = fold_rows(folder, c.inner);
+            Group::Cases(c)
+        }
+    }
+}
+
+pub fn walk_accent<F: Fold + ?Sized>(folder: &mut F, accent: ExpressionAccent) -> ExpressionAccent {
+    match accent {
+        ExpressionAccent::Generic(mut g) => {
+            g.inner = fold_boxed(folder, g.inner);
+            ExpressionAccent::Generic(g)
+        }
+        ExpressionAccent::OverSet(mut o) => {
+            o.top = fold_boxed(folder, o.top);
+            o.bottom = fold_boxed(folder, o.bottom);
+            ExpressionAccent::OverSet(o)
+        }
+        ExpressionAccent::UnderSet(mut u) => {
+            u.top = fold_boxed(folder, u.top);
+            u.bottom = fold_boxed(folder, u.bottom);
+            ExpressionAccent::UnderSet(u)
+        }
+        ExpressionAccent::Color(mut c) => {
+            c.inner = fold_boxed(folder, c.inner);
+            ExpressionAccent::Color(c)
+        }
+    }
+}
+
+fn fold_boxed<F: Fold + ?Sized>(folder: &mut F, mut element: Box<Element>) -> Box<Element> {
+    *element = folder.fold_element(*element);
+    element
+}
+
+fn fold_limit<F: Fold + ?Sized>(
+    folder: &mut F,
+    limit: Option<Box<Element>>,
+) -> Option<Box<Element>> {
+    limit.map(|l| fold_boxed(folder, l))
+}
+
+fn fold_inner<F: Fold + ?Sized>(folder: &mut F, mut inner: Box<Expression>) -> Box<Expression> {
+    *inner = folder.fold_expression(*inner);
+    inner
+}
+
+fn fold_rows<F: Fold + ?Sized>(folder: &mut F, rows: Vec<Vec<Expression>>) -> Vec<Vec<Expression>> {
+    rows.into_iter()
+        .map(|row| row.into_iter().map(|e| folder.fold_expression(e)).collect())
+        .collect()
+}
@@ -1,8 +1,12 @@
 use crate::elements::special::Expression;
+use crate::span::Span;
+use crate::tokens::Grouping;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Group {
-    MSep,
+    MSep(Span),
     Parentheses(Parentheses),
     Brackets(Brackets),
     Braces(Braces),
@@ -14,67 +18,113 @@ pub enum Group {
     Norm(Norm),
     Matrix(Matrix),
     Vector(Vector),
+    Cases(Cases),
+    Fenced(Fenced),
     NonEnclosed(NonEnclosed),
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Parentheses {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Brackets {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Braces {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Angles {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XGroup {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Abs {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Floor {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ceil {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Norm {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Matrix {
     pub inner: Vec<Vec<Expression>>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Vector {
     pub inner: Vec<Vec<Expression>>,
+    pub span: Span,
 }
 
+/// A piecewise definition like `{(x, if x >= 0), (-x, if x < 0):}` with
+/// an opening brace and left aligned columns
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cases {
+    pub inner: Vec<Vec<Expression>>,
+    pub span: Span,
+}
+
+/// A group with delimiters that don't belong together like `(a, b]`
+/// or with hidden delimiters like `{: a :}`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fenced {
+    pub left: Grouping,
+    pub right: Grouping,
+    pub inner: Box<Expression>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NonEnclosed {
     pub inner: Box<Expression>,
+    pub span: Span,
 }
 
 impl Group {
@@ -85,6 +135,53 @@ impl Group {
             Group::Brackets(b) => Some(b.inner.clone()),
             _ => None,
         };
-        inner.map(|inner| Group::NonEnclosed(NonEnclosed { inner }))
+        inner.map(|inner| {
+            Group::NonEnclosed(NonEnclosed {
+                inner,
+                span: self.span(),
+            })
+        })
+    }
+
+    /// Returns the span of the source the group was parsed from
+    pub fn span(&self) -> Span {
+        match self {
+            Group::MSep(span) => *span,
+            Group::Parentheses(p) => p.span,
+            Group::Brackets(b) => b.span,
+            Group::Braces(b) => b.span,
+            Group::Angles(a) => a.span,
+            Group::XGroup(x) => x.span,
+            Group::Abs(a) => a.span,
+            Group::Floor(f) => f.span,
+            Group::Ceil(c) => c.span,
+            Group::Norm(n) => n.span,
+            Group::Matrix(m) => m.span,
+            Group::Vector(v) => v.span,
+            Group::Cases(c) => c.span,
+            Group::Fenced(f) => f.span,
+            Group::NonEnclosed(n) => n.span,
+        }
+    }
+
+    /// Returns a mutable reference to the span of the element
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Group::MSep(span) => span,
+            Group::Parentheses(p) => &mut p.span,
+            Group::Brackets(b) => &mut b.span,
+            Group::Braces(b) => &mut b.span,
+            Group::Angles(a) => &mut a.span,
+            Group::XGroup(x) => &mut x.span,
+            Group::Abs(a) => &mut a.span,
+            Group::Floor(f) => &mut f.span,
+            Group::Ceil(c) => &mut c.span,
+            Group::Norm(n) => &mut n.span,
+            Group::Matrix(m) => &mut m.span,
+            Group::Vector(v) => &mut v.span,
+            Group::Cases(c) => &mut c.span,
+            Group::Fenced(f) => &mut f.span,
+            Group::NonEnclosed(n) => &mut n.span,
+        }
     }
 }
@@ -1,33 +1,85 @@
-use crate::tokens::{Arrow, FontCommand, Function, Greek, Logical, Misc, Operation, Relation};
+use crate::span::Span;
+use crate::tokens::{
+    Arrow, CustomSymbol, FontCommand, Function, Greek, Logical, Misc, Operation, Relation,
+};
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Literal {
     Text(PlainText),
     Symbol(Symbol),
     Number(Number),
-    Greek(Greek),
-    FontCommand(FontCommand),
-    Relation(Relation),
-    Function(Function),
-    Logical(Logical),
-    Arrow(Arrow),
-    Misc(Misc),
-    Operation(Operation),
-    NewLine,
+    Greek(Greek, Span),
+    FontCommand(FontCommand, Span),
+    Relation(Relation, Span),
+    Function(Function, Span),
+    Logical(Logical, Span),
+    Arrow(Arrow, Span),
+    Misc(Misc, Span),
+    Operation(Operation, Span),
+    Custom(CustomSymbol, Span),
+    NewLine(Span),
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlainText {
     pub text: String,
     pub formatting: Option<FontCommand>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol {
     pub symbol: String,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Number {
     pub number: String,
+    pub span: Span,
+}
+
+impl Literal {
+    /// Returns the span of the source the literal was parsed from
+    pub fn span(&self) -> Span {
+        match self {
+            Literal::Text(t) => t.span,
+            Literal::Symbol(s) => s.span,
+            Literal::Number(n) => n.span,
+            Literal::Greek(_, span)
+            | Literal::FontCommand(_, span)
+            | Literal::Relation(_, span)
+            | Literal::Function(_, span)
+            | Literal::Logical(_, span)
+            | Literal::Arrow(_, span)
+            | Literal::Misc(_, span)
+            | Literal::Operation(_, span)
+            | Literal::Custom(_, span)
+            | Literal::NewLine(span) => *span,
+        }
+    }
+
+    /// Returns a mutable reference to the span of the element
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Literal::Text(t) => &mut t.span,
+            Literal::Symbol(s) => &mut s.span,
+            Literal::Number(n) => &mut n.span,
+            Literal::Greek(_, span)
+            | Literal::FontCommand(_, span)
+            | Literal::Relation(_, span)
+            | Literal::Function(_, span)
+            | Literal::Logical(_, span)
+            | Literal::Arrow(_, span)
+            | Literal::Misc(_, span)
+            | Literal::Operation(_, span)
+            | Literal::Custom(_, span)
+            | Literal::NewLine(span) => span,
+        }
+    }
 }
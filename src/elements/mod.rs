@@ -2,14 +2,19 @@ use crate::elements::accent::ExpressionAccent;
 use crate::elements::group::Group;
 use crate::elements::literal::Literal;
 use crate::elements::special::Special;
+use crate::span::Span;
 use crate::utils::Boxed;
 
 pub mod accent;
+pub mod fold;
 pub mod group;
 pub mod literal;
 pub mod special;
+pub mod visit;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Element {
     Literal(Literal),
     Special(Special),
@@ -32,4 +37,16 @@ impl Element {
             self.clone()
         }
     }
+
+    /// Returns the span of the source the element was parsed from.
+    /// `Element::Null` doesn't correspond to any source and returns an empty span.
+    pub fn span(&self) -> Span {
+        match self {
+            Element::Literal(l) => l.span(),
+            Element::Special(s) => s.span(),
+            Element::Group(g) => g.span(),
+            Element::Accent(a) => a.span(),
+            Element::Null => Span::default(),
+        }
+    }
 }
@@ -1,12 +1,24 @@
+use crate::elements::accent::ExpressionAccent;
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::visit::{
+    walk_accent_mut, walk_expression_mut, walk_group_mut, walk_special_mut, VisitMut,
+};
 use crate::elements::Element;
+use crate::span::Span;
+use crate::tokens::Function;
 use crate::utils::Boxed;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Expression {
     pub children: Vec<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Special {
     Sum(Sum),
     Prod(Prod),
@@ -17,65 +29,207 @@ pub enum Special {
     Root(Root),
     Integral(Integral),
     OIntegral(OIntegral),
+    Limit(Limit),
+    FunctionApplication(FunctionApplication),
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sum {
     pub top: Option<Box<Element>>,
     pub bottom: Option<Box<Element>>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Prod {
     pub top: Option<Box<Element>>,
     pub bottom: Option<Box<Element>>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frac {
     pub top: Box<Element>,
     pub bottom: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pow {
     pub base: Box<Element>,
     pub exp: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sub {
     pub base: Box<Element>,
     pub lower: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sqrt {
     pub inner: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Root {
     pub base: Box<Element>,
     pub inner: Box<Element>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Integral {
     pub top: Option<Box<Element>>,
     pub bottom: Option<Box<Element>>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OIntegral {
     pub top: Option<Box<Element>>,
     pub bottom: Option<Box<Element>>,
+    pub span: Span,
+}
+
+/// An operator like `lim_(x->0)` that has its limits written below (and above) it
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limit {
+    pub operator: LimitOperator,
+    pub top: Option<Box<Element>>,
+    pub bottom: Option<Box<Element>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LimitOperator {
+    Lim,
+    BigLim,
+    Sup,
+    Inf,
+    Max,
+    Min,
+}
+
+/// A function applied to the element following it like `sin^2 x` or `log_2(x)`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionApplication {
+    pub function: Function,
+    pub exponent: Option<Box<Element>>,
+    pub subscript: Option<Box<Element>>,
+    pub argument: Box<Element>,
+    pub span: Span,
 }
 
 impl Expression {
     pub fn add_child(&mut self, child: Element) {
         self.children.push(child)
     }
+
+    /// Returns a copy of the expression with all spans set to `Span::default()`
+    /// to compare trees regardless of the position they were parsed from.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// let expression = asciimath_rs::parse("a + (b)");
+    /// assert_ne!(expression, asciimath_rs::parse("a+(b)"));
+    /// assert_eq!(
+    ///     expression.without_spans(),
+    ///     asciimath_rs::parse("a+(b)").without_spans()
+    /// );
+    /// ```
+    pub fn without_spans(&self) -> Self {
+        let mut expression = self.clone();
+        SpanEraser.visit_expression_mut(&mut expression);
+
+        expression
+    }
+
+    /// Compares the structure of both expressions ignoring their spans
+    pub fn structurally_eq(&self, other: &Expression) -> bool {
+        self.without_spans() == other.without_spans()
+    }
+}
+
+/// Resets the spans of all visited nodes
+struct SpanEraser;
+
+impl VisitMut for SpanEraser {
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        expression.span = Span::default();
+        walk_expression_mut(self, expression)
+    }
+
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {
+        *literal.span_mut() = Span::default();
+    }
+
+    fn visit_special_mut(&mut self, special: &mut Special) {
+        *special.span_mut() = Span::default();
+        walk_special_mut(self, special)
+    }
+
+    fn visit_group_mut(&mut self, group: &mut Group) {
+        *group.span_mut() = Span::default();
+        walk_group_mut(self, group)
+    }
+
+    fn visit_accent_mut(&mut self, accent: &mut ExpressionAccent) {
+        *accent.span_mut() = Span::default();
+        walk_accent_mut(self, accent)
+    }
+}
+
+impl Special {
+    /// Returns the span of the source the element was parsed from
+    pub fn span(&self) -> Span {
+        match self {
+            Special::Sum(s) => s.span,
+            Special::Prod(p) => p.span,
+            Special::Frac(f) => f.span,
+            Special::Pow(p) => p.span,
+            Special::Sub(s) => s.span,
+            Special::Sqrt(s) => s.span,
+            Special::Root(r) => r.span,
+            Special::Integral(i) => i.span,
+            Special::OIntegral(i) => i.span,
+            Special::Limit(l) => l.span,
+            Special::FunctionApplication(f) => f.span,
+        }
+    }
+
+    /// Returns a mutable reference to the span of the element
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        match self {
+            Special::Sum(s) => &mut s.span,
+            Special::Prod(p) => &mut p.span,
+            Special::Frac(f) => &mut f.span,
+            Special::Pow(p) => &mut p.span,
+            Special::Sub(s) => &mut s.span,
+            Special::Sqrt(s) => &mut s.span,
+            Special::Root(r) => &mut r.span,
+            Special::Integral(i) => &mut i.span,
+            Special::OIntegral(i) => &mut i.span,
+            Special::Limit(l) => &mut l.span,
+            Special::FunctionApplication(f) => &mut f.span,
+        }
+    }
 }
 
 impl Boxed for Expression {}
@@ -0,0 +1,299 @@
+use crate::elements::accent::ExpressionAccent;
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::special::{Expression, Special};
+use crate::elements::Element;
+
+/// Visits the nodes of an expression tree by reference.
+///
+/// Every method defaults to the matching `walk_*` function which
+/// visits all children of the node. Implementations override the methods for the
+/// nodes they're interested in and call the `walk_*` function to keep descending.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::elements::literal::Literal;
+/// use asciimath_rs::elements::visit::{walk_literal, Visit};
+///
+/// struct Symbols(Vec<String>);
+///
+/// impl Visit for Symbols {
+///     fn visit_literal(&mut self, literal: &Literal) {
+///         if let Literal::Symbol(s) = literal {
+///             self.0.push(s.symbol.clone());
+///         }
+///         walk_literal(self, literal);
+///     }
+/// }
+///
+/// let mut symbols = Symbols(Vec::new());
+/// symbols.visit_expression(&asciimath_rs::parse("x^2 + sqrt(y)"));
+/// assert_eq!(symbols.0, vec!["x", "y"]);
+/// ```
+pub trait Visit {
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression)
+    }
+
+    fn visit_element(&mut self, element: &Element) {
+        walk_element(self, element)
+    }
+
+    fn visit_literal(&mut self, literal: &Literal) {
+        walk_literal(self, literal)
+    }
+
+    fn visit_special(&mut self, special: &Special) {
+        walk_special(self, special)
+    }
+
+    fn visit_group(&mut self, group: &Group) {
+        walk_group(self, group)
+    }
+
+    fn visit_accent(&mut self, accent: &ExpressionAccent) {
+        walk_accent(self, accent)
+    }
+}
+
+/// Visits the nodes of an expression tree by mutable reference like [Visit]
+pub trait VisitMut {
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression)
+    }
+
+    fn visit_element_mut(&mut self, element: &mut Element) {
+        walk_element_mut(self, element)
+    }
+
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {
+        walk_literal_mut(self, literal)
+    }
+
+    fn visit_special_mut(&mut self, special: &mut Special) {
+        walk_special_mut(self, special)
+    }
+
+    fn visit_group_mut(&mut self, group: &mut Group) {
+        walk_group_mut(self, group)
+    }
+
+    fn visit_accent_mut(&mut self, accent: &mut ExpressionAccent) {
+        walk_accent_mut(self, accent)
+    }
+}
+
+pub fn walk_expression<V: Visit + ?Sized>(visitor: &mut V, expression: &Expression) {
+    for child in &expression.children {
+        visitor.visit_element(child);
+    }
+}
+
+pub fn walk_element<V: Visit + ?Sized>(visitor: &mut V, element: &Element) {
+    match element {
+        Element::Literal(l) => visitor.visit_literal(l),
+        Element::Special(s) => visitor.visit_special(s),
+        Element::Group(g) => visitor.visit_group(g),
+        Element::Accent(a) => visitor.visit_accent(a),
+        Element::Null => {}
+    }
+}
+
+/// Literals don't have any children
+pub fn walk_literal<V: Visit + ?Sized>(_visitor: &mut V, _literal: &Literal) {}
+
+pub fn walk_special<V: Visit + ?Sized>(visitor: &mut V, special: &Special) {
+    match special {
+        Special::Sum(s) => walk_limits(visitor, &s.bottom, &s.top),
+        Special::Prod(p) => walk_limits(visitor, &p.bottom, &p.top),
+        Special::Integral(i) => walk_limits(visitor, &i.bottom, &i.top),
+        Special::OIntegral(i) => walk_limits(visitor, &i.bottom, &i.top),
+        Special::Limit(l) => walk_limits(visitor, &l.bottom, &l.top),
+        Special::FunctionApplication(f) => {
+            walk_limits(visitor, &f.subscript, &f.exponent);
+            visitor.visit_element(&f.argument);
+        }
+        Special::Frac(f) => {
+            visitor.visit_element(&f.top);
+            visitor.visit_element(&f.bottom);
+        }
+        Special::Pow(p) => {
+            visitor.visit_element(&p.base);
+            visitor.visit_element(&p.exp);
+        }
+        Special::Sub(s) => {
+            visitor.visit_element(&s.base);
+            visitor.visit_element(&s.lower);
+        }
+        Special::Sqrt(s) => visitor.visit_element(&s.inner),
+        Special::Root(r) => {
+            visitor.visit_element(&r.base);
+            visitor.visit_element(&r.inner);
+        }
+    }
+}
+
+fn walk_limits<V: Visit + ?Sized>(
+    visitor: &mut V,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) {
+    for limit in bottom.iter().chain(top.iter()) {
+        visitor.visit_element(limit);
+    }
+}
+
+pub fn walk_group<V: Visit + ?Sized>(visitor: &mut V, group: &Group) {
+    match group {
+        Group::MSep(_) => {}
+        Group::Parentheses(p) => visitor.visit_expression(&p.inner),
+        Group::Brackets(b) => visitor.visit_expression(&b.inner),
+        Group::Braces(b) => visitor.visit_expression(&b.inner),
+        Group::Angles(a) => visitor.visit_expression(&a.inner),
+        Group::XGroup(x) => visitor.visit_expression(&x.inner),
+        Group::Abs(a) => visitor.visit_expression(&a.inner),
+        Group::Floor(f) => visitor.visit_expression(&f.inner),
+        Group::Ceil(c) => visitor.visit_expression(&c.inner),
+        Group::Norm(n) => visitor.visit_expression(&n.inner),
+        Group::Fenced(f) => visitor.visit_expression(&f.inner),
+        Group::NonEnclosed(n) => visitor.visit_expression(&n.inner),
+        Group::Matrix(m) => m
+            .inner
+            .iter()
+            .flatten()
+            .for_each(|e| visitor.visit_expression(e)),
+        Group::Vector(v) => v
+            .inner
+            .iter()
+            .flatten()
+            .for_each(|e| visitor.visit_expression(e)),
+        Group::Cases(c) => c
+            .inner
+            .iter()
+            .flatten()
+            .for_each(|e| visitor.visit_expression(e)),
+    }
+}
+
+pub fn walk_accent<V: Visit + ?Sized>(visitor: &mut V, accent: &ExpressionAccent) {
+    match accent {
+        ExpressionAccent::Generic(g) => visitor.visit_element(&g.inner),
+        ExpressionAccent::OverSet(o) => {
+            visitor.visit_element(&o.top);
+            visitor.visit_element(&o.bottom);
+        }
+        ExpressionAccent::UnderSet(u) => {
+            visitor.visit_element(&u.top);
+            visitor.visit_element(&u.bottom);
+        }
+        ExpressionAccent::Color(c) => visitor.visit_element(&c.inner),
+    }
+}
+
+pub fn walk_expression_mut<V: VisitMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    for child in &mut expression.children {
+        visitor.visit_element_mut(child);
+    }
+}
+
+pub fn walk_element_mut<V: VisitMut + ?Sized>(visitor: &mut V, element: &mut Element) {
+    match element {
+        Element::Literal(l) => visitor.visit_literal_mut(l),
+        Element::Special(s) => visitor.visit_special_mut(s),
+        Element::Group(g) => visitor.visit_group_mut(g),
+        Element::Accent(a) => visitor.visit_accent_mut(a),
+        Element::Null => {}
+    }
+}
+
+/// Literals don't have any children
+pub fn walk_literal_mut<V: VisitMut + ?Sized>(_visitor: &mut V, _literal: &mut Literal) {}
+
+pub fn walk_special_mut<V: VisitMut + ?Sized>(visitor: &mut V, special: &mut Special) {
+    match special {
+        Special::Sum(s) => walk_limits_mut(visitor, &mut s.bottom, &mut s.top),
+        Special::Prod(p) => walk_limits_mut(visitor, &mut p.bottom, &mut p.top),
+        Special::Integral(i) => walk_limits_mut(visitor, &mut i.bottom, &mut i.top),
+        Special::OIntegral(i) => walk_limits_mut(visitor, &mut i.bottom, &mut i.top),
+        Special::Limit(l) => walk_limits_mut(visitor, &mut l.bottom, &mut l.top),
+        Special::FunctionApplication(f) => {
+            walk_limits_mut(visitor, &mut f.subscript, &mut f.exponent);
+            visitor.visit_element_mut(&mut f.argument);
+        }
+        Special::Frac(f) => {
+            visitor.visit_element_mut(&mut f.top);
+            visitor.visit_element_mut(&mut f.bottom);
+        }
+        Special::Pow(p) => {
+            visitor.visit_element_mut(&mut p.base);
+            visitor.visit_element_mut(&mut p.exp);
+        }
+        Special::Sub(s) => {
+            visitor.visit_element_mut(&mut s.base);
+            visitor.visit_element_mut(&mut s.lower);
+        }
+        Special::Sqrt(s) => visitor.visit_element_mut(&mut s.inner),
+        Special::Root(r) => {
+            visitor.visit_element_mut(&mut r.base);
+            visitor.visit_element_mut(&mut r.inner);
+        }
+    }
+}
+
+fn walk_limits_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    bottom: &mut Option<Box<Element>>,
+    top: &mut Option<Box<Element>>,
+) {
+    for limit in bottom.iter_mut().chain(top.iter_mut()) {
+        visitor.visit_element_mut(limit);
+    }
+}
+
+pub fn walk_group_mut<V: VisitMut + ?Sized>(visitor: &mut V, group: &mut Group) {
+    match group {
+        Group::MSep(_) => {}
+        Group::Parentheses(p) => visitor.visit_expression_mut(&mut p.inner),
+        Group::Brackets(b) => visitor.visit_expression_mut(&mut b.inner),
+        Group::Braces(b) => visitor.visit_expression_mut(&mut b.inner),
+        Group::Angles(a) => visitor.visit_expression_mut(&mut a.inner),
+        Group::XGroup(x) => visitor.visit_expression_mut(&mut x.inner),
+        Group::Abs(a) => visitor.visit_expression_mut(&mut a.inner),
+        Group::Floor(f) => visitor.visit_expression_mut(&mut f.inner),
+        Group::Ceil(c) => visitor.visit_expression_mut(&mut c.inner),
+        Group::Norm(n) => visitor.visit_expression_mut(&mut n.inner),
+        Group::Fenced(f) => visitor.visit_expression_mut(&mut f.inner),
+        Group::NonEnclosed(n) => visitor.visit_expression_mut(&mut n.inner),
+        Group::Matrix(m) => m
+            .inner
+            .iter_mut()
+            .flatten()
+            .for_each(|e| visitor.visit_expression_mut(e)),
+        Group::Vector(v) => v
+            .inner
+            .iter_mut()
+            .flatten()
+            .for_each(|e| visitor.visit_expression_mut(e)),
+        Group::Cases(c) => c
+            .inner
+            .iter_mut()
+            .flatten()
+            .for_each(|e| visitor.visit_expression_mut(e)),
+    }
+}
+
+pub fn walk_accent_mut<V: VisitMut + ?Sized>(visitor: &mut V, accent: &mut ExpressionAccent) {
+    match accent {
+        ExpressionAccent::Generic(g) => visitor.visit_element_mut(&mut g.inner),
+        ExpressionAccent::OverSet(o) => {
+            visitor.visit_element_mut(&mut o.top);
+            visitor.visit_element_mut(&mut o.bottom);
+        }
+        ExpressionAccent::UnderSet(u) => {
+            visitor.visit_element_mut(&mut u.top);
+            visitor.visit_element_mut(&mut u.bottom);
+        }
+        ExpressionAccent::Color(c) => visitor.visit_element_mut(&mut c.inner),
+    }
+}
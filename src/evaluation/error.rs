@@ -0,0 +1,57 @@
+use crate::span::Span;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// An error that was encountered while evaluating an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationError {
+    pub kind: EvaluationErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluationErrorKind {
+    /// A variable that has no value assigned
+    UnknownVariable(String),
+    /// An element that can't be evaluated to a number (e.g. relations or integrals).
+    /// Contains the AsciiMath source of the element.
+    Unsupported(String),
+    /// An operator or function that is missing its operand (e.g. `2 +` or `sin`)
+    MissingOperand,
+    /// A number literal that can't be represented as `f64`
+    InvalidNumber(String),
+    /// The limits of a sum or product aren't of the form `_(i=a)^b` with integer `a` and `b`
+    /// of at most 2^53
+    InvalidBounds,
+    /// Sums and products with more terms in total than can be evaluated
+    TooManyIterations,
+}
+
+impl EvaluationError {
+    pub fn new(kind: EvaluationErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for EvaluationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}: ", self.span.start, self.span.end)?;
+        match &self.kind {
+            EvaluationErrorKind::UnknownVariable(name) => write!(f, "unknown variable '{}'", name),
+            EvaluationErrorKind::Unsupported(element) => {
+                write!(f, "'{}' can't be evaluated", element)
+            }
+            EvaluationErrorKind::MissingOperand => write!(f, "missing operand"),
+            EvaluationErrorKind::InvalidNumber(number) => write!(f, "invalid number '{}'", number),
+            EvaluationErrorKind::InvalidBounds => {
+                write!(f, "limits must be of the form _(i=a)^b with integer bounds")
+            }
+            EvaluationErrorKind::TooManyIterations => {
+                write!(f, "sums and products have too many terms")
+            }
+        }
+    }
+}
+
+impl Error for EvaluationError {}
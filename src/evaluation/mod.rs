@@ -0,0 +1,437 @@
+use crate::elements::accent::ExpressionAccent;
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::special::{Expression, Special};
+use crate::elements::Element;
+use crate::evaluation::error::{EvaluationError, EvaluationErrorKind};
+use crate::format::asciimath::ToAsciiMath;
+use crate::span::Span;
+use crate::tokens::{Function, Grouping, Operation, Relation, SymbolCategory};
+use std::collections::HashMap;
+use std::f64::consts;
+use std::slice;
+
+pub mod error;
+
+type EvaluationResult<T> = Result<T, EvaluationError>;
+
+/// The largest magnitude of the bounds of a sum or product, above it
+/// consecutive integers can't be represented as `f64`
+const MAX_BOUND: f64 = 9_007_199_254_740_992.0;
+
+/// The maximum number of terms evaluated by all sums and products of an expression
+const MAX_ITERATIONS: i64 = 1_000_000;
+
+/// Evaluates an expression to a number with the given variable values.
+///
+/// Sequences of elements are evaluated with the usual precedence: `+` and `-` bind
+/// weaker than `*`, `xx` and `-:` or juxtaposition (`2x`). Functions apply to the
+/// element that follows them (`sin 2x` is `(sin 2) * x`), `log` is the decimal and
+/// `ln` the natural logarithm and `log_b` the logarithm to the base `b`.
+/// A sum or product `sum_(i=a)^b` iterates over the rest of its term with `i`
+/// bound to every integer from `a` to `b`.
+///
+/// Variables are looked up by their AsciiMath name (`x`, `alpha`).
+/// `pi` and `e` default to their constants unless they're given as variables.
+/// Results follow `f64` semantics so dividing by zero results in an infinite value.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::evaluation::evaluate;
+/// use std::collections::HashMap;
+///
+/// let expression = asciimath_rs::parse("sum_(i=1)^n i^2 + x/2");
+/// let mut variables = HashMap::new();
+/// variables.insert("n".to_string(), 3.0);
+/// variables.insert("x".to_string(), 5.0);
+///
+/// assert_eq!(evaluate(&expression, &variables), Ok(16.5));
+/// ```
+pub fn evaluate(
+    expression: &Expression,
+    variables: &HashMap<String, f64>,
+) -> EvaluationResult<f64> {
+    let mut scope = Scope {
+        variables,
+        bound: Vec::new(),
+        iterations: MAX_ITERATIONS,
+    };
+
+    scope.evaluate_expression(expression)
+}
+
+/// The variables given by the user together with the indices bound by sums and products
+struct Scope<'a> {
+    variables: &'a HashMap<String, f64>,
+    bound: Vec<(String, f64)>,
+    /// The number of terms sums and products may still evaluate, shared by nested
+    /// series so that they can't multiply their lengths
+    iterations: i64,
+}
+
+impl<'a> Scope<'a> {
+    fn lookup(&self, name: &str, span: Span) -> EvaluationResult<f64> {
+        self.bound
+            .iter()
+            .rev()
+            .find(|(bound, _)| bound == name)
+            .map(|(_, value)| *value)
+            .or_else(|| self.variables.get(name).copied())
+            .or(match name {
+                "pi" => Some(consts::PI),
+                "e" => Some(consts::E),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                EvaluationError::new(EvaluationErrorKind::UnknownVariable(name.to_string()), span)
+            })
+    }
+
+    fn evaluate_expression(&mut self, expression: &Expression) -> EvaluationResult<f64> {
+        if expression.children.is_empty() {
+            return Err(EvaluationError::new(
+                EvaluationErrorKind::MissingOperand,
+                expression.span,
+            ));
+        }
+        let (value, _) = self.evaluate_sum(&expression.children, 0)?;
+
+        Ok(value)
+    }
+
+    /// Evaluates a single element that is the operand of another element
+    fn evaluate_operand(&mut self, element: &Element) -> EvaluationResult<f64> {
+        let (value, _) = self.evaluate_sum(slice::from_ref(element), 0)?;
+
+        Ok(value)
+    }
+
+    /// Evaluates the terms separated by `+` and `-` starting at the given index.
+    /// Returns the value and the index after the last evaluated element.
+    fn evaluate_sum(
+        &mut self,
+        elements: &[Element],
+        index: usize,
+    ) -> EvaluationResult<(f64, usize)> {
+        let (mut value, mut index) = self.evaluate_term(elements, index)?;
+
+        while let Some(Element::Literal(Literal::Operation(op, _))) = elements.get(index) {
+            let (term, next) = match op {
+                Operation::Plus | Operation::Minus => self.evaluate_term(elements, index + 1)?,
+                _ => break,
+            };
+            if *op == Operation::Plus {
+                value += term;
+            } else {
+                value -= term;
+            }
+            index = next;
+        }
+
+        Ok((value, index))
+    }
+
+    /// Evaluates a product of factors that are either juxtaposed or separated by
+    /// a multiplication or division operator
+    fn evaluate_term(
+        &mut self,
+        elements: &[Element],
+        index: usize,
+    ) -> EvaluationResult<(f64, usize)> {
+        let (mut value, mut index) = self.evaluate_unary(elements, index)?;
+
+        while let Some(element) = elements.get(index) {
+            match element {
+                Element::Literal(Literal::Operation(Operation::Plus, _))
+                | Element::Literal(Literal::Operation(Operation::Minus, _)) => break,
+                Element::Literal(Literal::Operation(Operation::CDot, _))
+                | Element::Literal(Literal::Operation(Operation::Ast, _))
+                | Element::Literal(Literal::Operation(Operation::Star, _))
+                | Element::Literal(Literal::Operation(Operation::Times, _)) => {
+                    let (factor, next) = self.evaluate_unary(elements, index + 1)?;
+                    value *= factor;
+                    index = next;
+                }
+                Element::Literal(Literal::Operation(Operation::Div, _)) => {
+                    let (factor, next) = self.evaluate_unary(elements, index + 1)?;
+                    value /= factor;
+                    index = next;
+                }
+                _ => {
+                    let (factor, next) = self.evaluate_unary(elements, index)?;
+                    value *= factor;
+                    index = next;
+                }
+            }
+        }
+
+        Ok((value, index))
+    }
+
+    fn evaluate_unary(
+        &mut self,
+        elements: &[Element],
+        index: usize,
+    ) -> EvaluationResult<(f64, usize)> {
+        match elements.get(index) {
+            None => Err(missing_operand(elements, index)),
+            Some(Element::Literal(Literal::Operation(Operation::Minus, _))) => {
+                let (value, next) = self.evaluate_unary(elements, index + 1)?;
+                Ok((-value, next))
+            }
+            Some(Element::Literal(Literal::Operation(Operation::Plus, _))) => {
+                self.evaluate_unary(elements, index + 1)
+            }
+            Some(_) => self.evaluate_factor(elements, index),
+        }
+    }
+
+    /// Evaluates the element at the given index together with the elements
+    /// it applies to (function arguments and the terms of sums and products)
+    fn evaluate_factor(
+        &mut self,
+        elements: &[Element],
+        index: usize,
+    ) -> EvaluationResult<(f64, usize)> {
+        match &elements[index] {
+            Element::Literal(Literal::Function(function, span)) => {
+                let (argument, next) = self.evaluate_unary(elements, index + 1)?;
+                Ok((apply(function, argument, *span)?, next))
+            }
+            Element::Special(Special::Pow(pow)) => {
+                if let Element::Literal(Literal::Function(function, span)) = &*pow.base {
+                    let (argument, next) = self.evaluate_unary(elements, index + 1)?;
+                    let exp = self.evaluate_operand(&pow.exp)?;
+                    Ok((apply(function, argument, *span)?.powf(exp), next))
+                } else {
+                    Ok((self.evaluate_element(&elements[index])?, index + 1))
+                }
+            }
+            Element::Special(Special::Sub(sub)) => {
+                if let Element::Literal(Literal::Function(Function::Log, _)) = &*sub.base {
+                    let (argument, next) = self.evaluate_unary(elements, index + 1)?;
+                    let base = self.evaluate_operand(&sub.lower)?;
+                    Ok((argument.ln() / base.ln(), next))
+                } else {
+                    Ok((self.evaluate_element(&elements[index])?, index + 1))
+                }
+            }
+            Element::Special(Special::Sum(sum)) => {
+                self.evaluate_series(elements, index, &sum.bottom, &sum.top, sum.span, false)
+            }
+            Element::Special(Special::Prod(prod)) => {
+                self.evaluate_series(elements, index, &prod.bottom, &prod.top, prod.span, true)
+            }
+            element => Ok((self.evaluate_element(element)?, index + 1)),
+        }
+    }
+
+    /// Evaluates a sum or product over the rest of the term following it
+    fn evaluate_series(
+        &mut self,
+        elements: &[Element],
+        index: usize,
+        bottom: &Option<Box<Element>>,
+        top: &Option<Box<Element>>,
+        span: Span,
+        product: bool,
+    ) -> EvaluationResult<(f64, usize)> {
+        let invalid_bounds = || EvaluationError::new(EvaluationErrorKind::InvalidBounds, span);
+        let (bottom, top) = bottom
+            .as_ref()
+            .zip(top.as_ref())
+            .ok_or_else(invalid_bounds)?;
+        let (name, start) = self.evaluate_index(bottom).ok_or_else(invalid_bounds)??;
+        let end = self.evaluate_operand(top)?;
+
+        if !is_integer(start) || !is_integer(end) {
+            return Err(invalid_bounds());
+        }
+        if start.abs() > MAX_BOUND || end.abs() > MAX_BOUND {
+            return Err(invalid_bounds());
+        }
+        let (start, end) = (start as i64, end as i64);
+
+        let too_many_iterations =
+            || EvaluationError::new(EvaluationErrorKind::TooManyIterations, span);
+        if end - start >= self.iterations {
+            return Err(too_many_iterations());
+        }
+        if index + 1 >= elements.len() {
+            return Err(missing_operand(elements, index + 1));
+        }
+        let mut value = if product { 1.0 } else { 0.0 };
+        let mut i = start;
+
+        // the term is evaluated at least once to find its end even if the range is empty
+        loop {
+            if self.iterations <= 0 {
+                return Err(too_many_iterations());
+            }
+            self.iterations -= 1;
+            self.bound.push((name.clone(), i as f64));
+            let result = self.evaluate_term(elements, index + 1);
+            self.bound.pop();
+            let (term, next) = result?;
+
+            if i <= end {
+                if product {
+                    value *= term;
+                } else {
+                    value += term;
+                }
+            }
+            i += 1;
+            if i > end {
+                return Ok((value, next));
+            }
+        }
+    }
+
+    /// Evaluates the lower limit `i=a` of a sum or product into the name
+    /// of the index and its start value
+    fn evaluate_index(&mut self, bottom: &Element) -> Option<EvaluationResult<(String, f64)>> {
+        let inner = match bottom {
+            Element::Group(Group::NonEnclosed(n)) => &n.inner,
+            Element::Group(Group::Parentheses(p)) => &p.inner,
+            _ => return None,
+        };
+        match inner.children.as_slice() {
+            [Element::Literal(Literal::Symbol(index)), Element::Literal(Literal::Relation(Relation::Eq, _)), start @ ..]
+                if !start.is_empty() =>
+            {
+                Some(
+                    self.evaluate_sum(start, 0)
+                        .map(|(value, _)| (index.symbol.clone(), value)),
+                )
+            }
+            _ => None,
+        }
+    }
+
+    /// Evaluates an element that doesn't depend on the elements around it
+    fn evaluate_element(&mut self, element: &Element) -> EvaluationResult<f64> {
+        match element {
+            Element::Literal(Literal::Number(n)) => n.number.parse().map_err(|_| {
+                EvaluationError::new(EvaluationErrorKind::InvalidNumber(n.number.clone()), n.span)
+            }),
+            Element::Literal(Literal::Symbol(s)) => self.lookup(&s.symbol, s.span),
+            Element::Literal(Literal::Greek(g, span)) => self.lookup(&g.to_asciimath(), *span),
+            Element::Literal(Literal::Custom(c, span))
+                if c.category == SymbolCategory::Identifier =>
+            {
+                self.lookup(&c.name, *span)
+            }
+            Element::Literal(Literal::Function(_, span)) => Err(EvaluationError::new(
+                EvaluationErrorKind::MissingOperand,
+                *span,
+            )),
+            Element::Group(Group::Parentheses(p)) => self.evaluate_expression(&p.inner),
+            Element::Group(Group::Brackets(b)) => self.evaluate_expression(&b.inner),
+            Element::Group(Group::Braces(b)) => self.evaluate_expression(&b.inner),
+            Element::Group(Group::NonEnclosed(n)) => self.evaluate_expression(&n.inner),
+            Element::Group(Group::Fenced(f))
+                if f.left == Grouping::RBraceHidden && f.right == Grouping::LBraceHidden =>
+            {
+                self.evaluate_expression(&f.inner)
+            }
+            Element::Group(Group::Abs(a)) => Ok(self.evaluate_expression(&a.inner)?.abs()),
+            Element::Group(Group::Floor(f)) => Ok(self.evaluate_expression(&f.inner)?.floor()),
+            Element::Group(Group::Ceil(c)) => Ok(self.evaluate_expression(&c.inner)?.ceil()),
+            Element::Special(Special::Frac(f)) => {
+                Ok(self.evaluate_operand(&f.top)? / self.evaluate_operand(&f.bottom)?)
+            }
+            Element::Special(Special::Pow(p)) => Ok(self
+                .evaluate_operand(&p.base)?
+                .powf(self.evaluate_operand(&p.exp)?)),
+            Element::Special(Special::FunctionApplication(f)) => {
+                let argument = self.evaluate_operand(&f.argument)?;
+                let value = match (&f.function, &f.subscript) {
+                    (Function::Log, Some(base)) => {
+                        argument.ln() / self.evaluate_operand(base)?.ln()
+                    }
+                    (function, None) => apply(function, argument, f.span)?,
+                    (_, Some(_)) => {
+                        return Err(EvaluationError::new(
+                            EvaluationErrorKind::Unsupported(element.to_asciimath()),
+                            f.span,
+                        ))
+                    }
+                };
+
+                match &f.exponent {
+                    Some(exp) => Ok(value.powf(self.evaluate_operand(exp)?)),
+                    None => Ok(value),
+                }
+            }
+            Element::Special(Special::Sqrt(s)) => Ok(self.evaluate_operand(&s.inner)?.sqrt()),
+            Element::Special(Special::Root(r)) => {
+                let base = self.evaluate_operand(&r.base)?;
+                let inner = self.evaluate_operand(&r.inner)?;
+
+                // odd roots of negative numbers are real
+                if inner < 0.0 && is_integer(base) && base % 2.0 != 0.0 {
+                    Ok(-(-inner).powf(base.recip()))
+                } else {
+                    Ok(inner.powf(base.recip()))
+                }
+            }
+            Element::Accent(ExpressionAccent::Color(c)) => self.evaluate_operand(&c.inner),
+            Element::Null => Err(EvaluationError::new(
+                EvaluationErrorKind::MissingOperand,
+                element.span(),
+            )),
+            _ => Err(EvaluationError::new(
+                EvaluationErrorKind::Unsupported(element.to_asciimath()),
+                element.span(),
+            )),
+        }
+    }
+}
+
+fn apply(function: &Function, x: f64, span: Span) -> EvaluationResult<f64> {
+    let value = match function {
+        Function::Sin => x.sin(),
+        Function::Cos => x.cos(),
+        Function::Tan => x.tan(),
+        Function::Sec => x.cos().recip(),
+        Function::Csc => x.sin().recip(),
+        Function::Cot => x.tan().recip(),
+        Function::ArcSin => x.asin(),
+        Function::ArcCos => x.acos(),
+        Function::ArcTan => x.atan(),
+        Function::Sinh => x.sinh(),
+        Function::Cosh => x.cosh(),
+        Function::Tanh => x.tanh(),
+        Function::Sech => x.cosh().recip(),
+        Function::Csch => x.sinh().recip(),
+        Function::Coth => x.tanh().recip(),
+        Function::Exp => x.exp(),
+        Function::Log => x.log10(),
+        Function::Ln => x.ln(),
+        _ => {
+            return Err(EvaluationError::new(
+                EvaluationErrorKind::Unsupported(function.to_asciimath()),
+                span,
+            ))
+        }
+    };
+
+    Ok(value)
+}
+
+fn is_integer(value: f64) -> bool {
+    value.is_finite() && value.fract() == 0.0
+}
+
+/// Returns the error for a missing operand after the element before the given index
+fn missing_operand(elements: &[Element], index: usize) -> EvaluationError {
+    let span = index
+        .checked_sub(1)
+        .and_then(|i| elements.get(i))
+        .map(Element::span)
+        .unwrap_or_default();
+
+    EvaluationError::new(EvaluationErrorKind::MissingOperand, span)
+}
@@ -0,0 +1,768 @@
+use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
+use crate::elements::group::{
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
+};
+use crate::elements::literal::{Literal, Number, PlainText, Symbol};
+use crate::elements::special::{
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
+};
+use crate::elements::Element;
+use crate::tokens::{
+    Accent, Arrow, CustomSymbol, FontCommand, Function, Greek, Grouping, Logical, Misc, Operation,
+    Relation,
+};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Trait to convert the given object back into AsciiMath source.
+///
+/// The output is canonical: every element is written with one fixed spelling and
+/// elements are separated by single spaces. Parsing the output of an expression that
+/// was created by `parse` results in an equal expression.
+pub trait ToAsciiMath {
+    fn to_asciimath(&self) -> String;
+}
+
+impl ToAsciiMath for Literal {
+    fn to_asciimath(&self) -> String {
+        match self {
+            Literal::Text(t) => t.to_asciimath(),
+            Literal::Symbol(s) => s.to_asciimath(),
+            Literal::Number(n) => n.to_asciimath(),
+            Literal::Greek(g, _) => g.to_asciimath(),
+            Literal::FontCommand(f, _) => f.to_asciimath(),
+            Literal::Relation(r, _) => r.to_asciimath(),
+            Literal::Function(f, _) => f.to_asciimath(),
+            Literal::Logical(l, _) => l.to_asciimath(),
+            Literal::Arrow(a, _) => a.to_asciimath(),
+            Literal::Misc(m, _) => m.to_asciimath(),
+            Literal::Operation(o, _) => o.to_asciimath(),
+            Literal::Custom(c, _) => c.to_asciimath(),
+            Literal::NewLine(_) => "\\\n".to_string(),
+        }
+    }
+}
+
+impl ToAsciiMath for CustomSymbol {
+    fn to_asciimath(&self) -> String {
+        self.name.clone()
+    }
+}
+
+impl ToAsciiMath for Greek {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Greek::Alpha => "alpha",
+            Greek::Beta => "beta",
+            Greek::Gamma => "gamma",
+            Greek::BigGamma => "Gamma",
+            Greek::Delta => "delta",
+            Greek::BigDelta => "Delta",
+            Greek::Epsilon => "epsilon",
+            Greek::VarEpsilon => "varepsilon",
+            Greek::Zeta => "zeta",
+            Greek::Eta => "eta",
+            Greek::Theta => "theta",
+            Greek::BigTheta => "Theta",
+            Greek::VarTheta => "vartheta",
+            Greek::Iota => "iota",
+            Greek::Kappa => "kappa",
+            Greek::Lambda => "lambda",
+            Greek::BigLambda => "Lambda",
+            Greek::Mu => "mu",
+            Greek::Nu => "nu",
+            Greek::Xi => "xi",
+            Greek::BigXi => "Xi",
+            Greek::Pi => "pi",
+            Greek::BigPi => "Pi",
+            Greek::Rho => "rho",
+            Greek::Sigma => "sigma",
+            Greek::BigSigma => "Sigma",
+            Greek::Tau => "tau",
+            Greek::Upsilon => "upsilon",
+            Greek::Phi => "phi",
+            Greek::BigPhi => "Phi",
+            Greek::VarPhi => "varphi",
+            Greek::Chi => "chi",
+            Greek::Psi => "psi",
+            Greek::BigPsi => "Psi",
+            Greek::Omega => "omega",
+            Greek::BigOmega => "Omega",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for PlainText {
+    fn to_asciimath(&self) -> String {
+        // text containing quotes can only be written as text(...)
+        let text = if self.text.contains('"') && !self.text.contains(')') {
+            format!("text({})", self.text)
+        } else {
+            format!("\"{}\"", self.text)
+        };
+        if let Some(formatting) = &self.formatting {
+            format!("{} {}", formatting.to_asciimath(), text)
+        } else {
+            text
+        }
+    }
+}
+
+impl ToAsciiMath for FontCommand {
+    fn to_asciimath(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToAsciiMath for Symbol {
+    fn to_asciimath(&self) -> String {
+        self.symbol.clone()
+    }
+}
+
+impl ToAsciiMath for Number {
+    fn to_asciimath(&self) -> String {
+        self.number.clone()
+    }
+}
+
+impl ToAsciiMath for Relation {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Relation::Eq => "=",
+            Relation::Ne => "!=",
+            Relation::Lt => "<",
+            Relation::Gt => ">",
+            Relation::Le => "<=",
+            Relation::Ge => ">=",
+            Relation::Prec => "-<",
+            Relation::PrecEq => "-<=",
+            Relation::Succ => ">-",
+            Relation::SuccEq => ">-=",
+            Relation::In => "in",
+            Relation::NotIn => "!in",
+            Relation::SubSet => "sub",
+            Relation::SupSet => "sup",
+            Relation::SubSetEq => "sube",
+            Relation::SupSetEq => "supe",
+            Relation::Equiv => "-=",
+            Relation::Cong => "~=",
+            Relation::Approx => "~~",
+            Relation::PropTo => "prop",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Function {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Sec => "sec",
+            Function::Csc => "csc",
+            Function::Cot => "cot",
+            Function::ArcSin => "arcsin",
+            Function::ArcCos => "arccos",
+            Function::ArcTan => "arctan",
+            Function::Sinh => "sinh",
+            Function::Cosh => "cosh",
+            Function::Tanh => "tanh",
+            Function::Sech => "sech",
+            Function::Csch => "csch",
+            Function::Coth => "coth",
+            Function::Exp => "exp",
+            Function::Log => "log",
+            Function::Ln => "ln",
+            Function::Det => "det",
+            Function::Dim => "dim",
+            Function::Mod => "mod",
+            Function::Gcd => "gcd",
+            Function::Lcm => "lcm",
+            Function::Lub => "lub",
+            Function::Glb => "glb",
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::F => "f",
+            Function::G => "g",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Logical {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Logical::And => "and",
+            Logical::Or => "or",
+            Logical::Not => "not",
+            Logical::Implies => "=>",
+            Logical::If => "if",
+            Logical::Iff => "<=>",
+            Logical::ForAll => "AA",
+            Logical::Exists => "EE",
+            Logical::Bot => "_|_",
+            Logical::Top => "TT",
+            Logical::VDash => "|--",
+            Logical::Models => "|==",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Arrow {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Arrow::UpArrow => "uarr",
+            Arrow::DownArrow => "darr",
+            Arrow::RightArrow => "rarr",
+            Arrow::To => "->",
+            Arrow::RightArrowTail => ">->",
+            Arrow::TwoHeadRightArrow => "->>",
+            Arrow::TwoHeadRightArrowTail => ">->>",
+            Arrow::MapsTo => "|->",
+            Arrow::LeftArrow => "larr",
+            Arrow::LeftRightArrow => "harr",
+            Arrow::BigRightArrow => "rArr",
+            Arrow::BigLeftArrow => "lArr",
+            Arrow::BigLeftRightArrow => "hArr",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Misc {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Misc::AsciiFrac => "/",
+            Misc::LatexFrac => "frac",
+            Misc::Sub => "_",
+            Misc::Pow => "^",
+            Misc::Sqrt => "sqrt",
+            Misc::Root => "root",
+            Misc::Int => "int",
+            Misc::OInt => "oint",
+            Misc::Del => "del",
+            Misc::Grad => "grad",
+            Misc::PlusMinus => "pm",
+            Misc::EmptySet => "O/",
+            Misc::Infty => "oo",
+            Misc::Aleph => "aleph",
+            Misc::Therefore => ":.",
+            Misc::Because => ":'",
+            Misc::PLDots => "|...|",
+            Misc::PCDots => "|cdots|",
+            Misc::VDots => "vdots",
+            Misc::DDots => "ddots",
+            Misc::EPipes => "|\\ |",
+            Misc::EQuad => "|quad|",
+            Misc::Angle => "/_",
+            Misc::Frown => "frown",
+            Misc::Triangle => "triangle",
+            Misc::Diamond => "diamond",
+            Misc::Square => "square",
+            Misc::LFloor => "|__",
+            Misc::RFloor => "rfloor",
+            Misc::LCeiling => "|~",
+            Misc::RCeiling => "~|",
+            Misc::Complex => "CC",
+            Misc::Natural => "NN",
+            Misc::Rational => "QQ",
+            Misc::Real => "RR",
+            Misc::Integer => "ZZ",
+            Misc::LatexText => "text",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Operation {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Operation::Plus => "+",
+            Operation::Minus => "-",
+            Operation::CDot => "*",
+            Operation::Ast => "**",
+            Operation::Star => "***",
+            Operation::Slash => "//",
+            Operation::Backslash => "setminus",
+            Operation::Times => "xx",
+            Operation::Div => "-:",
+            Operation::LTimes => "|><",
+            Operation::RTimes => "rtimes",
+            Operation::Bowtie => "|><|",
+            Operation::Circ => "@",
+            Operation::OPlus => "o+",
+            Operation::OTimes => "ox",
+            Operation::ODot => "o.",
+            Operation::Sum => "sum",
+            Operation::Prod => "prod",
+            Operation::Lim => "lim",
+            Operation::BigLim => "Lim",
+            Operation::Sup => "sup",
+            Operation::Inf => "inf",
+            Operation::Wedge => "^^",
+            Operation::BidWedge => "^^^",
+            Operation::Vee => "vv",
+            Operation::BigVee => "vvv",
+            Operation::Cap => "nn",
+            Operation::BigCap => "nnn",
+            Operation::Cup => "uu",
+            Operation::BigCup => "uuu",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Grouping {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            Grouping::RParen => "(",
+            Grouping::LParen => ")",
+            Grouping::RBracket => "[",
+            Grouping::LBracket => "]",
+            Grouping::RBrace => "{",
+            Grouping::LBrace => "}",
+            Grouping::RBraceHidden => "{:",
+            Grouping::LBraceHidden => ":}",
+            Grouping::LAngle => "(:",
+            Grouping::RAngle => ":)",
+            Grouping::LXPar => "{: x )",
+            Grouping::RXPar => "( x :}",
+            Grouping::Abs => "abs",
+            Grouping::Floor => "floor",
+            Grouping::Ceil => "ceil",
+            Grouping::Norm => "norm",
+            Grouping::MSep => ",",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for Accent {
+    fn to_asciimath(&self) -> String {
+        match self {
+            Accent::Hat => "hat".to_string(),
+            Accent::Overline => "bar".to_string(),
+            Accent::Underline => "ul".to_string(),
+            Accent::Vec => "vec".to_string(),
+            Accent::Dot => "dot".to_string(),
+            Accent::DDot => "ddot".to_string(),
+            Accent::OverSet => "overset".to_string(),
+            Accent::UnderSet => "underset".to_string(),
+            Accent::UnderBrace => "ubrace".to_string(),
+            Accent::OverBrace => "obrace".to_string(),
+            Accent::Color(color) => format!("color({})", color),
+            Accent::Cancel => "cancel".to_string(),
+        }
+    }
+}
+
+impl ToAsciiMath for OverSet {
+    fn to_asciimath(&self) -> String {
+        format!(
+            "overset {} {}",
+            self.top.to_asciimath(),
+            self.bottom.to_asciimath()
+        )
+    }
+}
+
+impl ToAsciiMath for UnderSet {
+    fn to_asciimath(&self) -> String {
+        format!(
+            "underset {} {}",
+            self.bottom.to_asciimath(),
+            self.top.to_asciimath()
+        )
+    }
+}
+
+impl ToAsciiMath for Color {
+    fn to_asciimath(&self) -> String {
+        format!("color({}) {}", self.color, self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for GenericAccent {
+    fn to_asciimath(&self) -> String {
+        format!(
+            "{} {}",
+            self.accent.to_asciimath(),
+            self.inner.to_asciimath()
+        )
+    }
+}
+
+impl ToAsciiMath for Group {
+    fn to_asciimath(&self) -> String {
+        match self {
+            Group::Vector(v) => v.to_asciimath(),
+            Group::Cases(c) => c.to_asciimath(),
+            Group::MSep(_) => ",".to_string(),
+            Group::Parentheses(p) => p.to_asciimath(),
+            Group::Brackets(b) => b.to_asciimath(),
+            Group::Braces(b) => b.to_asciimath(),
+            Group::Angles(a) => a.to_asciimath(),
+            Group::XGroup(x) => x.to_asciimath(),
+            Group::Abs(a) => a.to_asciimath(),
+            Group::Floor(f) => f.to_asciimath(),
+            Group::Ceil(c) => c.to_asciimath(),
+            Group::Norm(n) => n.to_asciimath(),
+            Group::Fenced(f) => f.to_asciimath(),
+            Group::Matrix(m) => m.to_asciimath(),
+            Group::NonEnclosed(ne) => ne.to_asciimath(),
+        }
+    }
+}
+
+impl ToAsciiMath for Parentheses {
+    fn to_asciimath(&self) -> String {
+        format!("({})", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Brackets {
+    fn to_asciimath(&self) -> String {
+        format!("[{}]", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Braces {
+    fn to_asciimath(&self) -> String {
+        format!("{{{}}}", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Angles {
+    fn to_asciimath(&self) -> String {
+        format!("(: {} :)", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Fenced {
+    fn to_asciimath(&self) -> String {
+        let left = self.left.to_asciimath();
+        let right = self.right.to_asciimath();
+        // keeps the colons of hidden braces and angles apart from the inner expression
+        let left_padding = if left.ends_with(':') { " " } else { "" };
+        let right_padding = if right.starts_with(':') { " " } else { "" };
+
+        format!(
+            "{}{}{}{}{}",
+            left,
+            left_padding,
+            self.inner.to_asciimath(),
+            right_padding,
+            right
+        )
+    }
+}
+
+impl ToAsciiMath for XGroup {
+    fn to_asciimath(&self) -> String {
+        format!("( x :}} {} {{: x )", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Abs {
+    fn to_asciimath(&self) -> String {
+        format!("abs({})", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Floor {
+    fn to_asciimath(&self) -> String {
+        format!("floor({})", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Ceil {
+    fn to_asciimath(&self) -> String {
+        format!("ceil({})", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Norm {
+    fn to_asciimath(&self) -> String {
+        format!("norm({})", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Matrix {
+    fn to_asciimath(&self) -> String {
+        format!("[{}]", asciimath_rows(&self.inner, "[", "]"))
+    }
+}
+
+impl ToAsciiMath for Vector {
+    fn to_asciimath(&self) -> String {
+        format!("({})", asciimath_rows(&self.inner, "(", ")"))
+    }
+}
+
+impl ToAsciiMath for Cases {
+    fn to_asciimath(&self) -> String {
+        format!("{{{} :}}", asciimath_rows(&self.inner, "(", ")"))
+    }
+}
+
+impl ToAsciiMath for NonEnclosed {
+    /// Operands that were enclosed in any kind of brackets are stored as `NonEnclosed`
+    /// groups. Braces are used to write them as they are never parsed as matrices.
+    fn to_asciimath(&self) -> String {
+        format!("{{{}}}", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Special {
+    fn to_asciimath(&self) -> String {
+        match self {
+            Special::Sum(s) => s.to_asciimath(),
+            Special::Prod(p) => p.to_asciimath(),
+            Special::Frac(f) => f.to_asciimath(),
+            Special::Pow(p) => p.to_asciimath(),
+            Special::Sub(s) => s.to_asciimath(),
+            Special::Sqrt(s) => s.to_asciimath(),
+            Special::Root(r) => r.to_asciimath(),
+            Special::Integral(i) => i.to_asciimath(),
+            Special::OIntegral(i) => i.to_asciimath(),
+            Special::Limit(l) => l.to_asciimath(),
+            Special::FunctionApplication(f) => f.to_asciimath(),
+        }
+    }
+}
+
+impl ToAsciiMath for Sum {
+    fn to_asciimath(&self) -> String {
+        asciimath_limits("sum", &self.bottom, &self.top)
+    }
+}
+
+impl ToAsciiMath for Prod {
+    fn to_asciimath(&self) -> String {
+        asciimath_limits("prod", &self.bottom, &self.top)
+    }
+}
+
+impl ToAsciiMath for Frac {
+    /// Fractions with a bracketed operand can only be created with `frac` as the
+    /// brackets of `/` operands are removed while parsing.
+    fn to_asciimath(&self) -> String {
+        let is_bracketed = |element: &Element| {
+            matches!(
+                element,
+                Element::Group(Group::Parentheses(_))
+                    | Element::Group(Group::Brackets(_))
+                    | Element::Group(Group::Braces(_))
+            )
+        };
+
+        if is_bracketed(&self.top) || is_bracketed(&self.bottom) {
+            asciimath_frac_command(self)
+        } else {
+            format!(
+                "{} / {}",
+                self.top.to_asciimath(),
+                self.bottom.to_asciimath()
+            )
+        }
+    }
+}
+
+impl ToAsciiMath for Sqrt {
+    fn to_asciimath(&self) -> String {
+        format!("sqrt {}", self.inner.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Root {
+    fn to_asciimath(&self) -> String {
+        format!(
+            "root {} {}",
+            self.base.to_asciimath(),
+            self.inner.to_asciimath()
+        )
+    }
+}
+
+impl ToAsciiMath for FunctionApplication {
+    /// Fractions are written with `frac` as the function would only be applied to the
+    /// numerator of `a / b`.
+    fn to_asciimath(&self) -> String {
+        let argument = match &*self.argument {
+            Element::Special(Special::Frac(f)) => asciimath_frac_command(f),
+            argument => argument.to_asciimath(),
+        };
+
+        format!(
+            "{} {}",
+            asciimath_limits(
+                &self.function.to_asciimath(),
+                &self.subscript,
+                &self.exponent
+            ),
+            argument
+        )
+    }
+}
+
+impl ToAsciiMath for Pow {
+    fn to_asciimath(&self) -> String {
+        format!("{} ^ {}", self.base.to_asciimath(), self.exp.to_asciimath())
+    }
+}
+
+impl ToAsciiMath for Sub {
+    fn to_asciimath(&self) -> String {
+        format!(
+            "{} _ {}",
+            self.base.to_asciimath(),
+            self.lower.to_asciimath()
+        )
+    }
+}
+
+impl ToAsciiMath for Integral {
+    fn to_asciimath(&self) -> String {
+        asciimath_limits("int", &self.bottom, &self.top)
+    }
+}
+
+impl ToAsciiMath for OIntegral {
+    fn to_asciimath(&self) -> String {
+        asciimath_limits("oint", &self.bottom, &self.top)
+    }
+}
+
+impl ToAsciiMath for Limit {
+    fn to_asciimath(&self) -> String {
+        asciimath_limits(&self.operator.to_asciimath(), &self.bottom, &self.top)
+    }
+}
+
+impl ToAsciiMath for LimitOperator {
+    fn to_asciimath(&self) -> String {
+        let inner = match self {
+            LimitOperator::Lim => "lim",
+            LimitOperator::BigLim => "Lim",
+            LimitOperator::Sup => "sup",
+            LimitOperator::Inf => "inf",
+            LimitOperator::Max => "max",
+            LimitOperator::Min => "min",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToAsciiMath for ExpressionAccent {
+    fn to_asciimath(&self) -> String {
+        match self {
+            ExpressionAccent::Generic(g) => g.to_asciimath(),
+            ExpressionAccent::OverSet(o) => o.to_asciimath(),
+            ExpressionAccent::UnderSet(u) => u.to_asciimath(),
+            ExpressionAccent::Color(c) => c.to_asciimath(),
+        }
+    }
+}
+
+impl ToAsciiMath for Expression {
+    /// Recursively converts the Expression back into AsciiMath source.
+    ///
+    /// Example:
+    ///
+    ///```
+    /// use asciimath_rs::format::asciimath::ToAsciiMath;
+    ///
+    /// let expression = asciimath_rs::parse("sum_(i=1)^n i^2");
+    /// let source = expression.to_asciimath();
+    /// assert_eq!(source, "sum _ {i = 1} ^ n i ^ 2");
+    /// assert!(asciimath_rs::parse(source).structurally_eq(&expression));
+    /// ```
+    fn to_asciimath(&self) -> String {
+        self.children
+            .iter()
+            .map(|child| child.to_asciimath())
+            .filter(|child| !child.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl ToAsciiMath for Element {
+    fn to_asciimath(&self) -> String {
+        match self {
+            Element::Special(s) => s.to_asciimath(),
+            Element::Literal(l) => l.to_asciimath(),
+            Element::Group(g) => g.to_asciimath(),
+            Element::Accent(a) => a.to_asciimath(),
+            Element::Null => "".to_string(),
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_asciimath())
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_asciimath())
+    }
+}
+
+/// Creates the AsciiMath for an operator with optional lower and upper limits
+fn asciimath_frac_command(frac: &Frac) -> String {
+    format!(
+        "frac {} {}",
+        frac.top.to_asciimath(),
+        frac.bottom.to_asciimath()
+    )
+}
+
+fn asciimath_limits(
+    operator: &str,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) -> String {
+    let mut source = operator.to_string();
+
+    if let Some(bottom) = bottom {
+        source.push_str(&format!(" _ {}", bottom.to_asciimath()));
+    }
+    if let Some(top) = top {
+        source.push_str(&format!(" ^ {}", top.to_asciimath()));
+    }
+
+    source
+}
+
+/// Creates the rows of a matrix or vector enclosed by the given brackets
+fn asciimath_rows(rows: &[Vec<Expression>], open: &str, close: &str) -> String {
+    rows.iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                .map(|cell| cell.to_asciimath())
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("{}{}{}", open, cells, close)
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,782 @@
+use crate::elements::accent::ExpressionAccent;
+use crate::elements::group::{Cases, Fenced, Group};
+use crate::elements::literal::{Literal, Symbol};
+use crate::elements::special::{Expression, Integral, Limit, LimitOperator, Special};
+use crate::elements::Element;
+use crate::format::asciimath::ToAsciiMath;
+use crate::format::error::{ContentMathMLError, ContentMathMLErrorKind};
+use crate::format::mathml::ToMathML;
+use crate::semantic::precedence::{Operator, PrecedenceTable};
+use crate::semantic::{build, Apply, Binary, Node, Unary};
+use crate::span::Span;
+use crate::tokens::{
+    Arrow, Function, Grouping, Logical, Misc, Operation, Relation, SymbolCategory,
+};
+use htmlescape::encode_minimal;
+
+pub type ContentMathMLResult<T> = Result<T, ContentMathMLError>;
+
+/// Trait to convert the given object into a Content MathML representation.
+///
+/// The conversion uses the semantic tree of an expression to find the operands of each
+/// operator. Constructs without an unambiguous Content MathML counterpart like `oint`,
+/// accents or tuples are reported as `ContentMathMLErrorKind::Unsupported`.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::format::content_mathml::ToContentMathML;
+///
+/// let expression = asciimath_rs::parse("2x + 1");
+/// assert_eq!(
+///     expression.to_content_mathml().unwrap(),
+///     "<apply><plus/><apply><times/><cn>2</cn><ci>x</ci></apply><cn>1</cn></apply>"
+/// );
+/// ```
+pub trait ToContentMathML {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String>;
+}
+
+impl ToContentMathML for Expression {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        build(self, &PrecedenceTable::default())?.to_content_mathml()
+    }
+}
+
+impl ToContentMathML for Node {
+    /// Converts the node. The expressions inside of its operands
+    /// are built with the default precedence table.
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        match self {
+            Node::Operand(e) => e.to_content_mathml(),
+            Node::Unary(u) => u.to_content_mathml(),
+            Node::Binary(b) => b.to_content_mathml(),
+            Node::Apply(a) => a.to_content_mathml(),
+            Node::Tuple(t) => Err(unsupported_node(self, t.span)),
+        }
+    }
+}
+
+impl ToContentMathML for Unary {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        let operator = match &self.operator {
+            Operator::Operation(Operation::Plus) => "<plus/>",
+            Operator::Operation(Operation::Minus) => "<minus/>",
+            Operator::Logical(Logical::Not) => "<not/>",
+            _ => return Err(unsupported_node(&Node::Unary(self.clone()), self.span)),
+        };
+
+        Ok(format!(
+            "<apply>{}{}</apply>",
+            operator,
+            self.operand.to_content_mathml()?
+        ))
+    }
+}
+
+impl ToContentMathML for Binary {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        if matches!(self.operator, Operator::Relation(_)) {
+            return relation_chain(self);
+        }
+        let (operator, associative, swapped) = match binary_operator(&self.operator) {
+            Some(operator) => operator,
+            None => return Err(unsupported_node(&Node::Binary(self.clone()), self.span)),
+        };
+        let mut operands = Vec::new();
+        if associative {
+            flatten(self, &mut operands);
+        } else if swapped {
+            operands = vec![&*self.right, &*self.left];
+        } else {
+            operands = vec![&*self.left, &*self.right];
+        }
+
+        apply(operator, &operands)
+    }
+}
+
+impl ToContentMathML for Apply {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        match &self.operator {
+            Element::Literal(Literal::Function(f, _)) => {
+                function_application(f, None, None, &self.operand, self.span)
+            }
+            Element::Special(Special::Pow(p)) => match &*p.base {
+                Element::Literal(Literal::Function(f, _)) => {
+                    function_application(f, Some(&p.exp), None, &self.operand, self.span)
+                }
+                _ => Err(unsupported(&self.operator)),
+            },
+            Element::Special(Special::Sub(s)) => match &*s.base {
+                Element::Literal(Literal::Function(f, _)) => {
+                    function_application(f, None, Some(&s.lower), &self.operand, self.span)
+                }
+                _ => Err(unsupported(&self.operator)),
+            },
+            Element::Special(Special::Sum(s)) => big_operator(
+                "<sum/>",
+                &self.operator,
+                s.bottom.as_deref(),
+                s.top.as_deref(),
+                &self.operand,
+            ),
+            Element::Special(Special::Prod(p)) => big_operator(
+                "<product/>",
+                &self.operator,
+                p.bottom.as_deref(),
+                p.top.as_deref(),
+                &self.operand,
+            ),
+            Element::Special(Special::Integral(i)) => integral(i, &self.operand),
+            Element::Special(Special::Limit(l)) => limit(l, &self.operand),
+            operator => Err(unsupported(operator)),
+        }
+    }
+}
+
+impl ToContentMathML for Element {
+    fn to_content_mathml(&self) -> ContentMathMLResult<String> {
+        match self {
+            Element::Literal(l) => literal(l, self),
+            Element::Special(s) => special(s, self),
+            Element::Group(g) => group(g, self),
+            Element::Accent(ExpressionAccent::Color(c)) => c.inner.to_content_mathml(),
+            Element::Accent(_) | Element::Null => Err(unsupported(self)),
+        }
+    }
+}
+
+fn literal(literal: &Literal, element: &Element) -> ContentMathMLResult<String> {
+    let content = match literal {
+        // symbols like `!` or `'` are operators without a counterpart
+        Literal::Symbol(s) if is_name(s) => format!("<ci>{}</ci>", encode_minimal(&s.symbol)),
+        Literal::Number(n) => format!("<cn>{}</cn>", encode_minimal(&n.number)),
+        Literal::Greek(g, _) => format!("<ci>{}</ci>", g.to_mathml()),
+        Literal::Custom(c, _) if c.category == SymbolCategory::Identifier => {
+            format!("<ci>{}</ci>", c.mathml)
+        }
+        Literal::Custom(c, _) if c.category == SymbolCategory::Number => {
+            format!("<cn>{}</cn>", c.mathml)
+        }
+        Literal::Function(f, _) => match function(f) {
+            Some(function) => function,
+            None => return Err(unsupported(element)),
+        },
+        Literal::Misc(m, _) => {
+            let constant = match m {
+                Misc::Infty => "<infinity/>",
+                Misc::EmptySet => "<emptyset/>",
+                Misc::Complex => "<complexes/>",
+                Misc::Natural => "<naturalnumbers/>",
+                Misc::Rational => "<rationals/>",
+                Misc::Real => "<reals/>",
+                Misc::Integer => "<integers/>",
+                _ => return Err(unsupported(element)),
+            };
+            constant.to_string()
+        }
+        _ => return Err(unsupported(element)),
+    };
+
+    Ok(content)
+}
+
+fn special(special: &Special, element: &Element) -> ContentMathMLResult<String> {
+    match special {
+        Special::Frac(f) => apply_elements("<divide/>", &[&f.top, &f.bottom]),
+        Special::Pow(p) => apply_elements("<power/>", &[&p.base, &p.exp]),
+        // indexed identifiers like `x_i` are identifiers with presentation markup
+        Special::Sub(s) => match &*s.base {
+            Element::Literal(Literal::Symbol(symbol)) if is_name(symbol) => {
+                Ok(format!("<ci>{}</ci>", s.to_mathml()))
+            }
+            Element::Literal(Literal::Greek(_, _)) => Ok(format!("<ci>{}</ci>", s.to_mathml())),
+            _ => Err(unsupported(element)),
+        },
+        Special::Sqrt(s) => apply_elements("<root/>", &[&s.inner]),
+        Special::Root(r) => Ok(format!(
+            "<apply><root/><degree>{}</degree>{}</apply>",
+            r.base.to_content_mathml()?,
+            r.inner.to_content_mathml()?
+        )),
+        Special::FunctionApplication(f) => {
+            let argument = node(&f.argument)?;
+            function_application(
+                &f.function,
+                f.exponent.as_deref(),
+                f.subscript.as_deref(),
+                &argument,
+                f.span,
+            )
+        }
+        _ => Err(unsupported(element)),
+    }
+}
+
+fn group(group: &Group, element: &Element) -> ContentMathMLResult<String> {
+    match group {
+        Group::Parentheses(_) | Group::NonEnclosed(_) => node(element)?.to_content_mathml(),
+        Group::Fenced(f)
+            if f.left == Grouping::RBraceHidden && f.right == Grouping::LBraceHidden =>
+        {
+            node(element)?.to_content_mathml()
+        }
+        Group::Fenced(f) => interval(f, element),
+        Group::Abs(a) => apply("<abs/>", &[&build_expression(&a.inner)?]),
+        Group::Floor(f) => apply("<floor/>", &[&build_expression(&f.inner)?]),
+        Group::Ceil(c) => apply("<ceiling/>", &[&build_expression(&c.inner)?]),
+        Group::Braces(b) => {
+            if b.inner.children.is_empty() {
+                return Ok("<set/>".to_string());
+            }
+            let items = items(build_expression(&b.inner)?);
+            Ok(format!(
+                "<set>{}</set>",
+                contents(&items.iter().collect::<Vec<_>>())?
+            ))
+        }
+        Group::Brackets(b) => match items(build_expression(&b.inner)?)[..] {
+            [ref start, ref end] => Ok(format!(
+                "<interval closure='closed'>{}{}</interval>",
+                start.to_content_mathml()?,
+                end.to_content_mathml()?
+            )),
+            _ => Err(unsupported(element)),
+        },
+        Group::Matrix(m) => matrix(&m.inner),
+        Group::Vector(v) if v.inner.iter().all(|row| row.len() == 1) => {
+            let mut content = String::new();
+            for row in &v.inner {
+                content.push_str(&build_expression(&row[0])?.to_content_mathml()?);
+            }
+            Ok(format!("<vector>{}</vector>", content))
+        }
+        Group::Vector(v) => matrix(&v.inner),
+        Group::Cases(c) => piecewise(c),
+        _ => Err(unsupported(element)),
+    }
+}
+
+/// Converts intervals with mixed delimiters like `(a, b]`
+fn interval(fenced: &Fenced, element: &Element) -> ContentMathMLResult<String> {
+    let closure = match (&fenced.left, &fenced.right) {
+        (Grouping::RParen, Grouping::LParen) => "open",
+        (Grouping::RParen, Grouping::LBracket) => "open-closed",
+        (Grouping::RBracket, Grouping::LParen) => "closed-open",
+        (Grouping::RBracket, Grouping::LBracket) => "closed",
+        _ => return Err(unsupported(element)),
+    };
+    match items(build_expression(&fenced.inner)?)[..] {
+        [ref start, ref end] => Ok(format!(
+            "<interval closure='{}'>{}{}</interval>",
+            closure,
+            start.to_content_mathml()?,
+            end.to_content_mathml()?
+        )),
+        _ => Err(unsupported(element)),
+    }
+}
+
+fn matrix(rows: &[Vec<Expression>]) -> ContentMathMLResult<String> {
+    let mut content = String::new();
+
+    for row in rows {
+        content.push_str("<matrixrow>");
+        for cell in row {
+            content.push_str(&build_expression(cell)?.to_content_mathml()?);
+        }
+        content.push_str("</matrixrow>");
+    }
+
+    Ok(format!("<matrix>{}</matrix>", content))
+}
+
+/// Converts cases into pieces of the value in the first column and the
+/// condition in the second one. A leading `if` of the condition is removed.
+fn piecewise(cases: &Cases) -> ContentMathMLResult<String> {
+    let mut content = String::new();
+
+    for row in &cases.inner {
+        let (value, condition) = match &row[..] {
+            [value, condition] => (value, condition),
+            _ => {
+                return Err(unsupported(&Element::Group(Group::Cases(cases.clone()))));
+            }
+        };
+        let value = build_expression(value)?.to_content_mathml()?;
+        let mut condition = condition.clone();
+        match condition.children.first() {
+            Some(Element::Literal(Literal::Logical(Logical::If, _))) => {
+                condition.children.remove(0);
+            }
+            Some(Element::Literal(Literal::Text(t))) if t.text.trim() == "otherwise" => {
+                content.push_str(&format!("<otherwise>{}</otherwise>", value));
+                continue;
+            }
+            Some(Element::Literal(Literal::Text(t))) if t.text.trim() == "if" => {
+                condition.children.remove(0);
+            }
+            _ => {}
+        }
+        content.push_str(&format!(
+            "<piece>{}{}</piece>",
+            value,
+            build_expression(&condition)?.to_content_mathml()?
+        ));
+    }
+
+    Ok(format!("<piecewise>{}</piecewise>", content))
+}
+
+/// Converts a function applied to an argument. Comma separated arguments like
+/// in `max(a, b)` are the arguments of the function, an exponent like in `sin^2 x`
+/// is the power of the application and only the subscript of `log` is supported.
+fn function_application(
+    function: &Function,
+    exponent: Option<&Element>,
+    subscript: Option<&Element>,
+    argument: &Node,
+    span: Span,
+) -> ContentMathMLResult<String> {
+    let head = function.to_asciimath();
+    let mut content = match self::function(function) {
+        Some(content) => content,
+        None => return Err(unsupported_source(head, span)),
+    };
+    if let Some(subscript) = subscript {
+        if *function != Function::Log {
+            return Err(unsupported_source(
+                format!("{}_{}", head, subscript.to_asciimath()),
+                span,
+            ));
+        }
+        content.push_str(&format!(
+            "<logbase>{}</logbase>",
+            subscript.to_content_mathml()?
+        ));
+    }
+    let arguments = match argument {
+        Node::Tuple(t) => t.items.iter().collect(),
+        argument => vec![argument],
+    };
+    content = format!("<apply>{}{}</apply>", content, contents(&arguments)?);
+
+    match exponent {
+        Some(exponent) => Ok(format!(
+            "<apply><power/>{}{}</apply>",
+            content,
+            exponent.to_content_mathml()?
+        )),
+        None => Ok(content),
+    }
+}
+
+/// Returns the Content MathML of a function that is applied to its arguments
+fn function(function: &Function) -> Option<String> {
+    let name = match function {
+        Function::Exp => "exp",
+        Function::Sin => "sin",
+        Function::Cos => "cos",
+        Function::Tan => "tan",
+        Function::Sec => "sec",
+        Function::Csc => "csc",
+        Function::Cot => "cot",
+        Function::Sinh => "sinh",
+        Function::Cosh => "cosh",
+        Function::Tanh => "tanh",
+        Function::Sech => "sech",
+        Function::Csch => "csch",
+        Function::Coth => "coth",
+        Function::ArcSin => "arcsin",
+        Function::ArcCos => "arccos",
+        Function::ArcTan => "arctan",
+        Function::Ln => "ln",
+        Function::Log => "log",
+        Function::Max => "max",
+        Function::Min => "min",
+        Function::Gcd => "gcd",
+        Function::Lcm => "lcm",
+        Function::Det => "determinant",
+        Function::F => return Some("<ci type='function'>f</ci>".to_string()),
+        Function::G => return Some("<ci type='function'>g</ci>".to_string()),
+        Function::Glb | Function::Lub | Function::Mod | Function::Dim => return None,
+    };
+
+    Some(format!("<{}/>", name))
+}
+
+/// Converts sums and products with a lower limit like `i=1` or a domain like `i in S`
+fn big_operator(
+    operator: &str,
+    element: &Element,
+    bottom: Option<&Element>,
+    top: Option<&Element>,
+    operand: &Node,
+) -> ContentMathMLResult<String> {
+    let bottom = match bottom {
+        Some(bottom) => node(bottom)?,
+        None => return Err(unsupported_application(element, operand)),
+    };
+    let qualifiers = match (&bottom, top) {
+        (Node::Binary(b), top) if b.operator == Operator::Relation(Relation::Eq) => {
+            let mut qualifiers = format!(
+                "{}<lowlimit>{}</lowlimit>",
+                bound_variable(&b.left, element, operand)?,
+                b.right.to_content_mathml()?
+            );
+            if let Some(top) = top {
+                qualifiers.push_str(&format!("<uplimit>{}</uplimit>", top.to_content_mathml()?));
+            }
+            qualifiers
+        }
+        (Node::Binary(b), None) if b.operator == Operator::Relation(Relation::In) => format!(
+            "{}<domainofapplication>{}</domainofapplication>",
+            bound_variable(&b.left, element, operand)?,
+            b.right.to_content_mathml()?
+        ),
+        _ => return Err(unsupported_application(element, operand)),
+    };
+
+    Ok(format!(
+        "<apply>{}{}{}</apply>",
+        operator,
+        qualifiers,
+        operand.to_content_mathml()?
+    ))
+}
+
+/// Converts an integral whose integrand ends with the differential like `x^2 dx`
+fn integral(integral: &Integral, operand: &Node) -> ContentMathMLResult<String> {
+    let element = Element::Special(Special::Integral(integral.clone()));
+    let (integrand, variable) = match differential(operand) {
+        Some(differential) => differential,
+        None => return Err(unsupported_application(&element, operand)),
+    };
+    let integrand = match integrand {
+        Some(integrand) => integrand.to_content_mathml()?,
+        None => "<cn>1</cn>".to_string(),
+    };
+    let limits = match (&integral.bottom, &integral.top) {
+        (Some(bottom), Some(top)) => format!(
+            "<lowlimit>{}</lowlimit><uplimit>{}</uplimit>",
+            bottom.to_content_mathml()?,
+            top.to_content_mathml()?
+        ),
+        (Some(domain), None) => format!(
+            "<domainofapplication>{}</domainofapplication>",
+            domain.to_content_mathml()?
+        ),
+        (None, None) => String::new(),
+        (None, Some(_)) => return Err(unsupported_application(&element, operand)),
+    };
+
+    Ok(format!(
+        "<apply><int/><bvar>{}</bvar>{}{}</apply>",
+        variable.to_content_mathml()?,
+        limits,
+        integrand
+    ))
+}
+
+/// Splits `f dx` into the integrand and the variable of integration.
+/// The integrand is `None` for integrals like `int dx`.
+fn differential(operand: &Node) -> Option<(Option<&Node>, &Node)> {
+    let is_d = |node: &Node| matches!(node, Node::Operand(Element::Literal(Literal::Symbol(s))) if s.symbol == "d");
+    let product = match operand {
+        Node::Binary(b) if b.operator == Operator::ImplicitMultiplication => b,
+        _ => return None,
+    };
+    if !is_identifier(&product.right) {
+        return None;
+    }
+    match &*product.left {
+        d if is_d(d) => Some((None, &product.right)),
+        Node::Binary(b) if b.operator == Operator::ImplicitMultiplication && is_d(&b.right) => {
+            Some((Some(&b.left), &product.right))
+        }
+        _ => None,
+    }
+}
+
+/// Converts limits like `lim_(x->0)`
+fn limit(limit: &Limit, operand: &Node) -> ContentMathMLResult<String> {
+    let element = Element::Special(Special::Limit(limit.clone()));
+    let bottom = match (&limit.operator, &limit.bottom, &limit.top) {
+        (LimitOperator::Lim, Some(bottom), None) | (LimitOperator::BigLim, Some(bottom), None) => {
+            node(bottom)?
+        }
+        _ => return Err(unsupported_application(&element, operand)),
+    };
+    match &bottom {
+        Node::Binary(b)
+            if matches!(
+                b.operator,
+                Operator::Arrow(Arrow::To) | Operator::Arrow(Arrow::RightArrow)
+            ) =>
+        {
+            Ok(format!(
+                "<apply><limit/>{}<lowlimit>{}</lowlimit>{}</apply>",
+                bound_variable(&b.left, &element, operand)?,
+                b.right.to_content_mathml()?,
+                operand.to_content_mathml()?
+            ))
+        }
+        _ => Err(unsupported_application(&element, operand)),
+    }
+}
+
+fn bound_variable(
+    variable: &Node,
+    element: &Element,
+    operand: &Node,
+) -> ContentMathMLResult<String> {
+    if is_identifier(variable) {
+        Ok(format!("<bvar>{}</bvar>", variable.to_content_mathml()?))
+    } else {
+        Err(unsupported_application(element, operand))
+    }
+}
+
+fn is_identifier(node: &Node) -> bool {
+    match node {
+        Node::Operand(Element::Literal(Literal::Symbol(s))) => is_name(s),
+        Node::Operand(Element::Literal(Literal::Greek(_, _))) => true,
+        _ => false,
+    }
+}
+
+/// Returns if the symbol is an alphanumeric name that can be an identifier
+fn is_name(symbol: &Symbol) -> bool {
+    symbol.symbol.chars().all(char::is_alphanumeric)
+}
+
+/// Converts chains of relations like `a < b <= c` into the conjunction of each comparison.
+/// Chains of the same relation are a single application like `<apply><lt/>a b c</apply>`.
+fn relation_chain(binary: &Binary) -> ContentMathMLResult<String> {
+    let mut operands = vec![&*binary.right];
+    let mut relations = vec![binary];
+    let mut left = &*binary.left;
+
+    while let Node::Binary(b) = left {
+        if !matches!(b.operator, Operator::Relation(_)) {
+            break;
+        }
+        operands.push(&b.right);
+        relations.push(b);
+        left = &b.left;
+    }
+    operands.push(left);
+    operands.reverse();
+    relations.reverse();
+
+    let mut comparisons = Vec::new();
+    for (i, relation) in relations.iter().enumerate() {
+        let (operator, swapped) = match relation_operator(&relation.operator) {
+            Some(operator) => operator,
+            None => {
+                return Err(unsupported_node(
+                    &Node::Binary((*relation).clone()),
+                    relation.span,
+                ))
+            }
+        };
+        comparisons.push((operator, swapped, operands[i], operands[i + 1]));
+    }
+    if comparisons
+        .iter()
+        .all(|(operator, swapped, _, _)| *operator == comparisons[0].0 && !swapped)
+    {
+        return apply(comparisons[0].0, &operands);
+    }
+    let mut content = String::new();
+    for (operator, swapped, left, right) in comparisons.iter() {
+        let operands = if *swapped {
+            [*right, *left]
+        } else {
+            [*left, *right]
+        };
+        content.push_str(&apply(operator, &operands)?);
+    }
+
+    if relations.len() == 1 {
+        Ok(content)
+    } else {
+        Ok(format!("<apply><and/>{}</apply>", content))
+    }
+}
+
+/// Returns the element of a relation and whether its operands are swapped
+/// like for `A supe B` which is written as `B sube A`
+fn relation_operator(operator: &Operator) -> Option<(&'static str, bool)> {
+    let relation = match operator {
+        Operator::Relation(r) => r,
+        _ => return None,
+    };
+    let operator = match relation {
+        Relation::Eq => ("<eq/>", false),
+        Relation::Ne => ("<neq/>", false),
+        Relation::Lt => ("<lt/>", false),
+        Relation::Gt => ("<gt/>", false),
+        Relation::Le => ("<leq/>", false),
+        Relation::Ge => ("<geq/>", false),
+        Relation::In => ("<in/>", false),
+        Relation::NotIn => ("<notin/>", false),
+        Relation::SubSet => ("<prsubset/>", false),
+        Relation::SubSetEq => ("<subset/>", false),
+        Relation::SupSet => ("<prsubset/>", true),
+        Relation::SupSetEq => ("<subset/>", true),
+        Relation::Equiv => ("<equivalent/>", false),
+        Relation::Approx => ("<approx/>", false),
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+/// Returns the element of an operator, whether it's associative
+/// and whether its operands are swapped like for `a if b`
+fn binary_operator(operator: &Operator) -> Option<(&'static str, bool, bool)> {
+    let operator = match operator {
+        Operator::Operation(Operation::Plus) => ("<plus/>", true, false),
+        Operator::Operation(Operation::Minus) => ("<minus/>", false, false),
+        Operator::Operation(Operation::CDot)
+        | Operator::Operation(Operation::Ast)
+        | Operator::Operation(Operation::Times)
+        | Operator::ImplicitMultiplication => ("<times/>", true, false),
+        Operator::Operation(Operation::Slash) | Operator::Operation(Operation::Div) => {
+            ("<divide/>", false, false)
+        }
+        Operator::Operation(Operation::Backslash) => ("<setdiff/>", false, false),
+        Operator::Operation(Operation::Cap) => ("<intersect/>", true, false),
+        Operator::Operation(Operation::Cup) => ("<union/>", true, false),
+        Operator::Operation(Operation::Circ) => ("<compose/>", true, false),
+        Operator::Logical(Logical::And) => ("<and/>", true, false),
+        Operator::Logical(Logical::Or) => ("<or/>", true, false),
+        Operator::Logical(Logical::Implies) => ("<implies/>", false, false),
+        Operator::Logical(Logical::If) => ("<implies/>", false, true),
+        Operator::Logical(Logical::Iff) => ("<equivalent/>", false, false),
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+/// Collects the operands of nested applications of the same associative operator
+fn flatten<'a>(binary: &'a Binary, operands: &mut Vec<&'a Node>) {
+    for side in [&*binary.left, &*binary.right] {
+        match side {
+            Node::Binary(b) if same_operator(&b.operator, &binary.operator) => flatten(b, operands),
+            node => operands.push(node),
+        }
+    }
+}
+
+fn same_operator(a: &Operator, b: &Operator) -> bool {
+    binary_operator(a).map(|(o, _, _)| o) == binary_operator(b).map(|(o, _, _)| o)
+}
+
+fn apply(operator: &str, operands: &[&Node]) -> ContentMathMLResult<String> {
+    Ok(format!(
+        "<apply>{}{}</apply>",
+        operator,
+        contents(operands)?
+    ))
+}
+
+fn apply_elements(operator: &str, operands: &[&Element]) -> ContentMathMLResult<String> {
+    let mut content = String::new();
+    for operand in operands {
+        content.push_str(&operand.to_content_mathml()?);
+    }
+
+    Ok(format!("<apply>{}{}</apply>", operator, content))
+}
+
+fn contents(nodes: &[&Node]) -> ContentMathMLResult<String> {
+    let mut content = String::new();
+    for node in nodes {
+        content.push_str(&node.to_content_mathml()?);
+    }
+
+    Ok(content)
+}
+
+/// Returns the items of a tuple or the node itself
+fn items(node: Node) -> Vec<Node> {
+    match node {
+        Node::Tuple(t) => t.items,
+        node => vec![node],
+    }
+}
+
+fn build_expression(expression: &Expression) -> ContentMathMLResult<Node> {
+    Ok(build(expression, &PrecedenceTable::default())?)
+}
+
+/// Builds the semantic tree of a single element which replaces
+/// groups that only group with the tree of their contents
+fn node(element: &Element) -> ContentMathMLResult<Node> {
+    build_expression(&Expression {
+        children: vec![element.clone()],
+        span: element.span(),
+    })
+}
+
+fn unsupported(element: &Element) -> ContentMathMLError {
+    unsupported_source(element.to_asciimath(), element.span())
+}
+
+fn unsupported_node(node: &Node, span: Span) -> ContentMathMLError {
+    unsupported_source(node_source(node), span)
+}
+
+fn unsupported_source(source: String, span: Span) -> ContentMathMLError {
+    ContentMathMLError::new(ContentMathMLErrorKind::Unsupported(source), span)
+}
+
+/// Reports a big operator applied to its operand
+fn unsupported_application(element: &Element, operand: &Node) -> ContentMathMLError {
+    unsupported_source(
+        format!("{} {}", element.to_asciimath(), node_source(operand)),
+        element.span().merge(&operand.span()),
+    )
+}
+
+/// Returns the AsciiMath source of a node
+fn node_source(node: &Node) -> String {
+    match node {
+        Node::Operand(e) => e.to_asciimath(),
+        Node::Unary(u) => format!(
+            "{}{}",
+            operator_source(&u.operator),
+            node_source(&u.operand)
+        ),
+        Node::Binary(b) => format!(
+            "{} {} {}",
+            node_source(&b.left),
+            operator_source(&b.operator),
+            node_source(&b.right)
+        ),
+        Node::Apply(a) => format!("{} {}", a.operator.to_asciimath(), node_source(&a.operand)),
+        Node::Tuple(t) => format!(
+            "({})",
+            t.items
+                .iter()
+                .map(node_source)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn operator_source(operator: &Operator) -> String {
+    match operator {
+        Operator::Operation(o) => o.to_asciimath(),
+        Operator::Relation(r) => r.to_asciimath(),
+        Operator::Logical(l) => l.to_asciimath(),
+        Operator::Arrow(a) => a.to_asciimath(),
+        Operator::Custom(name) => name.clone(),
+        Operator::ImplicitMultiplication => String::new(),
+    }
+}
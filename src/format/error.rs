@@ -0,0 +1,52 @@
+use crate::semantic::error::{SemanticError, SemanticErrorKind};
+use crate::span::Span;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// An error that was encountered while converting an expression into Content MathML.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentMathMLError {
+    pub kind: ContentMathMLErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentMathMLErrorKind {
+    /// A construct without an unambiguous Content MathML counterpart
+    /// like `oint` or `hat x`. Contains the AsciiMath source of the construct.
+    Unsupported(String),
+    /// The semantic tree of the expression couldn't be built
+    Semantic(SemanticErrorKind),
+}
+
+impl ContentMathMLError {
+    pub fn new(kind: ContentMathMLErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl From<SemanticError> for ContentMathMLError {
+    fn from(error: SemanticError) -> Self {
+        Self::new(ContentMathMLErrorKind::Semantic(error.kind), error.span)
+    }
+}
+
+impl Display for ContentMathMLError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}: ", self.span.start, self.span.end)?;
+        match &self.kind {
+            ContentMathMLErrorKind::Unsupported(construct) => {
+                write!(f, "'{}' has no Content MathML counterpart", construct)
+            }
+            ContentMathMLErrorKind::Semantic(SemanticErrorKind::MissingOperand) => {
+                write!(f, "missing operand")
+            }
+            ContentMathMLErrorKind::Semantic(SemanticErrorKind::UnexpectedElement(element)) => {
+                write!(f, "unexpected '{}'", element)
+            }
+        }
+    }
+}
+
+impl Error for ContentMathMLError {}
@@ -0,0 +1,788 @@
+use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
+use crate::elements::group::{
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
+};
+use crate::elements::literal::{Literal, Number, PlainText, Symbol};
+use crate::elements::special::{
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
+};
+use crate::elements::Element;
+use crate::tokens::{
+    Accent, Arrow, CustomSymbol, FontCommand, Function, Greek, Grouping, Logical, Misc, Operation,
+    Relation,
+};
+
+/// Trait to convert the given object into a LaTeX (math mode) representation.
+pub trait ToLatex {
+    fn to_latex(&self) -> String;
+}
+
+impl ToLatex for Literal {
+    fn to_latex(&self) -> String {
+        match self {
+            Literal::Text(t) => t.to_latex(),
+            Literal::Symbol(s) => s.to_latex(),
+            Literal::Number(n) => n.to_latex(),
+            Literal::Greek(g, _) => g.to_latex(),
+            Literal::FontCommand(f, _) => f.to_latex(),
+            Literal::Relation(r, _) => r.to_latex(),
+            Literal::Function(f, _) => f.to_latex(),
+            Literal::Logical(l, _) => l.to_latex(),
+            Literal::Arrow(a, _) => a.to_latex(),
+            Literal::Misc(m, _) => m.to_latex(),
+            Literal::Operation(o, _) => o.to_latex(),
+            Literal::Custom(c, _) => c.to_latex(),
+            Literal::NewLine(_) => "\\\\".to_string(),
+        }
+    }
+}
+
+impl ToLatex for CustomSymbol {
+    fn to_latex(&self) -> String {
+        self.latex.clone()
+    }
+}
+
+impl ToLatex for Greek {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Greek::Alpha => "\\alpha",
+            Greek::Beta => "\\beta",
+            Greek::Gamma => "\\gamma",
+            Greek::BigGamma => "\\Gamma",
+            Greek::Delta => "\\delta",
+            Greek::BigDelta => "\\Delta",
+            Greek::Epsilon => "\\epsilon",
+            Greek::VarEpsilon => "\\varepsilon",
+            Greek::Zeta => "\\zeta",
+            Greek::Eta => "\\eta",
+            Greek::Theta => "\\theta",
+            Greek::BigTheta => "\\Theta",
+            Greek::VarTheta => "\\vartheta",
+            Greek::Iota => "\\iota",
+            Greek::Kappa => "\\kappa",
+            Greek::Lambda => "\\lambda",
+            Greek::BigLambda => "\\Lambda",
+            Greek::Mu => "\\mu",
+            Greek::Nu => "\\nu",
+            Greek::Xi => "\\xi",
+            Greek::BigXi => "\\Xi",
+            Greek::Pi => "\\pi",
+            Greek::BigPi => "\\Pi",
+            Greek::Rho => "\\rho",
+            Greek::Sigma => "\\sigma",
+            Greek::BigSigma => "\\Sigma",
+            Greek::Tau => "\\tau",
+            Greek::Upsilon => "\\upsilon",
+            Greek::Phi => "\\phi",
+            Greek::BigPhi => "\\Phi",
+            Greek::VarPhi => "\\varphi",
+            Greek::Chi => "\\chi",
+            Greek::Psi => "\\psi",
+            Greek::BigPsi => "\\Psi",
+            Greek::Omega => "\\omega",
+            Greek::BigOmega => "\\Omega",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for PlainText {
+    fn to_latex(&self) -> String {
+        if let Some(formatting) = &self.formatting {
+            format!("{}{{{}}}", formatting.to_latex(), escape(&self.text))
+        } else {
+            format!("\\text{{{}}}", escape_text(&self.text))
+        }
+    }
+}
+
+impl ToLatex for FontCommand {
+    fn to_latex(&self) -> String {
+        match self {
+            FontCommand::Big => "\\mathbf".to_string(),
+            FontCommand::BigOutline => "\\mathbb".to_string(),
+            FontCommand::Cursive => "\\mathcal".to_string(),
+            FontCommand::TText => "\\mathtt".to_string(),
+            FontCommand::Fr => "\\mathfrak".to_string(),
+            FontCommand::SansSerif => "\\mathsf".to_string(),
+        }
+    }
+}
+
+impl ToLatex for Symbol {
+    fn to_latex(&self) -> String {
+        escape(&self.symbol)
+    }
+}
+
+impl ToLatex for Number {
+    fn to_latex(&self) -> String {
+        self.number.clone()
+    }
+}
+
+impl ToLatex for Relation {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Relation::Eq => "=",
+            Relation::Ne => "\\ne",
+            Relation::Lt => "<",
+            Relation::Gt => ">",
+            Relation::Le => "\\le",
+            Relation::Ge => "\\ge",
+            Relation::Prec => "\\prec",
+            Relation::Succ => "\\succ",
+            Relation::PrecEq => "\\preceq",
+            Relation::SuccEq => "\\succeq",
+            Relation::In => "\\in",
+            Relation::NotIn => "\\notin",
+            Relation::SubSet => "\\subset",
+            Relation::SupSet => "\\supset",
+            Relation::SubSetEq => "\\subseteq",
+            Relation::SupSetEq => "\\supseteq",
+            Relation::Equiv => "\\equiv",
+            Relation::Cong => "\\cong",
+            Relation::Approx => "\\approx",
+            Relation::PropTo => "\\propto",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Function {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Function::Exp => "\\exp",
+            Function::Sin => "\\sin",
+            Function::Max => "\\max",
+            Function::Min => "\\min",
+            Function::Glb => "\\operatorname{glb}",
+            Function::G => "g",
+            Function::Lub => "\\operatorname{lub}",
+            Function::Lcm => "\\operatorname{lcm}",
+            Function::Gcd => "\\gcd",
+            Function::Mod => "\\operatorname{mod}",
+            Function::Dim => "\\dim",
+            Function::Det => "\\det",
+            Function::Ln => "\\ln",
+            Function::Log => "\\log",
+            Function::Cot => "\\cot",
+            Function::Csc => "\\csc",
+            Function::Sech => "\\operatorname{sech}",
+            Function::Tanh => "\\tanh",
+            Function::Cosh => "\\cosh",
+            Function::ArcSin => "\\arcsin",
+            Function::ArcCos => "\\arccos",
+            Function::ArcTan => "\\arctan",
+            Function::Tan => "\\tan",
+            Function::Cos => "\\cos",
+            Function::F => "f",
+            Function::Sec => "\\sec",
+            Function::Sinh => "\\sinh",
+            Function::Csch => "\\operatorname{csch}",
+            Function::Coth => "\\coth",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Logical {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Logical::And => "\\text{ and }",
+            Logical::Or => "\\text{ or }",
+            Logical::Not => "\\neg",
+            Logical::Implies => "\\Rightarrow",
+            Logical::If => "\\text{ if }",
+            Logical::Iff => "\\Leftrightarrow",
+            Logical::ForAll => "\\forall",
+            Logical::Exists => "\\exists",
+            Logical::Bot => "\\bot",
+            Logical::Top => "\\top",
+            Logical::VDash => "\\vdash",
+            Logical::Models => "\\models",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Arrow {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Arrow::UpArrow => "\\uparrow",
+            Arrow::DownArrow => "\\downarrow",
+            Arrow::RightArrow => "\\rightarrow",
+            Arrow::To => "\\to",
+            Arrow::RightArrowTail => "\\rightarrowtail",
+            Arrow::TwoHeadRightArrow => "\\twoheadrightarrow",
+            Arrow::TwoHeadRightArrowTail => "\\twoheadrightarrowtail",
+            Arrow::MapsTo => "\\mapsto",
+            Arrow::LeftArrow => "\\leftarrow",
+            Arrow::LeftRightArrow => "\\leftrightarrow",
+            Arrow::BigRightArrow => "\\Rightarrow",
+            Arrow::BigLeftArrow => "\\Leftarrow",
+            Arrow::BigLeftRightArrow => "\\Leftrightarrow",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Misc {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Misc::Del => "\\partial",
+            Misc::Grad => "\\nabla",
+            Misc::PlusMinus => "\\pm",
+            Misc::EmptySet => "\\emptyset",
+            Misc::Infty => "\\infty",
+            Misc::Aleph => "\\aleph",
+            Misc::Therefore => "\\therefore",
+            Misc::Because => "\\because",
+            Misc::PLDots => "\\ldots",
+            Misc::PCDots => "\\cdots",
+            Misc::VDots => "\\vdots",
+            Misc::DDots => "\\ddots",
+            Misc::EPipes => "\\ ",
+            Misc::EQuad => "\\quad",
+            Misc::Angle => "\\angle",
+            Misc::Frown => "\\frown",
+            Misc::Triangle => "\\triangle",
+            Misc::Diamond => "\\diamond",
+            Misc::Square => "\\square",
+            Misc::LFloor => "\\lfloor",
+            Misc::RFloor => "\\rfloor",
+            Misc::LCeiling => "\\lceil",
+            Misc::RCeiling => "\\rceil",
+            Misc::Complex => "\\mathbb{C}",
+            Misc::Natural => "\\mathbb{N}",
+            Misc::Rational => "\\mathbb{Q}",
+            Misc::Real => "\\mathbb{R}",
+            Misc::Integer => "\\mathbb{Z}",
+            Misc::AsciiFrac => "/",
+            Misc::LatexFrac => "\\frac",
+            Misc::Sub => "_",
+            Misc::Pow => "^",
+            Misc::Sqrt | Misc::Root => "\\sqrt",
+            Misc::Int => "\\int",
+            Misc::OInt => "\\oint",
+            Misc::LatexText => "\\text",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Operation {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            Operation::Plus => "+",
+            Operation::Minus => "-",
+            Operation::CDot => "\\cdot",
+            Operation::Ast => "\\ast",
+            Operation::Star => "\\star",
+            Operation::Slash => "/",
+            Operation::Backslash => "\\setminus",
+            Operation::Times => "\\times",
+            Operation::Div => "\\div",
+            Operation::LTimes => "\\ltimes",
+            Operation::RTimes => "\\rtimes",
+            Operation::Bowtie => "\\bowtie",
+            Operation::Circ => "\\circ",
+            Operation::OPlus => "\\oplus",
+            Operation::OTimes => "\\otimes",
+            Operation::ODot => "\\odot",
+            Operation::Sum => "\\sum",
+            Operation::Prod => "\\prod",
+            Operation::Lim => "\\lim",
+            Operation::BigLim => "\\operatorname*{Lim}",
+            Operation::Sup => "\\sup",
+            Operation::Inf => "\\inf",
+            Operation::Wedge => "\\wedge",
+            Operation::BidWedge => "\\bigwedge",
+            Operation::Vee => "\\vee",
+            Operation::BigVee => "\\bigvee",
+            Operation::Cap => "\\cap",
+            Operation::BigCap => "\\bigcap",
+            Operation::Cup => "\\cup",
+            Operation::BigCup => "\\bigcup",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for Accent {
+    fn to_latex(&self) -> String {
+        match self {
+            Accent::Hat => "\\hat".to_string(),
+            Accent::Overline => "\\overline".to_string(),
+            Accent::Underline => "\\underline".to_string(),
+            Accent::Vec => "\\vec".to_string(),
+            Accent::Dot => "\\dot".to_string(),
+            Accent::DDot => "\\ddot".to_string(),
+            Accent::UnderBrace => "\\underbrace".to_string(),
+            Accent::OverBrace => "\\overbrace".to_string(),
+            Accent::Cancel => "\\cancel".to_string(),
+            Accent::OverSet => "\\overset".to_string(),
+            Accent::UnderSet => "\\underset".to_string(),
+            Accent::Color(_) => "\\textcolor".to_string(),
+        }
+    }
+}
+
+impl ToLatex for OverSet {
+    fn to_latex(&self) -> String {
+        format!("\\overset{}{}", argument(&self.top), argument(&self.bottom))
+    }
+}
+
+impl ToLatex for UnderSet {
+    fn to_latex(&self) -> String {
+        format!(
+            "\\underset{}{}",
+            argument(&self.bottom),
+            argument(&self.top)
+        )
+    }
+}
+
+impl ToLatex for Color {
+    fn to_latex(&self) -> String {
+        if let Some(hex) = hex_color(&self.color) {
+            format!("\\textcolor[HTML]{{{}}}{}", hex, argument(&self.inner))
+        } else {
+            format!(
+                "\\textcolor{{{}}}{}",
+                escape(&self.color),
+                argument(&self.inner)
+            )
+        }
+    }
+}
+
+impl ToLatex for GenericAccent {
+    fn to_latex(&self) -> String {
+        match self.accent {
+            Accent::Hat
+            | Accent::Overline
+            | Accent::Underline
+            | Accent::Vec
+            | Accent::Dot
+            | Accent::DDot
+            | Accent::UnderBrace
+            | Accent::OverBrace
+            | Accent::Cancel => format!("{}{}", self.accent.to_latex(), argument(&self.inner)),
+            _ => self.inner.to_latex(),
+        }
+    }
+}
+
+impl ToLatex for Group {
+    fn to_latex(&self) -> String {
+        match self {
+            Group::Vector(v) => v.to_latex(),
+            Group::Cases(c) => c.to_latex(),
+            Group::MSep(_) => ",".to_string(),
+            Group::Parentheses(p) => p.to_latex(),
+            Group::Brackets(b) => b.to_latex(),
+            Group::Braces(b) => b.to_latex(),
+            Group::Angles(a) => a.to_latex(),
+            Group::XGroup(x) => x.to_latex(),
+            Group::Abs(a) => a.to_latex(),
+            Group::Floor(f) => f.to_latex(),
+            Group::Ceil(c) => c.to_latex(),
+            Group::Norm(n) => n.to_latex(),
+            Group::Fenced(f) => f.to_latex(),
+            Group::Matrix(m) => m.to_latex(),
+            Group::NonEnclosed(ne) => ne.to_latex(),
+        }
+    }
+}
+
+impl ToLatex for Parentheses {
+    fn to_latex(&self) -> String {
+        format!("\\left({}\\right)", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Brackets {
+    fn to_latex(&self) -> String {
+        format!("\\left[{}\\right]", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Braces {
+    fn to_latex(&self) -> String {
+        format!("\\left\\{{{}\\right\\}}", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Angles {
+    fn to_latex(&self) -> String {
+        format!("\\left\\langle {}\\right\\rangle", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Fenced {
+    fn to_latex(&self) -> String {
+        format!(
+            "\\left{}{}\\right{}",
+            latex_delimiter(&self.left),
+            self.inner.to_latex(),
+            latex_delimiter(&self.right)
+        )
+    }
+}
+
+impl ToLatex for XGroup {
+    fn to_latex(&self) -> String {
+        format!("\\left({}\\right.", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Abs {
+    fn to_latex(&self) -> String {
+        format!("\\left|{}\\right|", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Floor {
+    fn to_latex(&self) -> String {
+        format!("\\left\\lfloor {}\\right\\rfloor", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Ceil {
+    fn to_latex(&self) -> String {
+        format!("\\left\\lceil {}\\right\\rceil", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Norm {
+    fn to_latex(&self) -> String {
+        format!("\\left\\|{}\\right\\|", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Matrix {
+    fn to_latex(&self) -> String {
+        format!(
+            "\\begin{{bmatrix}}{}\\end{{bmatrix}}",
+            latex_table(&self.inner)
+        )
+    }
+}
+
+impl ToLatex for Vector {
+    fn to_latex(&self) -> String {
+        format!(
+            "\\begin{{pmatrix}}{}\\end{{pmatrix}}",
+            latex_table(&self.inner)
+        )
+    }
+}
+
+impl ToLatex for Cases {
+    fn to_latex(&self) -> String {
+        format!("\\begin{{cases}}{}\\end{{cases}}", latex_table(&self.inner))
+    }
+}
+
+impl ToLatex for NonEnclosed {
+    fn to_latex(&self) -> String {
+        format!("{{{}}}", self.inner.to_latex())
+    }
+}
+
+impl ToLatex for Special {
+    fn to_latex(&self) -> String {
+        match self {
+            Special::Sum(s) => s.to_latex(),
+            Special::Prod(p) => p.to_latex(),
+            Special::Frac(f) => f.to_latex(),
+            Special::Pow(p) => p.to_latex(),
+            Special::Sub(s) => s.to_latex(),
+            Special::Sqrt(s) => s.to_latex(),
+            Special::Root(r) => r.to_latex(),
+            Special::Integral(i) => i.to_latex(),
+            Special::OIntegral(i) => i.to_latex(),
+            Special::Limit(l) => l.to_latex(),
+            Special::FunctionApplication(f) => f.to_latex(),
+        }
+    }
+}
+
+impl ToLatex for Sum {
+    fn to_latex(&self) -> String {
+        latex_limits("\\sum", &self.bottom, &self.top)
+    }
+}
+
+impl ToLatex for Prod {
+    fn to_latex(&self) -> String {
+        latex_limits("\\prod", &self.bottom, &self.top)
+    }
+}
+
+impl ToLatex for Frac {
+    fn to_latex(&self) -> String {
+        format!("\\frac{}{}", argument(&self.top), argument(&self.bottom))
+    }
+}
+
+impl ToLatex for Sqrt {
+    fn to_latex(&self) -> String {
+        format!("\\sqrt{}", argument(&self.inner))
+    }
+}
+
+impl ToLatex for Root {
+    fn to_latex(&self) -> String {
+        let base = argument(&self.base);
+        format!(
+            "\\sqrt[{}]{}",
+            &base[1..base.len() - 1],
+            argument(&self.inner)
+        )
+    }
+}
+
+impl ToLatex for FunctionApplication {
+    fn to_latex(&self) -> String {
+        format!(
+            "{} {}",
+            latex_limits(&self.function.to_latex(), &self.subscript, &self.exponent),
+            self.argument.to_latex()
+        )
+    }
+}
+
+impl ToLatex for Pow {
+    fn to_latex(&self) -> String {
+        format!("{}^{}", base(&self.base), argument(&self.exp))
+    }
+}
+
+impl ToLatex for Sub {
+    fn to_latex(&self) -> String {
+        format!("{}_{}", base(&self.base), argument(&self.lower))
+    }
+}
+
+impl ToLatex for Integral {
+    fn to_latex(&self) -> String {
+        latex_limits("\\int", &self.bottom, &self.top)
+    }
+}
+
+impl ToLatex for OIntegral {
+    fn to_latex(&self) -> String {
+        latex_limits("\\oint", &self.bottom, &self.top)
+    }
+}
+
+impl ToLatex for Limit {
+    fn to_latex(&self) -> String {
+        latex_limits(&self.operator.to_latex(), &self.bottom, &self.top)
+    }
+}
+
+impl ToLatex for LimitOperator {
+    fn to_latex(&self) -> String {
+        let inner = match self {
+            LimitOperator::Lim => "\\lim",
+            LimitOperator::BigLim => "\\operatorname*{Lim}",
+            LimitOperator::Sup => "\\sup",
+            LimitOperator::Inf => "\\inf",
+            LimitOperator::Max => "\\max",
+            LimitOperator::Min => "\\min",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToLatex for ExpressionAccent {
+    fn to_latex(&self) -> String {
+        match self {
+            ExpressionAccent::Generic(g) => g.to_latex(),
+            ExpressionAccent::OverSet(o) => o.to_latex(),
+            ExpressionAccent::UnderSet(u) => u.to_latex(),
+            ExpressionAccent::Color(c) => c.to_latex(),
+        }
+    }
+}
+
+impl ToLatex for Expression {
+    /// Recursively converts the Expression into a LaTeX representation.
+    ///
+    /// The result is meant to be used in math mode, e.g. enclosed in `$...$`
+    /// or `\[...\]`. Cancelled terms require the `cancel` package, colors the
+    /// `xcolor` package and the blackboard bold font the `amssymb` package.
+    ///
+    /// Example:
+    ///
+    ///```
+    /// use asciimath_rs::format::latex::ToLatex;
+    ///
+    /// let expression = asciimath_rs::parse("frac(1)(2) + alpha");
+    /// assert_eq!(expression.to_latex(), "\\frac{1}{2} + \\alpha");
+    /// ```
+    fn to_latex(&self) -> String {
+        self.children
+            .iter()
+            .map(|child| child.to_latex())
+            .filter(|child| !child.is_empty())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl ToLatex for Element {
+    fn to_latex(&self) -> String {
+        match self {
+            Element::Special(s) => s.to_latex(),
+            Element::Literal(l) => l.to_latex(),
+            Element::Group(g) => g.to_latex(),
+            Element::Accent(a) => a.to_latex(),
+            Element::Null => "".to_string(),
+        }
+    }
+}
+
+/// Creates a braced LaTeX argument for the element. Groups that only exist to
+/// delimit the operand in AsciiMath (e.g. the parentheses in `frac(a)(b)`) are
+/// replaced by the braces.
+fn argument(element: &Element) -> String {
+    match element {
+        Element::Group(Group::NonEnclosed(ne)) => format!("{{{}}}", ne.inner.to_latex()),
+        Element::Group(Group::Parentheses(p)) => format!("{{{}}}", p.inner.to_latex()),
+        _ => format!("{{{}}}", element.to_latex()),
+    }
+}
+
+/// Creates the base of a script. Unlike the script itself the base keeps its
+/// parentheses, `(a+b)^2` is not `a+b^2`, so only elements without delimiters
+/// are braced.
+fn base(element: &Element) -> String {
+    match element {
+        Element::Group(g) => g.to_latex(),
+        Element::Special(Special::Sub(sub)) => sub.to_latex(),
+        _ => format!("{{{}}}", element.to_latex()),
+    }
+}
+
+/// Creates the LaTeX for an operator with optional lower and upper limits
+fn latex_limits(
+    operator: &str,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) -> String {
+    let mut latex = operator.to_string();
+
+    if let Some(bottom) = bottom {
+        latex.push_str(&format!("_{}", argument(bottom)));
+    }
+    if let Some(top) = top {
+        latex.push_str(&format!("^{}", argument(top)));
+    }
+
+    latex
+}
+
+/// Creates the body of a LaTeX matrix environment with `&` separated columns
+/// and `\\` separated rows
+fn latex_table(rows: &[Vec<Expression>]) -> String {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.to_latex())
+                .collect::<Vec<String>>()
+                .join(" & ")
+        })
+        .collect::<Vec<String>>()
+        .join(" \\\\ ")
+}
+
+/// Converts a hex color like `#f00` into the uppercase six digit form used by xcolor
+fn hex_color(color: &str) -> Option<String> {
+    let hex = color.strip_prefix('#')?;
+
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        3 | 4 => Some(hex.chars().take(3).flat_map(|c| vec![c, c]).collect()),
+        6 | 8 => Some(hex.chars().take(6).collect()),
+        _ => None,
+    }
+    .map(|hex: String| hex.to_uppercase())
+}
+
+/// Escapes characters that have a special meaning in LaTeX math mode
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\backslash "),
+            '{' | '}' | '#' | '$' | '%' | '&' | '_' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '^' => escaped.push_str("\\hat{}"),
+            '~' => escaped.push_str("\\sim "),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+/// Escapes characters that have a special meaning in LaTeX text mode (`\text{}`)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '#' | '$' | '%' | '&' | '_' => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            '^' => escaped.push_str("\\^{}"),
+            '~' => escaped.push_str("\\~{}"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+/// Returns the delimiter for `\left` and `\right` of the given grouping.
+/// Hidden delimiters are written as `.`.
+fn latex_delimiter(grouping: &Grouping) -> &'static str {
+    match grouping {
+        Grouping::RParen | Grouping::RXPar => "(",
+        Grouping::LParen | Grouping::LXPar => ")",
+        Grouping::RBracket => "[",
+        Grouping::LBracket => "]",
+        Grouping::RBrace => "\\{",
+        Grouping::LBrace => "\\}",
+        Grouping::RBraceHidden | Grouping::LBraceHidden | Grouping::MSep => ".",
+        Grouping::LAngle => "\\langle ",
+        Grouping::RAngle => "\\rangle ",
+        Grouping::Abs => "|",
+        Grouping::Floor => "\\lfloor ",
+        Grouping::Ceil => "\\lceil ",
+        Grouping::Norm => "\\|",
+    }
+}
@@ -1,44 +1,68 @@
 use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
 use crate::elements::group::{
-    Abs, Angles, Braces, Brackets, Ceil, Floor, Group, Matrix, NonEnclosed, Norm, Parentheses,
-    Vector, XGroup,
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
 };
 use crate::elements::literal::{Literal, Number, PlainText, Symbol};
 use crate::elements::special::{
-    Expression, Frac, Integral, OIntegral, Pow, Prod, Root, Special, Sqrt, Sub, Sum,
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
 };
 use crate::elements::Element;
 use crate::tokens::{
-    Accent, Arrow, FontCommand, Function, Greek, Logical, Misc, Operation, Relation,
+    Accent, Arrow, CustomSymbol, FontCommand, Function, Greek, Grouping, Logical, Misc, Operation,
+    Relation, SymbolCategory,
 };
 use htmlescape::{encode_attribute, encode_minimal};
+use std::fmt;
 
 /// Trait to convert the given object into a MathML representation.
 pub trait ToMathML {
-    fn to_mathml(&self) -> String;
+    /// Writes the MathML representation into the given writer. Nested elements
+    /// are written into the same writer instead of allocating a string for each of them.
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result;
+
+    fn to_mathml(&self) -> String {
+        let mut mathml = String::new();
+        self.write_mathml(&mut mathml)
+            .expect("writing into a string never fails");
+
+        mathml
+    }
 }
 
 impl ToMathML for Literal {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self {
-            Literal::Text(t) => t.to_mathml(),
-            Literal::Symbol(s) => s.to_mathml(),
-            Literal::Number(n) => n.to_mathml(),
-            Literal::Greek(g) => g.to_mathml(),
-            Literal::FontCommand(f) => f.to_mathml(),
-            Literal::Relation(r) => r.to_mathml(),
-            Literal::Function(f) => f.to_mathml(),
-            Literal::Logical(l) => l.to_mathml(),
-            Literal::Arrow(a) => a.to_mathml(),
-            Literal::Misc(m) => m.to_mathml(),
-            Literal::Operation(o) => o.to_mathml(),
-            Literal::NewLine => "<mspace linebreak='newline' />".to_string(),
+            Literal::Text(t) => t.write_mathml(out),
+            Literal::Symbol(s) => s.write_mathml(out),
+            Literal::Number(n) => n.write_mathml(out),
+            Literal::Greek(g, _) => g.write_mathml(out),
+            Literal::FontCommand(f, _) => f.write_mathml(out),
+            Literal::Relation(r, _) => r.write_mathml(out),
+            Literal::Function(f, _) => f.write_mathml(out),
+            Literal::Logical(l, _) => l.write_mathml(out),
+            Literal::Arrow(a, _) => a.write_mathml(out),
+            Literal::Misc(m, _) => m.write_mathml(out),
+            Literal::Operation(o, _) => o.write_mathml(out),
+            Literal::Custom(c, _) => c.write_mathml(out),
+            Literal::NewLine(_) => out.write_str("<mspace linebreak='newline' />"),
+        }
+    }
+}
+
+impl ToMathML for CustomSymbol {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        match self.category {
+            SymbolCategory::Identifier => write!(out, "<mi>{}</mi>", self.mathml),
+            SymbolCategory::Number => write!(out, "<mn>{}</mn>", self.mathml),
+            _ => write!(out, "<mo>{}</mo>", self.mathml),
         }
     }
 }
 
 impl ToMathML for Greek {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Greek::Alpha => "&alpha;",
             Greek::Beta => "&beta;",
@@ -78,51 +102,66 @@ impl ToMathML for Greek {
             Greek::BigOmega => "&Omega;",
         };
 
-        format!("<mi>{}</mi>", inner)
+        write!(out, "<mi>{}</mi>", inner)
     }
 }
 
 impl ToMathML for PlainText {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        // leading and trailing spaces would be stripped by the renderer
+        let text = encode_minimal(self.text.as_str());
+        let inner = text.trim_start_matches(' ');
+        let leading = text.len() - inner.len();
+        let trimmed = inner.trim_end_matches(' ');
+
         if let Some(formatting) = &self.formatting {
-            format!(
-                "<mtext mathvariant='{}'>{}</mtext>",
-                formatting.to_mathml(),
-                encode_minimal(self.text.as_str())
-            )
+            out.write_str("<mtext mathvariant='")?;
+            formatting.write_mathml(out)?;
+            out.write_str("'>")?;
         } else {
-            format!("<mtext>{}</mtext>", encode_minimal(self.text.as_str()))
+            out.write_str("<mtext>")?;
+        }
+        for _ in 0..leading {
+            out.write_str("&#160;")?;
         }
+        out.write_str(trimmed)?;
+        for _ in 0..inner.len() - trimmed.len() {
+            out.write_str("&#160;")?;
+        }
+
+        out.write_str("</mtext>")
     }
 }
 
 impl ToMathML for FontCommand {
-    fn to_mathml(&self) -> String {
-        match self {
-            FontCommand::Big => "bold".to_string(),
-            FontCommand::BigOutline => "double-struck".to_string(),
-            FontCommand::Cursive => "italic".to_string(),
-            FontCommand::TText => "script".to_string(),
-            FontCommand::Fr => "bold-fraktur".to_string(),
-            FontCommand::SansSerif => "sans-serif".to_string(),
-        }
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let variant = match self {
+            FontCommand::Big => "bold",
+            FontCommand::BigOutline => "double-struck",
+            FontCommand::Cursive => "italic",
+            FontCommand::TText => "script",
+            FontCommand::Fr => "bold-fraktur",
+            FontCommand::SansSerif => "sans-serif",
+        };
+
+        out.write_str(variant)
     }
 }
 
 impl ToMathML for Symbol {
-    fn to_mathml(&self) -> String {
-        format!("<mi>{}</mi>", encode_minimal(self.symbol.as_str()))
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(out, "<mi>{}</mi>", encode_minimal(self.symbol.as_str()))
     }
 }
 
 impl ToMathML for Number {
-    fn to_mathml(&self) -> String {
-        format!("<mn>{}</mn>", encode_minimal(self.number.as_str()))
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(out, "<mn>{}</mn>", encode_minimal(self.number.as_str()))
     }
 }
 
 impl ToMathML for Relation {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Relation::Eq => "=",
             Relation::Ne => "&ne;",
@@ -146,12 +185,12 @@ impl ToMathML for Relation {
             Relation::PropTo => "&prop;",
         };
 
-        format!("<mo>{}</mo>", inner)
+        write!(out, "<mo>{}</mo>", inner)
     }
 }
 
 impl ToMathML for Function {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Function::Exp => "exp",
             Function::Sin => "sin",
@@ -183,12 +222,12 @@ impl ToMathML for Function {
             Function::Csch => "csch",
             Function::Coth => "coth",
         };
-        format!("<mi>{}</mi>", inner)
+        write!(out, "<mi>{}</mi>", inner)
     }
 }
 
 impl ToMathML for Logical {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Logical::And => "and",
             Logical::Or => "or",
@@ -197,18 +236,18 @@ impl ToMathML for Logical {
             Logical::If => "if",
             Logical::Iff => "&hArr;",
             Logical::ForAll => "&forall;",
-            Logical::Exists => "exists;",
+            Logical::Exists => "&exist;",
             Logical::Bot => "&perp;",
             Logical::Top => "&top;",
             Logical::VDash => "&vdash;",
             Logical::Models => "&vDash;",
         };
-        format!("<mo>{}</mo>", inner)
+        write!(out, "<mo>{}</mo>", inner)
     }
 }
 
 impl ToMathML for Arrow {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Arrow::UpArrow => "&#8593;",
             Arrow::DownArrow => "&#8595;",
@@ -224,12 +263,12 @@ impl ToMathML for Arrow {
             Arrow::BigLeftArrow => "&#8678;",
             Arrow::BigLeftRightArrow => "&#11012;",
         };
-        format!("<mo>{}</mo>", inner)
+        write!(out, "<mo>{}</mo>", inner)
     }
 }
 
 impl ToMathML for Misc {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Misc::Del => "&part;",
             Misc::Grad => "&nabla;",
@@ -262,12 +301,12 @@ impl ToMathML for Misc {
             _ => "",
         };
 
-        format!("<mi>{}</mi>", inner)
+        write!(out, "<mi>{}</mi>", inner)
     }
 }
 
 impl ToMathML for Operation {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         let inner = match self {
             Operation::Plus => "&plus;",
             Operation::Minus => "&minus;",
@@ -295,370 +334,372 @@ impl ToMathML for Operation {
             Operation::BigCup => "&xcup;",
             _ => "",
         };
-        format!("<mo>{}</mo>", inner)
+        write!(out, "<mo>{}</mo>", inner)
     }
 }
 
 impl ToMathML for Accent {
-    fn to_mathml(&self) -> String {
-        match self {
-            Accent::Hat => "&circ;".to_string(),
-            Accent::Overline => "&macr;".to_string(),
-            Accent::Underline => "&ndash;".to_string(),
-            Accent::Vec => "&#8594;".to_string(),
-            Accent::Dot => ".".to_string(),
-            Accent::DDot => "..".to_string(),
-            Accent::UnderBrace => "&#9183;".to_string(),
-            Accent::OverBrace => "&#9182;".to_string(),
-            Accent::Cancel => "&#10187;".to_string(),
-            _ => "".to_string(),
-        }
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let inner = match self {
+            Accent::Hat => "&circ;",
+            Accent::Overline => "&macr;",
+            Accent::Underline => "&ndash;",
+            Accent::Vec => "&#8594;",
+            Accent::Dot => ".",
+            Accent::DDot => "..",
+            Accent::UnderBrace => "&#9183;",
+            Accent::OverBrace => "&#9182;",
+            Accent::Cancel => "&#10187;",
+            _ => "",
+        };
+
+        out.write_str(inner)
     }
 }
 
 impl ToMathML for OverSet {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mover accentover='true'><mrow>{}</mrow><mo>{}</mo>",
-            self.bottom.to_mathml(),
-            self.top.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mover><mrow>")?;
+        self.bottom.write_mathml(out)?;
+        out.write_str("</mrow><mrow>")?;
+        self.top.write_mathml(out)?;
+        out.write_str("</mrow></mover>")
     }
 }
 
 impl ToMathML for UnderSet {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<munder accentunder='true'><mrow>{}</mrow><mo>{}</mo>",
-            self.top.to_mathml(),
-            self.bottom.to_mathml(),
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<munder><mrow>")?;
+        self.top.write_mathml(out)?;
+        out.write_str("</mrow><mrow>")?;
+        self.bottom.write_mathml(out)?;
+        out.write_str("</mrow></munder>")
     }
 }
 
 impl ToMathML for Color {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mstyle mathcolor='{}'>{}</mstyle>",
-            encode_attribute(self.color.as_str()),
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            "<mstyle mathcolor='{}'>",
+            encode_attribute(self.color.as_str())
+        )?;
+        self.inner.write_mathml(out)?;
+        out.write_str("</mstyle>")
     }
 }
 
 impl ToMathML for GenericAccent {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self.accent {
             Accent::Hat
             | Accent::Overline
             | Accent::Vec
             | Accent::Dot
             | Accent::DDot
-            | Accent::OverBrace => format!(
-                "<mover accentover='true'><mrow>{}</mrow><mo>{}</mo></mover>",
-                self.inner.to_mathml(),
-                self.accent.to_mathml()
-            ),
-            Accent::Underline | Accent::UnderBrace => format!(
-                "<munder accentunder='true'><mrow>{}</mrow><mo>{}</mo></mover>",
-                self.inner.to_mathml(),
-                self.accent.to_mathml()
-            ),
-            _ => self.inner.to_mathml(),
+            | Accent::OverBrace => {
+                out.write_str("<mover accentover='true'><mrow>")?;
+                self.inner.write_mathml(out)?;
+                out.write_str("</mrow><mo>")?;
+                self.accent.write_mathml(out)?;
+                out.write_str("</mo></mover>")
+            }
+            Accent::Underline | Accent::UnderBrace => {
+                out.write_str("<munder accentunder='true'><mrow>")?;
+                self.inner.write_mathml(out)?;
+                out.write_str("</mrow><mo>")?;
+                self.accent.write_mathml(out)?;
+                out.write_str("</mo></munder>")
+            }
+            Accent::Cancel => {
+                out.write_str("<menclose notation='updiagonalstrike'><mrow>")?;
+                self.inner.write_mathml(out)?;
+                out.write_str("</mrow></menclose>")
+            }
+            _ => self.inner.write_mathml(out),
         }
     }
 }
 
 impl ToMathML for Group {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self {
-            Group::Vector(v) => v.to_mathml(),
-            Group::MSep => "<mo>,</mo>".to_string(),
-            Group::Parentheses(p) => p.to_mathml(),
-            Group::Brackets(b) => b.to_mathml(),
-            Group::Braces(b) => b.to_mathml(),
-            Group::Angles(a) => a.to_mathml(),
-            Group::XGroup(x) => x.to_mathml(),
-            Group::Abs(a) => a.to_mathml(),
-            Group::Floor(f) => f.to_mathml(),
-            Group::Ceil(c) => c.to_mathml(),
-            Group::Norm(n) => n.to_mathml(),
-            Group::Matrix(m) => m.to_mathml(),
-            Group::NonEnclosed(ne) => ne.to_mathml(),
+            Group::Vector(v) => v.write_mathml(out),
+            Group::Cases(c) => c.write_mathml(out),
+            Group::MSep(_) => out.write_str("<mo>,</mo>"),
+            Group::Parentheses(p) => p.write_mathml(out),
+            Group::Brackets(b) => b.write_mathml(out),
+            Group::Braces(b) => b.write_mathml(out),
+            Group::Angles(a) => a.write_mathml(out),
+            Group::XGroup(x) => x.write_mathml(out),
+            Group::Abs(a) => a.write_mathml(out),
+            Group::Floor(f) => f.write_mathml(out),
+            Group::Ceil(c) => c.write_mathml(out),
+            Group::Norm(n) => n.write_mathml(out),
+            Group::Matrix(m) => m.write_mathml(out),
+            Group::Fenced(f) => f.write_mathml(out),
+            Group::NonEnclosed(ne) => ne.write_mathml(out),
         }
     }
 }
 
 impl ToMathML for Parentheses {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>(</mo>{}<mo>)</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "(", &self.inner, ")")
     }
 }
 
 impl ToMathML for Brackets {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>[</mo>{}<mo>]</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "[", &self.inner, "]")
     }
 }
 
 impl ToMathML for Braces {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>&lbrace;</mo>{}<mo>&rbrace;</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "&lbrace;", &self.inner, "&rbrace;")
     }
 }
 
 impl ToMathML for Angles {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>&#10216;</mo>{}<mo>&#10217;</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "&#10216;", &self.inner, "&#10217;")
     }
 }
 
 impl ToMathML for XGroup {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>(x</mo>{}<mo>x)</mo></mrow>",
-            self.inner.to_mathml()
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "(x", &self.inner, "x)")
+    }
+}
+
+impl ToMathML for Fenced {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            "<mrow><mo fence='true'>{}</mo>",
+            mathml_delimiter(&self.left)
+        )?;
+        self.inner.write_mathml(out)?;
+        write!(
+            out,
+            "<mo fence='true'>{}</mo></mrow>",
+            mathml_delimiter(&self.right)
         )
     }
 }
 
 impl ToMathML for Abs {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>|</mo>{}<mo>|</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "|", &self.inner, "|")
     }
 }
 
 impl ToMathML for Floor {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>&lfloor;</mo>{}<mo>&rfloor;</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "&lfloor;", &self.inner, "&rfloor;")
     }
 }
 
 impl ToMathML for Ceil {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>&lceil;</mo>{}<mo>&rceil;</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "&lceil;", &self.inner, "&rceil;")
     }
 }
 
 impl ToMathML for Norm {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>||</mo>{}<mo>||</mo></mrow>",
-            self.inner.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_enclosed(out, "||", &self.inner, "||")
     }
 }
 
 impl ToMathML for Matrix {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>[</mo><mtable>{}</mtable><mo>]</mo></mrow>",
-            self.inner.iter().fold("".to_string(), |a, b| format!(
-                "{}<mtr>{}</mtr>",
-                a,
-                b.iter().fold("".to_string(), |a, b| format!(
-                    "{}<mtd>{}</mtd>",
-                    a,
-                    b.to_mathml()
-                ))
-            ))
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow><mo>[</mo><mtable>")?;
+        mathml_rows(out, &self.inner)?;
+        out.write_str("</mtable><mo>]</mo></mrow>")
     }
 }
 
 impl ToMathML for Vector {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow><mo>(</mo><mtable>{}</mtable><mo>)</mo></mrow>",
-            self.inner.iter().fold("".to_string(), |a, b| format!(
-                "{}<mtr>{}</mtr>",
-                a,
-                b.iter().fold("".to_string(), |a, b| format!(
-                    "{}<mtd>{}</mtd>",
-                    a,
-                    b.to_mathml()
-                ))
-            ))
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow><mo>(</mo><mtable>")?;
+        mathml_rows(out, &self.inner)?;
+        out.write_str("</mtable><mo>)</mo></mrow>")
+    }
+}
+
+impl ToMathML for Cases {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow><mo>&lbrace;</mo><mtable columnalign='left'>")?;
+        mathml_rows(out, &self.inner)?;
+        out.write_str("</mtable></mrow>")
     }
 }
 
 impl ToMathML for NonEnclosed {
-    fn to_mathml(&self) -> String {
-        format!("<mrow>{}</mrow>", self.inner.to_mathml())
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow>")?;
+        self.inner.write_mathml(out)?;
+        out.write_str("</mrow>")
     }
 }
 
 impl ToMathML for Special {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self {
-            Special::Sum(s) => s.to_mathml(),
-            Special::Prod(p) => p.to_mathml(),
-            Special::Frac(f) => f.to_mathml(),
-            Special::Pow(p) => p.to_mathml(),
-            Special::Sub(s) => s.to_mathml(),
-            Special::Sqrt(s) => s.to_mathml(),
-            Special::Root(r) => r.to_mathml(),
-            Special::Integral(i) => i.to_mathml(),
-            Special::OIntegral(i) => i.to_mathml(),
+            Special::Sum(s) => s.write_mathml(out),
+            Special::Prod(p) => p.write_mathml(out),
+            Special::Frac(f) => f.write_mathml(out),
+            Special::Pow(p) => p.write_mathml(out),
+            Special::Sub(s) => s.write_mathml(out),
+            Special::Sqrt(s) => s.write_mathml(out),
+            Special::Root(r) => r.write_mathml(out),
+            Special::Integral(i) => i.write_mathml(out),
+            Special::OIntegral(i) => i.write_mathml(out),
+            Special::Limit(l) => l.write_mathml(out),
+            Special::FunctionApplication(f) => f.write_mathml(out),
         }
     }
 }
 
 impl ToMathML for Sum {
-    fn to_mathml(&self) -> String {
-        if let Some(bottom) = &self.bottom {
-            if let Some(top) = &self.top {
-                format!(
-                    "<munderover><mi>&sum;</mi>{}{}</munderover>",
-                    bottom.to_mathml(),
-                    top.to_mathml()
-                )
-            } else {
-                format!("<munder><mi>&sum;</mi>{}</munder>", bottom.to_mathml())
-            }
-        } else if let Some(top) = &self.top {
-            format!("<mover><mi>&sum;<mi>{}</mover>", top.to_mathml())
-        } else {
-            "<mi>&sum;</mi>".to_string()
-        }
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_limits(out, "<mi>&sum;</mi>", &self.bottom, &self.top)
     }
 }
 
 impl ToMathML for Prod {
-    fn to_mathml(&self) -> String {
-        if let Some(bottom) = &self.bottom {
-            if let Some(top) = &self.top {
-                format!(
-                    "<munderover><mi>&prod;</mi>{}{}</munderover>",
-                    bottom.to_mathml(),
-                    top.to_mathml()
-                )
-            } else {
-                format!("<munder><mi>&prod;</mi>{}</munder>", bottom.to_mathml())
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_limits(out, "<mi>&prod;</mi>", &self.bottom, &self.top)
+    }
+}
+
+impl ToMathML for Limit {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let operator = match self.operator {
+            LimitOperator::Lim => "<mo>lim</mo>",
+            LimitOperator::BigLim => "<mo>Lim</mo>",
+            LimitOperator::Sup => "<mo>sup</mo>",
+            LimitOperator::Inf => "<mo>inf</mo>",
+            LimitOperator::Max => "<mo>max</mo>",
+            LimitOperator::Min => "<mo>min</mo>",
+        };
+
+        mathml_limits(out, operator, &self.bottom, &self.top)
+    }
+}
+
+impl ToMathML for LimitOperator {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let inner = match self {
+            LimitOperator::Lim => "lim",
+            LimitOperator::BigLim => "Lim",
+            LimitOperator::Sup => "sup",
+            LimitOperator::Inf => "inf",
+            LimitOperator::Max => "max",
+            LimitOperator::Min => "min",
+        };
+
+        out.write_str(inner)
+    }
+}
+
+impl ToMathML for FunctionApplication {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow>")?;
+        match (&self.subscript, &self.exponent) {
+            (Some(sub), Some(exp)) => {
+                out.write_str("<msubsup>")?;
+                self.function.write_mathml(out)?;
+                sub.write_mathml(out)?;
+                exp.write_mathml(out)?;
+                out.write_str("</msubsup>")?;
             }
-        } else if let Some(top) = &self.top {
-            format!("<mover><mi>&prod;<mi>{}</mover>", top.to_mathml())
-        } else {
-            "<mi>&prod;</mi>".to_string()
+            (Some(sub), None) => {
+                out.write_str("<msub>")?;
+                self.function.write_mathml(out)?;
+                sub.write_mathml(out)?;
+                out.write_str("</msub>")?;
+            }
+            (None, Some(exp)) => {
+                out.write_str("<msup>")?;
+                self.function.write_mathml(out)?;
+                exp.write_mathml(out)?;
+                out.write_str("</msup>")?;
+            }
+            (None, None) => self.function.write_mathml(out)?,
         }
+        out.write_str("<mo>&#x2061;</mo>")?;
+        self.argument.write_mathml(out)?;
+
+        out.write_str("</mrow>")
     }
 }
 
 impl ToMathML for Frac {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mfrac>{}{}</mfrac>",
-            self.top.to_mathml(),
-            self.bottom.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mfrac>")?;
+        self.top.write_mathml(out)?;
+        self.bottom.write_mathml(out)?;
+        out.write_str("</mfrac>")
     }
 }
 
 impl ToMathML for Sqrt {
-    fn to_mathml(&self) -> String {
-        format!("<msqrt>{}</msqrt>", self.inner.to_mathml())
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<msqrt>")?;
+        self.inner.write_mathml(out)?;
+        out.write_str("</msqrt>")
     }
 }
 
 impl ToMathML for Root {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mroot>{}{}</mroot>",
-            self.inner.to_mathml(),
-            self.base.to_mathml(),
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mroot>")?;
+        self.inner.write_mathml(out)?;
+        self.base.write_mathml(out)?;
+        out.write_str("</mroot>")
     }
 }
 
 impl ToMathML for Pow {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<msup>{}{}</msup>",
-            self.base.to_mathml(),
-            self.exp.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<msup>")?;
+        self.base.write_mathml(out)?;
+        self.exp.write_mathml(out)?;
+        out.write_str("</msup>")
     }
 }
 
 impl ToMathML for Sub {
-    fn to_mathml(&self) -> String {
-        format!(
-            "<msub>{}{}</msub>",
-            self.base.to_mathml(),
-            self.lower.to_mathml()
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<msub>")?;
+        self.base.write_mathml(out)?;
+        self.lower.write_mathml(out)?;
+        out.write_str("</msub>")
     }
 }
 
 impl ToMathML for Integral {
-    fn to_mathml(&self) -> String {
-        if let Some(bottom) = &self.bottom {
-            if let Some(top) = &self.top {
-                format!(
-                    "<munderover><mi>&int;</mi>{}{}</munderover>",
-                    bottom.to_mathml(),
-                    top.to_mathml()
-                )
-            } else {
-                format!("<munder><mi>&int;</mi>{}</munder>", bottom.to_mathml())
-            }
-        } else if let Some(top) = &self.top {
-            format!("<mover><mi>&int;<mi>{}</mover>", top.to_mathml())
-        } else {
-            "<mi>&int;</mi>".to_string()
-        }
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_limits(out, "<mi>&int;</mi>", &self.bottom, &self.top)
     }
 }
 
 impl ToMathML for OIntegral {
-    fn to_mathml(&self) -> String {
-        if let Some(bottom) = &self.bottom {
-            if let Some(top) = &self.top {
-                format!(
-                    "<munderover><mi>&conint;</mi>{}{}</munderover>",
-                    bottom.to_mathml(),
-                    top.to_mathml()
-                )
-            } else {
-                format!("<munder><mi>&conint;</mi>{}</munder>", bottom.to_mathml())
-            }
-        } else if let Some(top) = &self.top {
-            format!("<mover><mi>&conint;<mi>{}</mover>", top.to_mathml())
-        } else {
-            "<mi>&conint;</mi>".to_string()
-        }
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        mathml_limits(out, "<mi>&conint;</mi>", &self.bottom, &self.top)
     }
 }
 
 impl ToMathML for ExpressionAccent {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self {
-            ExpressionAccent::Generic(g) => g.to_mathml(),
-            ExpressionAccent::OverSet(o) => o.to_mathml(),
-            ExpressionAccent::UnderSet(u) => u.to_mathml(),
-            ExpressionAccent::Color(c) => c.to_mathml(),
+            ExpressionAccent::Generic(g) => g.write_mathml(out),
+            ExpressionAccent::OverSet(o) => o.write_mathml(out),
+            ExpressionAccent::UnderSet(u) => u.write_mathml(out),
+            ExpressionAccent::Color(c) => c.write_mathml(out),
         }
     }
 }
@@ -667,7 +708,8 @@ impl ToMathML for Expression {
     /// Recursively converts the Expression into a MathML representation.
     ///
     /// The result needs to be enclosed in `<math></math>` elements when used within
-    /// an html file. Currently MathML is only natively supported by Firefox and Safari.
+    /// an html file, which `MathMLOptions::render` does. Currently MathML is only
+    /// natively supported by Firefox and Safari.
     ///
     /// Example:
     ///
@@ -675,26 +717,223 @@ impl ToMathML for Expression {
     /// use asciimath_rs::format::mathml::ToMathML;
     ///
     /// let expression = asciimath_rs::parse("sin(2x - 1) + 2".to_string());
-    /// println!("<math>{}</math>", expression.to_mathml());
+    /// let mut html = String::from("<math>");
+    /// expression.write_mathml(&mut html).unwrap();
+    /// html.push_str("</math>");
+    ///
+    /// assert_eq!(html, format!("<math>{}</math>", expression.to_mathml()));
     /// ```
-    fn to_mathml(&self) -> String {
-        format!(
-            "<mrow>{}</mrow>",
-            self.children
-                .iter()
-                .fold("".to_string(), |a, b| format!("{}{}", a, b.to_mathml()))
-        )
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("<mrow>")?;
+        for child in &self.children {
+            child.write_mathml(out)?;
+        }
+
+        out.write_str("</mrow>")
     }
 }
 
 impl ToMathML for Element {
-    fn to_mathml(&self) -> String {
+    fn write_mathml(&self, out: &mut impl fmt::Write) -> fmt::Result {
         match self {
-            Element::Special(s) => s.to_mathml(),
-            Element::Literal(l) => l.to_mathml(),
-            Element::Group(g) => g.to_mathml(),
-            Element::Accent(a) => a.to_mathml(),
-            Element::Null => "".to_string(),
+            Element::Special(s) => s.write_mathml(out),
+            Element::Literal(l) => l.write_mathml(out),
+            Element::Group(g) => g.write_mathml(out),
+            Element::Accent(a) => a.write_mathml(out),
+            Element::Null => Ok(()),
+        }
+    }
+}
+
+/// Writes the limits of an operator below and above it
+fn mathml_limits(
+    out: &mut impl fmt::Write,
+    operator: &str,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) -> fmt::Result {
+    match (bottom, top) {
+        (Some(bottom), Some(top)) => {
+            write!(out, "<munderover>{}", operator)?;
+            bottom.write_mathml(out)?;
+            top.write_mathml(out)?;
+            out.write_str("</munderover>")
         }
+        (Some(bottom), None) => {
+            write!(out, "<munder>{}", operator)?;
+            bottom.write_mathml(out)?;
+            out.write_str("</munder>")
+        }
+        (None, Some(top)) => {
+            write!(out, "<mover>{}", operator)?;
+            top.write_mathml(out)?;
+            out.write_str("</mover>")
+        }
+        (None, None) => out.write_str(operator),
+    }
+}
+
+/// Writes the expression enclosed by the given operators
+fn mathml_enclosed(
+    out: &mut impl fmt::Write,
+    open: &str,
+    inner: &Expression,
+    close: &str,
+) -> fmt::Result {
+    write!(out, "<mrow><mo>{}</mo>", open)?;
+    inner.write_mathml(out)?;
+    write!(out, "<mo>{}</mo></mrow>", close)
+}
+
+/// Writes the rows of a table
+fn mathml_rows(out: &mut impl fmt::Write, rows: &[Vec<Expression>]) -> fmt::Result {
+    for row in rows {
+        out.write_str("<mtr>")?;
+        for cell in row {
+            out.write_str("<mtd>")?;
+            cell.write_mathml(out)?;
+            out.write_str("</mtd>")?;
+        }
+        out.write_str("</mtr>")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the operator content of the given grouping used as delimiter.
+/// Hidden delimiters have no content.
+fn mathml_delimiter(grouping: &Grouping) -> &'static str {
+    match grouping {
+        Grouping::RParen => "(",
+        Grouping::LParen => ")",
+        Grouping::RBracket => "[",
+        Grouping::LBracket => "]",
+        Grouping::RBrace => "&lbrace;",
+        Grouping::LBrace => "&rbrace;",
+        Grouping::RBraceHidden | Grouping::LBraceHidden => "",
+        Grouping::LAngle => "&#10216;",
+        Grouping::RAngle => "&#10217;",
+        Grouping::RXPar => "(x",
+        Grouping::LXPar => "x)",
+        Grouping::Abs => "|",
+        Grouping::Floor => "&lfloor;",
+        Grouping::Ceil => "&lceil;",
+        Grouping::Norm => "||",
+        Grouping::MSep => ",",
+    }
+}
+
+/// Whether a `<math>` element is rendered as its own block or within the text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayMode {
+    Block,
+    Inline,
+}
+
+/// Options to render an expression as a complete `<math>` element.
+///
+/// The alttext is used by assistive tools that can't read MathML and the annotation
+/// wraps the content in `<semantics>` with the source of the expression as
+/// `text/x-asciimath`, which is recovered when the formula is copied.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::format::mathml::{DisplayMode, MathMLOptions};
+///
+/// let source = "a/b";
+/// let options = MathMLOptions::default()
+///     .with_display(DisplayMode::Block)
+///     .with_source(source);
+/// assert_eq!(
+///     options.render(&asciimath_rs::parse(source)),
+///     "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"a/b\">\
+///      <semantics><mrow><mfrac><mi>a</mi><mi>b</mi></mfrac></mrow>\
+///      <annotation encoding=\"text/x-asciimath\">a/b</annotation></semantics></math>"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathMLOptions {
+    display: DisplayMode,
+    alttext: Option<String>,
+    annotation: Option<String>,
+}
+
+impl Default for MathMLOptions {
+    /// Renders inline without alttext and annotation
+    fn default() -> Self {
+        Self {
+            display: DisplayMode::Inline,
+            alttext: None,
+            annotation: None,
+        }
+    }
+}
+
+impl MathMLOptions {
+    pub fn with_display(mut self, display: DisplayMode) -> Self {
+        self.display = display;
+
+        self
+    }
+
+    pub fn with_alttext<S: ToString>(mut self, alttext: S) -> Self {
+        self.alttext = Some(alttext.to_string());
+
+        self
+    }
+
+    /// Wraps the content in `<semantics>` with the given AsciiMath source as annotation
+    pub fn with_annotation<S: ToString>(mut self, source: S) -> Self {
+        self.annotation = Some(source.to_string());
+
+        self
+    }
+
+    /// Uses the AsciiMath source of the expression as alttext and annotation
+    pub fn with_source<S: ToString>(self, source: S) -> Self {
+        let source = source.to_string();
+
+        self.with_alttext(&source).with_annotation(source)
+    }
+
+    /// Renders the expression as `<math>` element
+    pub fn render(&self, expression: &Expression) -> String {
+        let mut mathml = String::new();
+        self.write(expression, &mut mathml)
+            .expect("writing into a string never fails");
+
+        mathml
+    }
+
+    /// Writes the expression as `<math>` element into the given writer
+    pub fn write(&self, expression: &Expression, out: &mut impl fmt::Write) -> fmt::Result {
+        let display = match self.display {
+            DisplayMode::Block => "block",
+            DisplayMode::Inline => "inline",
+        };
+        write!(
+            out,
+            "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"{}\"",
+            display
+        )?;
+        if let Some(alttext) = &self.alttext {
+            write!(out, " alttext=\"{}\"", encode_minimal(alttext))?;
+        }
+        out.write_str(">")?;
+        match &self.annotation {
+            Some(source) => {
+                out.write_str("<semantics>")?;
+                expression.write_mathml(out)?;
+                write!(
+                    out,
+                    "<annotation encoding=\"text/x-asciimath\">{}</annotation></semantics>",
+                    encode_minimal(source)
+                )?;
+            }
+            None => expression.write_mathml(out)?,
+        }
+
+        out.write_str("</math>")
     }
 }
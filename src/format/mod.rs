@@ -1 +1,7 @@
+pub mod asciimath;
+pub mod content_mathml;
+pub mod error;
+pub mod latex;
 pub mod mathml;
+pub mod pretty;
+pub mod unicode;
@@ -0,0 +1,808 @@
+use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
+use crate::elements::group::{
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
+};
+use crate::elements::literal::Literal;
+use crate::elements::special::{
+    Expression, Frac, FunctionApplication, Integral, Limit, OIntegral, Pow, Prod, Root, Special,
+    Sqrt, Sub, Sum,
+};
+use crate::elements::Element;
+use crate::format::unicode::ToUnicode;
+use crate::tokens::{Accent, Grouping, SymbolCategory};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A rectangular block of text that is used to lay out expressions in two dimensions.
+///
+/// Every row has the same width. The baseline is the row that gets aligned with the
+/// baseline of neighbouring boxes, e.g. the fraction bar of a fraction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutBox {
+    rows: Vec<Vec<String>>,
+    baseline: usize,
+}
+
+impl LayoutBox {
+    /// Creates a box with a single row containing the given text
+    pub fn text(text: &str) -> Self {
+        let mut cells: Vec<String> = Vec::new();
+
+        for c in text.chars() {
+            match cells.last_mut() {
+                Some(cell) if is_combining(c) => cell.push(c),
+                _ => cells.push(c.to_string()),
+            }
+        }
+
+        Self {
+            rows: vec![cells],
+            baseline: 0,
+        }
+    }
+
+    /// Creates a box of the given size filled with spaces
+    pub fn blank(width: usize, height: usize) -> Self {
+        Self {
+            rows: vec![vec![" ".to_string(); width]; height.max(1)],
+            baseline: 0,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn baseline(&self) -> usize {
+        self.baseline
+    }
+
+    /// Returns the rows of the box with trailing whitespace removed
+    pub fn lines(&self) -> Vec<String> {
+        self.rows
+            .iter()
+            .map(|row| row.concat().trim_end().to_string())
+            .collect()
+    }
+
+    /// Places the given boxes at their (column, row) offsets into a new box.
+    /// Offsets may be negative, the resulting box is moved so that everything is visible.
+    fn compose(parts: Vec<(&LayoutBox, isize, isize)>, baseline: isize) -> Self {
+        let left = parts.iter().map(|(_, x, _)| *x).min().unwrap_or(0).min(0);
+        let top = parts
+            .iter()
+            .map(|(_, _, y)| *y)
+            .min()
+            .unwrap_or(0)
+            .min(baseline);
+        let right = parts
+            .iter()
+            .map(|(b, x, _)| x + b.width() as isize)
+            .max()
+            .unwrap_or(0);
+        let bottom = parts
+            .iter()
+            .map(|(b, _, y)| y + b.height() as isize)
+            .max()
+            .unwrap_or(0)
+            .max(baseline + 1);
+        let mut result = LayoutBox::blank((right - left) as usize, (bottom - top) as usize);
+
+        for (part, x, y) in parts {
+            for (row_index, row) in part.rows.iter().enumerate() {
+                let target_row = (y - top) as usize + row_index;
+                for (column_index, cell) in row.iter().enumerate() {
+                    result.rows[target_row][(x - left) as usize + column_index] = cell.clone();
+                }
+            }
+        }
+        result.baseline = (baseline - top) as usize;
+
+        result
+    }
+
+    /// Places the boxes next to each other aligned on their baselines
+    fn beside(boxes: &[LayoutBox]) -> Self {
+        let mut x = 0;
+        let parts = boxes
+            .iter()
+            .map(|b| {
+                let part = (b, x, -(b.baseline as isize));
+                x += b.width() as isize;
+                part
+            })
+            .collect();
+
+        Self::compose(parts, 0)
+    }
+
+    /// Places the boxes on top of each other centered horizontally.
+    /// The baseline of the box at the given index becomes the baseline of the result.
+    fn stacked(boxes: &[LayoutBox], baseline_index: usize) -> Self {
+        let width = boxes.iter().map(|b| b.width()).max().unwrap_or(0);
+        let mut y = 0;
+        let mut baseline = 0;
+        let mut parts = Vec::new();
+
+        for (i, b) in boxes.iter().enumerate() {
+            if i == baseline_index {
+                baseline = y + b.baseline as isize;
+            }
+            parts.push((b, ((width - b.width()) / 2) as isize, y));
+            y += b.height() as isize;
+        }
+
+        Self::compose(parts, baseline)
+    }
+
+    /// Creates a box of the given height that consists of a single column.
+    /// The first and last rows use their own characters if the height allows it.
+    fn column(top: &str, middle: &str, bottom: &str, height: usize) -> Self {
+        let rows = (0..height)
+            .map(|i| {
+                let cell = if i == 0 {
+                    top
+                } else if i == height - 1 {
+                    bottom
+                } else {
+                    middle
+                };
+                vec![cell.to_string()]
+            })
+            .collect();
+
+        Self { rows, baseline: 0 }
+    }
+
+    /// Encloses the box with delimiters that span its whole height
+    fn delimited(&self, open: Delimiter, close: Delimiter) -> Self {
+        let mut left = open.layout(self.height());
+        let mut right = close.layout(self.height());
+        left.baseline = self.baseline;
+        right.baseline = self.baseline;
+
+        Self::beside(&[left, self.clone(), right])
+    }
+}
+
+impl Display for LayoutBox {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+/// Delimiters that can be stretched to the height of the enclosed content
+#[derive(Clone, Copy)]
+enum Delimiter {
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    LAngle,
+    RAngle,
+    Bar,
+    DoubleBar,
+    LFloor,
+    RFloor,
+    LCeil,
+    RCeil,
+    None,
+}
+
+impl From<&Grouping> for Delimiter {
+    fn from(grouping: &Grouping) -> Self {
+        match grouping {
+            Grouping::RParen | Grouping::RXPar => Delimiter::LParen,
+            Grouping::LParen | Grouping::LXPar => Delimiter::RParen,
+            Grouping::RBracket => Delimiter::LBracket,
+            Grouping::LBracket => Delimiter::RBracket,
+            Grouping::RBrace => Delimiter::LBrace,
+            Grouping::LBrace => Delimiter::RBrace,
+            Grouping::LAngle => Delimiter::LAngle,
+            Grouping::RAngle => Delimiter::RAngle,
+            Grouping::Abs => Delimiter::Bar,
+            Grouping::Norm => Delimiter::DoubleBar,
+            Grouping::Floor => Delimiter::LFloor,
+            Grouping::Ceil => Delimiter::LCeil,
+            Grouping::RBraceHidden | Grouping::LBraceHidden | Grouping::MSep => Delimiter::None,
+        }
+    }
+}
+
+impl Delimiter {
+    fn layout(&self, height: usize) -> LayoutBox {
+        if height == 1 {
+            let single = match self {
+                Delimiter::LParen => "(",
+                Delimiter::RParen => ")",
+                Delimiter::LBracket => "[",
+                Delimiter::RBracket => "]",
+                Delimiter::LBrace => "{",
+                Delimiter::RBrace => "}",
+                Delimiter::LAngle => "⟨",
+                Delimiter::RAngle => "⟩",
+                Delimiter::Bar => "|",
+                Delimiter::DoubleBar => "‖",
+                Delimiter::LFloor => "⌊",
+                Delimiter::RFloor => "⌋",
+                Delimiter::LCeil => "⌈",
+                Delimiter::RCeil => "⌉",
+                Delimiter::None => "",
+            };
+            return LayoutBox::text(single);
+        }
+        match self {
+            Delimiter::LParen => LayoutBox::column("⎛", "⎜", "⎝", height),
+            Delimiter::RParen => LayoutBox::column("⎞", "⎟", "⎠", height),
+            Delimiter::LBracket => LayoutBox::column("⎡", "⎢", "⎣", height),
+            Delimiter::RBracket => LayoutBox::column("⎤", "⎥", "⎦", height),
+            Delimiter::LBrace | Delimiter::RBrace => {
+                let (top, middle, bottom, center) = match self {
+                    Delimiter::LBrace => ("⎧", "⎪", "⎩", "⎨"),
+                    _ => ("⎫", "⎪", "⎭", "⎬"),
+                };
+                let mut column = LayoutBox::column(top, middle, bottom, height);
+                if height > 2 {
+                    column.rows[height / 2] = vec![center.to_string()];
+                }
+                column
+            }
+            Delimiter::LAngle | Delimiter::RAngle => {
+                let (upper, center, lower) = match self {
+                    Delimiter::LAngle => ("╱", "⟨", "╲"),
+                    _ => ("╲", "⟩", "╱"),
+                };
+                let rows = (0..height)
+                    .map(|i| {
+                        let cell = if height % 2 == 1 && i == height / 2 {
+                            center
+                        } else if i < height / 2 {
+                            upper
+                        } else {
+                            lower
+                        };
+                        vec![cell.to_string()]
+                    })
+                    .collect();
+                LayoutBox { rows, baseline: 0 }
+            }
+            Delimiter::Bar => LayoutBox::column("│", "│", "│", height),
+            Delimiter::DoubleBar => LayoutBox::column("‖", "‖", "‖", height),
+            Delimiter::LFloor => LayoutBox::column("│", "│", "└", height),
+            Delimiter::RFloor => LayoutBox::column("│", "│", "┘", height),
+            Delimiter::LCeil => LayoutBox::column("┌", "│", "│", height),
+            Delimiter::RCeil => LayoutBox::column("┐", "│", "│", height),
+            Delimiter::None => LayoutBox::blank(0, height),
+        }
+    }
+}
+
+/// Trait to lay out the given object as two-dimensional text, similar to
+/// the pretty printers of computer algebra systems.
+pub trait ToLayout {
+    fn to_layout(&self) -> LayoutBox;
+}
+
+impl ToLayout for Literal {
+    fn to_layout(&self) -> LayoutBox {
+        match self {
+            Literal::Relation(_, _) | Literal::Arrow(_, _) => {
+                LayoutBox::text(&format!(" {} ", self.to_unicode()))
+            }
+            Literal::Custom(c, _)
+                if matches!(c.category, SymbolCategory::Relation | SymbolCategory::Arrow) =>
+            {
+                LayoutBox::text(&format!(" {} ", self.to_unicode()))
+            }
+            _ => LayoutBox::text(&self.to_unicode()),
+        }
+    }
+}
+
+impl ToLayout for OverSet {
+    fn to_layout(&self) -> LayoutBox {
+        LayoutBox::stacked(&[operand(&self.top), operand(&self.bottom)], 1)
+    }
+}
+
+impl ToLayout for UnderSet {
+    fn to_layout(&self) -> LayoutBox {
+        LayoutBox::stacked(&[operand(&self.top), operand(&self.bottom)], 0)
+    }
+}
+
+impl ToLayout for Color {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner.to_layout()
+    }
+}
+
+impl ToLayout for GenericAccent {
+    fn to_layout(&self) -> LayoutBox {
+        let inner = operand(&self.inner);
+        let width = inner.width();
+        let line = |c: &str| LayoutBox::text(&c.repeat(width));
+        let brace = |left: &str, right: &str| {
+            if width < 2 {
+                line("─")
+            } else {
+                LayoutBox::text(&format!("{}{}{}", left, "─".repeat(width - 2), right))
+            }
+        };
+
+        match self.accent {
+            Accent::Hat => LayoutBox::stacked(&[LayoutBox::text("^"), inner], 1),
+            Accent::Vec => LayoutBox::stacked(&[LayoutBox::text("→"), inner], 1),
+            Accent::Dot => LayoutBox::stacked(&[LayoutBox::text("."), inner], 1),
+            Accent::DDot => LayoutBox::stacked(&[LayoutBox::text(".."), inner], 1),
+            Accent::Overline => LayoutBox::stacked(&[line("_"), inner], 1),
+            Accent::Underline => LayoutBox::stacked(&[inner, line("‾")], 0),
+            Accent::OverBrace => LayoutBox::stacked(&[brace("╭", "╮"), inner], 1),
+            Accent::UnderBrace => LayoutBox::stacked(&[inner, brace("╰", "╯")], 0),
+            Accent::Cancel => {
+                let mut cancelled = inner;
+                for cell in cancelled.rows.iter_mut().flatten() {
+                    if cell != " " {
+                        cell.push('\u{0336}');
+                    }
+                }
+                cancelled
+            }
+            _ => inner,
+        }
+    }
+}
+
+impl ToLayout for Group {
+    fn to_layout(&self) -> LayoutBox {
+        match self {
+            Group::Vector(v) => v.to_layout(),
+            Group::Cases(c) => c.to_layout(),
+            Group::MSep(_) => LayoutBox::text(", "),
+            Group::Parentheses(p) => p.to_layout(),
+            Group::Brackets(b) => b.to_layout(),
+            Group::Braces(b) => b.to_layout(),
+            Group::Angles(a) => a.to_layout(),
+            Group::XGroup(x) => x.to_layout(),
+            Group::Abs(a) => a.to_layout(),
+            Group::Floor(f) => f.to_layout(),
+            Group::Ceil(c) => c.to_layout(),
+            Group::Norm(n) => n.to_layout(),
+            Group::Fenced(f) => f.to_layout(),
+            Group::Matrix(m) => m.to_layout(),
+            Group::NonEnclosed(ne) => ne.to_layout(),
+        }
+    }
+}
+
+impl ToLayout for Parentheses {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LParen, Delimiter::RParen)
+    }
+}
+
+impl ToLayout for Brackets {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LBracket, Delimiter::RBracket)
+    }
+}
+
+impl ToLayout for Braces {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LBrace, Delimiter::RBrace)
+    }
+}
+
+impl ToLayout for Angles {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LAngle, Delimiter::RAngle)
+    }
+}
+
+impl ToLayout for Fenced {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::from(&self.left), Delimiter::from(&self.right))
+    }
+}
+
+impl ToLayout for XGroup {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LParen, Delimiter::None)
+    }
+}
+
+impl ToLayout for Abs {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::Bar, Delimiter::Bar)
+    }
+}
+
+impl ToLayout for Floor {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LFloor, Delimiter::RFloor)
+    }
+}
+
+impl ToLayout for Ceil {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::LCeil, Delimiter::RCeil)
+    }
+}
+
+impl ToLayout for Norm {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner
+            .to_layout()
+            .delimited(Delimiter::DoubleBar, Delimiter::DoubleBar)
+    }
+}
+
+impl ToLayout for Matrix {
+    fn to_layout(&self) -> LayoutBox {
+        layout_table(&self.inner, false).delimited(Delimiter::LBracket, Delimiter::RBracket)
+    }
+}
+
+impl ToLayout for Vector {
+    fn to_layout(&self) -> LayoutBox {
+        layout_table(&self.inner, false).delimited(Delimiter::LParen, Delimiter::RParen)
+    }
+}
+
+impl ToLayout for Cases {
+    fn to_layout(&self) -> LayoutBox {
+        layout_table(&self.inner, true).delimited(Delimiter::LBrace, Delimiter::None)
+    }
+}
+
+impl ToLayout for NonEnclosed {
+    fn to_layout(&self) -> LayoutBox {
+        self.inner.to_layout()
+    }
+}
+
+impl ToLayout for Special {
+    fn to_layout(&self) -> LayoutBox {
+        match self {
+            Special::Sum(s) => s.to_layout(),
+            Special::Prod(p) => p.to_layout(),
+            Special::Frac(f) => f.to_layout(),
+            Special::Pow(p) => p.to_layout(),
+            Special::Sub(s) => s.to_layout(),
+            Special::Sqrt(s) => s.to_layout(),
+            Special::Root(r) => r.to_layout(),
+            Special::Integral(i) => i.to_layout(),
+            Special::OIntegral(i) => i.to_layout(),
+            Special::Limit(l) => l.to_layout(),
+            Special::FunctionApplication(f) => f.to_layout(),
+        }
+    }
+}
+
+impl ToLayout for Sum {
+    fn to_layout(&self) -> LayoutBox {
+        layout_limits("∑", &self.bottom, &self.top)
+    }
+}
+
+impl ToLayout for Prod {
+    fn to_layout(&self) -> LayoutBox {
+        layout_limits("∏", &self.bottom, &self.top)
+    }
+}
+
+impl ToLayout for Frac {
+    fn to_layout(&self) -> LayoutBox {
+        let top = operand(&self.top);
+        let bottom = operand(&self.bottom);
+        let bar = LayoutBox::text(&"─".repeat(top.width().max(bottom.width()) + 2));
+
+        LayoutBox::stacked(&[top, bar, bottom], 1)
+    }
+}
+
+impl ToLayout for Sqrt {
+    fn to_layout(&self) -> LayoutBox {
+        layout_radical(operand(&self.inner))
+    }
+}
+
+impl ToLayout for Root {
+    fn to_layout(&self) -> LayoutBox {
+        let base = operand(&self.base);
+        let radical = layout_radical(operand(&self.inner));
+
+        // the index is placed left of the top of the radical sign
+        LayoutBox::compose(
+            vec![
+                (&base, 0, 1 - base.height() as isize),
+                (&radical, base.width() as isize, 0),
+            ],
+            radical.baseline as isize,
+        )
+    }
+}
+
+impl ToLayout for FunctionApplication {
+    fn to_layout(&self) -> LayoutBox {
+        let function = LayoutBox::text(&self.function.to_unicode());
+        let mut parts = vec![(&function, 0, 0)];
+        let exp = self.exponent.as_ref().map(|e| operand(e));
+        let sub = self.subscript.as_ref().map(|s| operand(s));
+
+        if let Some(exp) = &exp {
+            parts.push((exp, function.width() as isize, -(exp.height() as isize)));
+        }
+        if let Some(sub) = &sub {
+            parts.push((sub, function.width() as isize, function.height() as isize));
+        }
+        let head = LayoutBox::compose(parts, function.baseline as isize);
+        let separator = LayoutBox::text(match &*self.argument {
+            Element::Group(_) => "",
+            _ => " ",
+        });
+
+        LayoutBox::beside(&[head, separator, self.argument.to_layout()])
+    }
+}
+
+impl ToLayout for Pow {
+    fn to_layout(&self) -> LayoutBox {
+        let base = self.base.to_layout();
+        let exp = operand(&self.exp);
+
+        LayoutBox::compose(
+            vec![
+                (&base, 0, 0),
+                (&exp, base.width() as isize, -(exp.height() as isize)),
+            ],
+            base.baseline as isize,
+        )
+    }
+}
+
+impl ToLayout for Sub {
+    fn to_layout(&self) -> LayoutBox {
+        let base = self.base.to_layout();
+        let lower = operand(&self.lower);
+
+        LayoutBox::compose(
+            vec![
+                (&base, 0, 0),
+                (&lower, base.width() as isize, base.height() as isize),
+            ],
+            base.baseline as isize,
+        )
+    }
+}
+
+impl ToLayout for Integral {
+    fn to_layout(&self) -> LayoutBox {
+        layout_limits("∫", &self.bottom, &self.top)
+    }
+}
+
+impl ToLayout for OIntegral {
+    fn to_layout(&self) -> LayoutBox {
+        layout_limits("∮", &self.bottom, &self.top)
+    }
+}
+
+impl ToLayout for Limit {
+    fn to_layout(&self) -> LayoutBox {
+        layout_limits(&self.operator.to_unicode(), &self.bottom, &self.top)
+    }
+}
+
+impl ToLayout for ExpressionAccent {
+    fn to_layout(&self) -> LayoutBox {
+        match self {
+            ExpressionAccent::Generic(g) => g.to_layout(),
+            ExpressionAccent::OverSet(o) => o.to_layout(),
+            ExpressionAccent::UnderSet(u) => u.to_layout(),
+            ExpressionAccent::Color(c) => c.to_layout(),
+        }
+    }
+}
+
+impl ToLayout for Expression {
+    /// Lays out the Expression as two-dimensional text.
+    ///
+    /// Example:
+    ///
+    ///```
+    /// use asciimath_rs::format::pretty::ToLayout;
+    ///
+    /// let expression = asciimath_rs::parse("x = 1/2");
+    /// assert_eq!(expression.to_layout().to_string(), "     1\nx = ───\n     2");
+    /// ```
+    fn to_layout(&self) -> LayoutBox {
+        let lines = self
+            .children
+            .split(|child| matches!(child, Element::Literal(Literal::NewLine(_))))
+            .map(|children| {
+                let mut boxes = Vec::new();
+
+                for (i, child) in children.iter().enumerate() {
+                    boxes.push(child.to_layout());
+
+                    // separate function names and limit operators from their arguments
+                    let separated = matches!(
+                        (child, children.get(i + 1)),
+                        (
+                            Element::Literal(Literal::Function(_, _)),
+                            Some(Element::Literal(_))
+                        ) | (Element::Special(Special::Limit(_)), Some(_))
+                    );
+                    if separated {
+                        boxes.push(LayoutBox::text(" "));
+                    }
+                }
+                LayoutBox::beside(&boxes)
+            })
+            .collect::<Vec<LayoutBox>>();
+
+        if lines.len() == 1 {
+            lines.into_iter().next().unwrap()
+        } else {
+            let mut y = 0;
+            let parts = lines
+                .iter()
+                .map(|line| {
+                    let part = (line, 0, y);
+                    y += line.height() as isize;
+                    part
+                })
+                .collect();
+            LayoutBox::compose(parts, lines[0].baseline as isize)
+        }
+    }
+}
+
+impl ToLayout for Element {
+    fn to_layout(&self) -> LayoutBox {
+        match self {
+            Element::Special(s) => s.to_layout(),
+            Element::Literal(l) => l.to_layout(),
+            Element::Group(g) => g.to_layout(),
+            Element::Accent(a) => a.to_layout(),
+            Element::Null => LayoutBox::blank(0, 1),
+        }
+    }
+}
+
+/// Lays out an operand of a fraction, radical or script. Brackets that only group
+/// the operand aren't drawn as the layout already shows the grouping.
+fn operand(element: &Element) -> LayoutBox {
+    match element {
+        Element::Group(Group::NonEnclosed(NonEnclosed { inner, .. }))
+        | Element::Group(Group::Parentheses(Parentheses { inner, .. })) => inner.to_layout(),
+        _ => element.to_layout(),
+    }
+}
+
+/// Draws a radical sign that spans the height of the given box
+fn layout_radical(inner: LayoutBox) -> LayoutBox {
+    let height = inner.height();
+    let mut sign = LayoutBox::blank(height + 1, height + 1);
+
+    for (i, row) in sign.rows.iter_mut().skip(1).enumerate() {
+        if i == height - 1 {
+            row[0] = "╲".to_string();
+            row[1] = "╱".to_string();
+        } else {
+            row[height - i] = "╱".to_string();
+        }
+    }
+    let bar = LayoutBox::text(&"_".repeat(inner.width()));
+
+    LayoutBox::compose(
+        vec![
+            (&sign, 0, 0),
+            (&bar, height as isize + 1, 0),
+            (&inner, height as isize + 1, 1),
+        ],
+        inner.baseline as isize + 1,
+    )
+}
+
+/// Draws an operator with limits centered above and below it
+fn layout_limits(
+    operator: &str,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) -> LayoutBox {
+    let mut boxes = Vec::new();
+    let mut baseline_index = 0;
+
+    if let Some(top) = top {
+        boxes.push(operand(top));
+        baseline_index = 1;
+    }
+    boxes.push(LayoutBox::text(operator));
+    if let Some(bottom) = bottom {
+        boxes.push(operand(bottom));
+    }
+
+    LayoutBox::stacked(&boxes, baseline_index)
+}
+
+/// Lays out the cells of a matrix in a grid with centered columns
+fn layout_table(rows: &[Vec<Expression>], left_aligned: bool) -> LayoutBox {
+    let cells = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_layout()).collect())
+        .collect::<Vec<Vec<LayoutBox>>>();
+    let column_count = cells.iter().map(|row| row.len()).max().unwrap_or(0);
+    let column_widths = (0..column_count)
+        .map(|column| {
+            cells
+                .iter()
+                .filter_map(|row| row.get(column).map(|cell| cell.width()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+    let mut parts = Vec::new();
+    let mut y = 0;
+
+    for row in &cells {
+        let ascent = row.iter().map(|cell| cell.baseline).max().unwrap_or(0);
+        let descent = row
+            .iter()
+            .map(|cell| cell.height() - cell.baseline)
+            .max()
+            .unwrap_or(1);
+        let mut x = 0;
+
+        for (column, cell) in row.iter().enumerate() {
+            let offset = if left_aligned {
+                0
+            } else {
+                (column_widths[column] - cell.width()) / 2
+            };
+            parts.push((
+                cell,
+                (x + offset) as isize,
+                (y + ascent - cell.baseline) as isize,
+            ));
+            x += column_widths[column] + 2;
+        }
+        y += ascent + descent;
+    }
+    let mut table = LayoutBox::compose(parts, 0);
+    table.baseline = (table.height() - 1) / 2;
+
+    table
+}
+
+/// Returns if the given char is a combining character that is drawn on top of the
+/// previous character
+fn is_combining(c: char) -> bool {
+    matches!(c as u32, 0x0300..=0x036F | 0x20D0..=0x20FF)
+}
@@ -0,0 +1,903 @@
+use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
+use crate::elements::group::{
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
+};
+use crate::elements::literal::{Literal, Number, PlainText, Symbol};
+use crate::elements::special::{
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
+};
+use crate::elements::Element;
+use crate::tokens::{
+    Accent, Arrow, CustomSymbol, FontCommand, Function, Greek, Grouping, Logical, Misc, Operation,
+    Relation,
+};
+
+/// Trait to convert the given object into a linear plain text representation
+/// using Unicode math symbols, e.g. `x²`, `√(a+b)` or `∑_{i=1}^{n}`.
+pub trait ToUnicode {
+    fn to_unicode(&self) -> String;
+}
+
+impl ToUnicode for Literal {
+    fn to_unicode(&self) -> String {
+        match self {
+            Literal::Text(t) => t.to_unicode(),
+            Literal::Symbol(s) => s.to_unicode(),
+            Literal::Number(n) => n.to_unicode(),
+            Literal::Greek(g, _) => g.to_unicode(),
+            Literal::FontCommand(_, _) => "".to_string(),
+            Literal::Relation(r, _) => r.to_unicode(),
+            Literal::Function(f, _) => f.to_unicode(),
+            Literal::Logical(l, _) => l.to_unicode(),
+            Literal::Arrow(a, _) => a.to_unicode(),
+            Literal::Misc(m, _) => m.to_unicode(),
+            Literal::Operation(o, _) => o.to_unicode(),
+            Literal::Custom(c, _) => c.to_unicode(),
+            Literal::NewLine(_) => "\n".to_string(),
+        }
+    }
+}
+
+impl ToUnicode for CustomSymbol {
+    fn to_unicode(&self) -> String {
+        self.unicode.clone()
+    }
+}
+
+impl ToUnicode for Greek {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Greek::Alpha => "α",
+            Greek::Beta => "β",
+            Greek::Gamma => "γ",
+            Greek::BigGamma => "Γ",
+            Greek::Delta => "δ",
+            Greek::BigDelta => "Δ",
+            Greek::Epsilon => "ε",
+            Greek::VarEpsilon => "ϵ",
+            Greek::Zeta => "ζ",
+            Greek::Eta => "η",
+            Greek::Theta => "θ",
+            Greek::BigTheta => "Θ",
+            Greek::VarTheta => "ϑ",
+            Greek::Iota => "ι",
+            Greek::Kappa => "κ",
+            Greek::Lambda => "λ",
+            Greek::BigLambda => "Λ",
+            Greek::Mu => "μ",
+            Greek::Nu => "ν",
+            Greek::Xi => "ξ",
+            Greek::BigXi => "Ξ",
+            Greek::Pi => "π",
+            Greek::BigPi => "Π",
+            Greek::Rho => "ρ",
+            Greek::Sigma => "σ",
+            Greek::BigSigma => "Σ",
+            Greek::Tau => "τ",
+            Greek::Upsilon => "υ",
+            Greek::Phi => "ϕ",
+            Greek::BigPhi => "Φ",
+            Greek::VarPhi => "φ",
+            Greek::Chi => "χ",
+            Greek::Psi => "ψ",
+            Greek::BigPsi => "Ψ",
+            Greek::Omega => "ω",
+            Greek::BigOmega => "Ω",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for PlainText {
+    fn to_unicode(&self) -> String {
+        if let Some(formatting) = &self.formatting {
+            self.text
+                .chars()
+                .map(|c| styled_char(c, formatting))
+                .collect()
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+impl ToUnicode for Symbol {
+    fn to_unicode(&self) -> String {
+        self.symbol.clone()
+    }
+}
+
+impl ToUnicode for Number {
+    fn to_unicode(&self) -> String {
+        self.number.clone()
+    }
+}
+
+impl ToUnicode for Relation {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Relation::Eq => "=",
+            Relation::Ne => "≠",
+            Relation::Lt => "<",
+            Relation::Gt => ">",
+            Relation::Le => "≤",
+            Relation::Ge => "≥",
+            Relation::Prec => "≺",
+            Relation::Succ => "≻",
+            Relation::PrecEq => "⪯",
+            Relation::SuccEq => "⪰",
+            Relation::In => "∈",
+            Relation::NotIn => "∉",
+            Relation::SubSet => "⊂",
+            Relation::SupSet => "⊃",
+            Relation::SubSetEq => "⊆",
+            Relation::SupSetEq => "⊇",
+            Relation::Equiv => "≡",
+            Relation::Cong => "≅",
+            Relation::Approx => "≈",
+            Relation::PropTo => "∝",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for Function {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Function::Exp => "exp",
+            Function::Sin => "sin",
+            Function::Max => "max",
+            Function::Min => "min",
+            Function::Glb => "glb",
+            Function::G => "g",
+            Function::Lub => "lub",
+            Function::Lcm => "lcm",
+            Function::Gcd => "gcd",
+            Function::Mod => "mod",
+            Function::Dim => "dim",
+            Function::Det => "det",
+            Function::Ln => "ln",
+            Function::Log => "log",
+            Function::Cot => "cot",
+            Function::Csc => "csc",
+            Function::Sech => "sech",
+            Function::Tanh => "tanh",
+            Function::Cosh => "cosh",
+            Function::ArcSin => "arcsin",
+            Function::ArcCos => "arccos",
+            Function::ArcTan => "arctan",
+            Function::Tan => "tan",
+            Function::Cos => "cos",
+            Function::F => "f",
+            Function::Sec => "sec",
+            Function::Sinh => "sinh",
+            Function::Csch => "csch",
+            Function::Coth => "coth",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for Logical {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Logical::And => " and ",
+            Logical::Or => " or ",
+            Logical::Not => "¬",
+            Logical::Implies => "⇒",
+            Logical::If => " if ",
+            Logical::Iff => "⇔",
+            Logical::ForAll => "∀",
+            Logical::Exists => "∃",
+            Logical::Bot => "⊥",
+            Logical::Top => "⊤",
+            Logical::VDash => "⊢",
+            Logical::Models => "⊨",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for Arrow {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Arrow::UpArrow => "↑",
+            Arrow::DownArrow => "↓",
+            Arrow::RightArrow => "→",
+            Arrow::To => "→",
+            Arrow::RightArrowTail => "↣",
+            Arrow::TwoHeadRightArrow => "↠",
+            Arrow::TwoHeadRightArrowTail => "⤖",
+            Arrow::MapsTo => "↦",
+            Arrow::LeftArrow => "←",
+            Arrow::LeftRightArrow => "↔",
+            Arrow::BigRightArrow => "⇒",
+            Arrow::BigLeftArrow => "⇐",
+            Arrow::BigLeftRightArrow => "⇔",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for Misc {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Misc::Del => "∂",
+            Misc::Grad => "∇",
+            Misc::PlusMinus => "±",
+            Misc::EmptySet => "∅",
+            Misc::Infty => "∞",
+            Misc::Aleph => "ℵ",
+            Misc::Therefore => "∴",
+            Misc::Because => "∵",
+            Misc::PLDots => "…",
+            Misc::PCDots => "⋯",
+            Misc::VDots => "⋮",
+            Misc::DDots => "⋱",
+            Misc::EPipes => " ",
+            Misc::EQuad => "  ",
+            Misc::Angle => "∠",
+            Misc::Frown => "⌢",
+            Misc::Triangle => "△",
+            Misc::Diamond => "⋄",
+            Misc::Square => "□",
+            Misc::LFloor => "⌊",
+            Misc::RFloor => "⌋",
+            Misc::LCeiling => "⌈",
+            Misc::RCeiling => "⌉",
+            Misc::Complex => "ℂ",
+            Misc::Natural => "ℕ",
+            Misc::Rational => "ℚ",
+            Misc::Real => "ℝ",
+            Misc::Integer => "ℤ",
+            Misc::AsciiFrac => "/",
+            Misc::Sub => "_",
+            Misc::Pow => "^",
+            _ => "",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for Operation {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            Operation::Plus => "+",
+            Operation::Minus => "−",
+            Operation::CDot => "⋅",
+            Operation::Ast => "∗",
+            Operation::Star => "⋆",
+            Operation::Slash => "/",
+            Operation::Backslash => "∖",
+            Operation::Times => "×",
+            Operation::Div => "÷",
+            Operation::LTimes => "⋉",
+            Operation::RTimes => "⋊",
+            Operation::Bowtie => "⋈",
+            Operation::Circ => "∘",
+            Operation::OPlus => "⊕",
+            Operation::OTimes => "⊗",
+            Operation::ODot => "⊙",
+            Operation::Sum => "∑",
+            Operation::Prod => "∏",
+            Operation::Lim => "lim",
+            Operation::BigLim => "Lim",
+            Operation::Sup => "sup",
+            Operation::Inf => "inf",
+            Operation::Wedge => "∧",
+            Operation::BidWedge => "⋀",
+            Operation::Vee => "∨",
+            Operation::BigVee => "⋁",
+            Operation::Cap => "∩",
+            Operation::BigCap => "⋂",
+            Operation::Cup => "∪",
+            Operation::BigCup => "⋃",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for OverSet {
+    fn to_unicode(&self) -> String {
+        format!(
+            "{}{}",
+            operand(&self.bottom),
+            script('^', &self.top.to_unicode())
+        )
+    }
+}
+
+impl ToUnicode for UnderSet {
+    fn to_unicode(&self) -> String {
+        format!(
+            "{}{}",
+            operand(&self.top),
+            script('_', &self.bottom.to_unicode())
+        )
+    }
+}
+
+impl ToUnicode for Color {
+    fn to_unicode(&self) -> String {
+        self.inner.to_unicode()
+    }
+}
+
+impl ToUnicode for GenericAccent {
+    /// Accents are written with combining characters. Combining characters that only
+    /// make sense on a single character fall back to the name of the accent.
+    fn to_unicode(&self) -> String {
+        let inner = self.inner.to_unicode();
+        let (mark, repeated) = match self.accent {
+            Accent::Hat => ('\u{0302}', false),
+            Accent::Overline => ('\u{0305}', true),
+            Accent::Underline => ('\u{0332}', true),
+            Accent::Vec => ('\u{20D7}', false),
+            Accent::Dot => ('\u{0307}', false),
+            Accent::DDot => ('\u{0308}', false),
+            Accent::Cancel => ('\u{0336}', true),
+            _ => return inner,
+        };
+
+        if repeated || inner.chars().count() == 1 {
+            inner.chars().flat_map(|c| vec![c, mark]).collect()
+        } else {
+            format!("{}({})", accent_name(&self.accent), inner)
+        }
+    }
+}
+
+impl ToUnicode for Group {
+    fn to_unicode(&self) -> String {
+        match self {
+            Group::Vector(v) => v.to_unicode(),
+            Group::Cases(c) => c.to_unicode(),
+            Group::MSep(_) => ", ".to_string(),
+            Group::Parentheses(p) => p.to_unicode(),
+            Group::Brackets(b) => b.to_unicode(),
+            Group::Braces(b) => b.to_unicode(),
+            Group::Angles(a) => a.to_unicode(),
+            Group::XGroup(x) => x.to_unicode(),
+            Group::Abs(a) => a.to_unicode(),
+            Group::Floor(f) => f.to_unicode(),
+            Group::Ceil(c) => c.to_unicode(),
+            Group::Norm(n) => n.to_unicode(),
+            Group::Fenced(f) => f.to_unicode(),
+            Group::Matrix(m) => m.to_unicode(),
+            Group::NonEnclosed(ne) => ne.to_unicode(),
+        }
+    }
+}
+
+impl ToUnicode for Parentheses {
+    fn to_unicode(&self) -> String {
+        format!("({})", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Brackets {
+    fn to_unicode(&self) -> String {
+        format!("[{}]", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Braces {
+    fn to_unicode(&self) -> String {
+        format!("{{{}}}", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Angles {
+    fn to_unicode(&self) -> String {
+        format!("⟨{}⟩", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Fenced {
+    fn to_unicode(&self) -> String {
+        format!(
+            "{}{}{}",
+            unicode_delimiter(&self.left),
+            self.inner.to_unicode(),
+            unicode_delimiter(&self.right)
+        )
+    }
+}
+
+impl ToUnicode for XGroup {
+    fn to_unicode(&self) -> String {
+        format!("({}", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Abs {
+    fn to_unicode(&self) -> String {
+        format!("|{}|", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Floor {
+    fn to_unicode(&self) -> String {
+        format!("⌊{}⌋", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Ceil {
+    fn to_unicode(&self) -> String {
+        format!("⌈{}⌉", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Norm {
+    fn to_unicode(&self) -> String {
+        format!("‖{}‖", self.inner.to_unicode())
+    }
+}
+
+impl ToUnicode for Matrix {
+    fn to_unicode(&self) -> String {
+        format!("[{}]", unicode_rows(&self.inner, "[", "]"))
+    }
+}
+
+impl ToUnicode for Vector {
+    fn to_unicode(&self) -> String {
+        format!("({})", unicode_rows(&self.inner, "(", ")"))
+    }
+}
+
+impl ToUnicode for Cases {
+    fn to_unicode(&self) -> String {
+        format!("{{{}", unicode_rows(&self.inner, "(", ")"))
+    }
+}
+
+impl ToUnicode for NonEnclosed {
+    fn to_unicode(&self) -> String {
+        self.inner.to_unicode()
+    }
+}
+
+impl ToUnicode for Special {
+    fn to_unicode(&self) -> String {
+        match self {
+            Special::Sum(s) => s.to_unicode(),
+            Special::Prod(p) => p.to_unicode(),
+            Special::Frac(f) => f.to_unicode(),
+            Special::Pow(p) => p.to_unicode(),
+            Special::Sub(s) => s.to_unicode(),
+            Special::Sqrt(s) => s.to_unicode(),
+            Special::Root(r) => r.to_unicode(),
+            Special::Integral(i) => i.to_unicode(),
+            Special::OIntegral(i) => i.to_unicode(),
+            Special::Limit(l) => l.to_unicode(),
+            Special::FunctionApplication(f) => f.to_unicode(),
+        }
+    }
+}
+
+impl ToUnicode for Sum {
+    fn to_unicode(&self) -> String {
+        unicode_limits("∑", &self.bottom, &self.top)
+    }
+}
+
+impl ToUnicode for Prod {
+    fn to_unicode(&self) -> String {
+        unicode_limits("∏", &self.bottom, &self.top)
+    }
+}
+
+impl ToUnicode for Frac {
+    fn to_unicode(&self) -> String {
+        format!("{}/{}", operand(&self.top), operand(&self.bottom))
+    }
+}
+
+impl ToUnicode for Sqrt {
+    fn to_unicode(&self) -> String {
+        format!("√{}", operand(&self.inner))
+    }
+}
+
+impl ToUnicode for Root {
+    fn to_unicode(&self) -> String {
+        let base = operand(&self.base);
+        let radical = match base.as_str() {
+            "3" => "∛".to_string(),
+            "4" => "∜".to_string(),
+            _ => match to_superscript(&base) {
+                Some(base) => format!("{}√", base),
+                None => format!("root({})", base),
+            },
+        };
+
+        format!("{}{}", radical, operand(&self.inner))
+    }
+}
+
+impl ToUnicode for FunctionApplication {
+    fn to_unicode(&self) -> String {
+        let mut output = self.function.to_unicode();
+
+        if let Some(sub) = &self.subscript {
+            output.push_str(&script('_', &sub.to_unicode()));
+        }
+        if let Some(exp) = &self.exponent {
+            output.push_str(&script('^', &exp.to_unicode()));
+        }
+        if !matches!(&*self.argument, Element::Group(_)) {
+            output.push(' ');
+        }
+        output.push_str(&self.argument.to_unicode());
+
+        output
+    }
+}
+
+impl ToUnicode for Pow {
+    fn to_unicode(&self) -> String {
+        format!(
+            "{}{}",
+            self.base.to_unicode(),
+            script('^', &self.exp.to_unicode())
+        )
+    }
+}
+
+impl ToUnicode for Sub {
+    fn to_unicode(&self) -> String {
+        format!(
+            "{}{}",
+            self.base.to_unicode(),
+            script('_', &self.lower.to_unicode())
+        )
+    }
+}
+
+impl ToUnicode for Integral {
+    fn to_unicode(&self) -> String {
+        unicode_limits("∫", &self.bottom, &self.top)
+    }
+}
+
+impl ToUnicode for OIntegral {
+    fn to_unicode(&self) -> String {
+        unicode_limits("∮", &self.bottom, &self.top)
+    }
+}
+
+impl ToUnicode for Limit {
+    fn to_unicode(&self) -> String {
+        unicode_limits(&self.operator.to_unicode(), &self.bottom, &self.top)
+    }
+}
+
+impl ToUnicode for LimitOperator {
+    fn to_unicode(&self) -> String {
+        let inner = match self {
+            LimitOperator::Lim => "lim",
+            LimitOperator::BigLim => "Lim",
+            LimitOperator::Sup => "sup",
+            LimitOperator::Inf => "inf",
+            LimitOperator::Max => "max",
+            LimitOperator::Min => "min",
+        };
+
+        inner.to_string()
+    }
+}
+
+impl ToUnicode for ExpressionAccent {
+    fn to_unicode(&self) -> String {
+        match self {
+            ExpressionAccent::Generic(g) => g.to_unicode(),
+            ExpressionAccent::OverSet(o) => o.to_unicode(),
+            ExpressionAccent::UnderSet(u) => u.to_unicode(),
+            ExpressionAccent::Color(c) => c.to_unicode(),
+        }
+    }
+}
+
+impl ToUnicode for Expression {
+    /// Recursively converts the Expression into a linear Unicode string.
+    ///
+    /// Exponents and indices are written with superscript and subscript characters
+    /// when possible. Fractions, matrices and other constructs that can't be expressed
+    /// in a single line are written in a parenthesised linear notation.
+    ///
+    /// Example:
+    ///
+    ///```
+    /// use asciimath_rs::format::unicode::ToUnicode;
+    ///
+    /// let expression = asciimath_rs::parse("sqrt(a+b) <= x^2");
+    /// assert_eq!(expression.to_unicode(), "√(a+b)≤x²");
+    /// ```
+    fn to_unicode(&self) -> String {
+        let mut output = String::new();
+
+        for (i, child) in self.children.iter().enumerate() {
+            output.push_str(&child.to_unicode());
+
+            // separate function names and limit operators from their arguments
+            let separated = matches!(
+                (child, self.children.get(i + 1)),
+                (
+                    Element::Literal(Literal::Function(_, _)),
+                    Some(Element::Literal(_))
+                ) | (Element::Special(Special::Limit(_)), Some(_))
+            );
+            if separated {
+                output.push(' ');
+            }
+        }
+
+        output
+    }
+}
+
+impl ToUnicode for Element {
+    fn to_unicode(&self) -> String {
+        match self {
+            Element::Special(s) => s.to_unicode(),
+            Element::Literal(l) => l.to_unicode(),
+            Element::Group(g) => g.to_unicode(),
+            Element::Accent(a) => a.to_unicode(),
+            Element::Null => "".to_string(),
+        }
+    }
+}
+
+/// Converts an operand of a fraction or radical. Brackets that only group the operand
+/// are removed and replaced with parentheses if the operand consists of several parts.
+fn operand(element: &Element) -> String {
+    let inner = match element {
+        Element::Group(Group::NonEnclosed(NonEnclosed { inner, .. }))
+        | Element::Group(Group::Parentheses(Parentheses { inner, .. })) => inner,
+        Element::Literal(_)
+        | Element::Group(_)
+        | Element::Special(Special::Pow(_))
+        | Element::Special(Special::Sub(_))
+        | Element::Special(Special::Sqrt(_))
+        | Element::Special(Special::Root(_))
+        | Element::Null => return element.to_unicode(),
+        _ => return format!("({})", element.to_unicode()),
+    };
+
+    if inner.children.len() == 1 {
+        operand(&inner.children[0])
+    } else {
+        format!("({})", inner.to_unicode())
+    }
+}
+
+/// Creates a super- or subscript. Superscript and subscript characters are used if all
+/// characters of the script have one, otherwise it's written as `^{script}` or `_{script}`.
+fn script(marker: char, script: &str) -> String {
+    let converted = if marker == '^' {
+        to_superscript(script)
+    } else {
+        to_subscript(script)
+    };
+
+    converted.unwrap_or_else(|| format!("{}{{{}}}", marker, script))
+}
+
+/// Creates the output for an operator with optional lower and upper limits
+fn unicode_limits(
+    operator: &str,
+    bottom: &Option<Box<Element>>,
+    top: &Option<Box<Element>>,
+) -> String {
+    let mut output = operator.to_string();
+
+    if let Some(bottom) = bottom {
+        output.push_str(&format!("_{{{}}}", bottom.to_unicode()));
+    }
+    if let Some(top) = top {
+        output.push_str(&format!("^{{{}}}", top.to_unicode()));
+    }
+
+    output
+}
+
+/// Creates the rows of a matrix or vector enclosed by the given brackets
+fn unicode_rows(rows: &[Vec<Expression>], open: &str, close: &str) -> String {
+    rows.iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                // the separator already spaces the cells, e.g. before ` if ` in cases
+                .map(|cell| cell.to_unicode().trim_start().to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("{}{}{}", open, cells, close)
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn accent_name(accent: &Accent) -> &'static str {
+    match accent {
+        Accent::Hat => "hat",
+        Accent::Vec => "vec",
+        Accent::Dot => "dot",
+        Accent::DDot => "ddot",
+        _ => "",
+    }
+}
+
+fn to_superscript(text: &str) -> Option<String> {
+    text.chars()
+        .map(|c| {
+            Some(match c {
+                '0' => '⁰',
+                '1' => '¹',
+                '2' => '²',
+                '3' => '³',
+                '4' => '⁴',
+                '5' => '⁵',
+                '6' => '⁶',
+                '7' => '⁷',
+                '8' => '⁸',
+                '9' => '⁹',
+                '+' => '⁺',
+                '-' | '−' => '⁻',
+                '=' => '⁼',
+                '(' => '⁽',
+                ')' => '⁾',
+                'a' => 'ᵃ',
+                'b' => 'ᵇ',
+                'c' => 'ᶜ',
+                'd' => 'ᵈ',
+                'e' => 'ᵉ',
+                'f' => 'ᶠ',
+                'g' => 'ᵍ',
+                'h' => 'ʰ',
+                'i' => 'ⁱ',
+                'j' => 'ʲ',
+                'k' => 'ᵏ',
+                'l' => 'ˡ',
+                'm' => 'ᵐ',
+                'n' => 'ⁿ',
+                'o' => 'ᵒ',
+                'p' => 'ᵖ',
+                'r' => 'ʳ',
+                's' => 'ˢ',
+                't' => 'ᵗ',
+                'u' => 'ᵘ',
+                'v' => 'ᵛ',
+                'w' => 'ʷ',
+                'x' => 'ˣ',
+                'y' => 'ʸ',
+                'z' => 'ᶻ',
+                _ => return None,
+            })
+        })
+        .collect()
+}
+
+fn to_subscript(text: &str) -> Option<String> {
+    text.chars()
+        .map(|c| {
+            Some(match c {
+                '0' => '₀',
+                '1' => '₁',
+                '2' => '₂',
+                '3' => '₃',
+                '4' => '₄',
+                '5' => '₅',
+                '6' => '₆',
+                '7' => '₇',
+                '8' => '₈',
+                '9' => '₉',
+                '+' => '₊',
+                '-' | '−' => '₋',
+                '=' => '₌',
+                '(' => '₍',
+                ')' => '₎',
+                'a' => 'ₐ',
+                'e' => 'ₑ',
+                'h' => 'ₕ',
+                'i' => 'ᵢ',
+                'j' => 'ⱼ',
+                'k' => 'ₖ',
+                'l' => 'ₗ',
+                'm' => 'ₘ',
+                'n' => 'ₙ',
+                'o' => 'ₒ',
+                'p' => 'ₚ',
+                'r' => 'ᵣ',
+                's' => 'ₛ',
+                't' => 'ₜ',
+                'u' => 'ᵤ',
+                'v' => 'ᵥ',
+                'x' => 'ₓ',
+                _ => return None,
+            })
+        })
+        .collect()
+}
+
+/// Converts a character into the Mathematical Alphanumeric Symbols block
+/// for the given font. Characters without a styled variant are returned unchanged.
+fn styled_char(c: char, font: &FontCommand) -> char {
+    // characters of the letterlike symbols block that are missing in the alphanumeric block
+    let exception = match (font, c) {
+        (FontCommand::BigOutline, 'C') => Some('ℂ'),
+        (FontCommand::BigOutline, 'H') => Some('ℍ'),
+        (FontCommand::BigOutline, 'N') => Some('ℕ'),
+        (FontCommand::BigOutline, 'P') => Some('ℙ'),
+        (FontCommand::BigOutline, 'Q') => Some('ℚ'),
+        (FontCommand::BigOutline, 'R') => Some('ℝ'),
+        (FontCommand::BigOutline, 'Z') => Some('ℤ'),
+        (FontCommand::Cursive, 'B') => Some('ℬ'),
+        (FontCommand::Cursive, 'E') => Some('ℰ'),
+        (FontCommand::Cursive, 'F') => Some('ℱ'),
+        (FontCommand::Cursive, 'H') => Some('ℋ'),
+        (FontCommand::Cursive, 'I') => Some('ℐ'),
+        (FontCommand::Cursive, 'L') => Some('ℒ'),
+        (FontCommand::Cursive, 'M') => Some('ℳ'),
+        (FontCommand::Cursive, 'R') => Some('ℛ'),
+        (FontCommand::Cursive, 'e') => Some('ℯ'),
+        (FontCommand::Cursive, 'g') => Some('ℊ'),
+        (FontCommand::Cursive, 'o') => Some('ℴ'),
+        (FontCommand::Fr, 'C') => Some('ℭ'),
+        (FontCommand::Fr, 'H') => Some('ℌ'),
+        (FontCommand::Fr, 'I') => Some('ℑ'),
+        (FontCommand::Fr, 'R') => Some('ℜ'),
+        (FontCommand::Fr, 'Z') => Some('ℨ'),
+        _ => None,
+    };
+    if let Some(exception) = exception {
+        return exception;
+    }
+    // code points of 'A', 'a' and '0' in the given font
+    let (upper, lower, digit) = match font {
+        FontCommand::Big => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+        FontCommand::BigOutline => (0x1D538, 0x1D552, Some(0x1D7D8)),
+        FontCommand::Cursive => (0x1D49C, 0x1D4B6, None),
+        FontCommand::Fr => (0x1D504, 0x1D51E, None),
+        FontCommand::SansSerif => (0x1D5A0, 0x1D5BA, Some(0x1D7E2)),
+        FontCommand::TText => (0x1D670, 0x1D68A, Some(0x1D7F6)),
+    };
+    let code_point = match c {
+        'A'..='Z' => Some(upper + (c as u32 - 'A' as u32)),
+        'a'..='z' => Some(lower + (c as u32 - 'a' as u32)),
+        '0'..='9' => digit.map(|digit| digit + (c as u32 - '0' as u32)),
+        _ => None,
+    };
+
+    code_point.and_then(char::from_u32).unwrap_or(c)
+}
+
+/// Returns the character of the given grouping used as delimiter.
+/// Hidden delimiters are empty.
+fn unicode_delimiter(grouping: &Grouping) -> &'static str {
+    match grouping {
+        Grouping::RParen | Grouping::RXPar => "(",
+        Grouping::LParen | Grouping::LXPar => ")",
+        Grouping::RBracket => "[",
+        Grouping::LBracket => "]",
+        Grouping::RBrace => "{",
+        Grouping::LBrace => "}",
+        Grouping::RBraceHidden | Grouping::LBraceHidden => "",
+        Grouping::LAngle => "⟨",
+        Grouping::RAngle => "⟩",
+        Grouping::Abs => "|",
+        Grouping::Floor => "⌊",
+        Grouping::Ceil => "⌈",
+        Grouping::Norm => "‖",
+        Grouping::MSep => ",",
+    }
+}
@@ -5,12 +5,17 @@ extern crate maplit;
 extern crate lazy_static;
 
 use crate::elements::special::Expression;
+use crate::parsing::error::ParseError;
+use crate::parsing::symbols::SymbolTable;
 use crate::parsing::tokenizer::Tokenizer;
 use crate::parsing::tree_parser::TreeParser;
 
 pub mod elements;
+pub mod evaluation;
 pub mod format;
 pub mod parsing;
+pub mod semantic;
+pub mod span;
 pub mod tokens;
 pub(crate) mod utils;
 
@@ -26,12 +31,72 @@ pub(crate) mod utils;
 /// let expression = asciimath_rs::parse("sin(2x) + 3".to_string());
 /// ```
 pub fn parse<S: AsRef<str>>(content: S) -> Expression {
-    let mut tokenizer = Tokenizer::new(content);
-    let tokens = tokenizer.parse();
+    parse_with_symbols(content, &SymbolTable::default())
+}
+
+/// Parses the contents of a string like `parse` but recognizes the
+/// symbols and expands the macros of the given table.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::parsing::symbols::SymbolTable;
+/// use asciimath_rs::tokens::Macro;
+///
+/// let symbols = SymbolTable::default().with_macro(Macro::new("perp", &["v"], "vec(v)_(_|_)"));
+/// let expression = asciimath_rs::parse_with_symbols("perp(u) + perp(w)", &symbols);
+/// ```
+pub fn parse_with_symbols<S: AsRef<str>>(content: S, symbols: &SymbolTable) -> Expression {
+    let mut tokenizer = Tokenizer::with_symbols(content, symbols);
+    let tokens = tokenizer.parse_spanned();
     let mut tree_parser = TreeParser::new(tokens);
 
     tree_parser.parse()
 }
 
+/// Parses the contents of a string into an AsciiMath expression like `parse`
+/// but fails on malformed input.
+///
+/// Instead of substituting missing parts of the input with `Element::Null`
+/// all errors encountered while tokenizing and building the expression tree
+/// are returned.
+///
+/// Example:
+///
+/// ```
+/// assert!(asciimath_rs::try_parse("sin(2x) + 3").is_ok());
+/// assert!(asciimath_rs::try_parse("frac{a}").is_err());
+/// ```
+pub fn try_parse<S: AsRef<str>>(content: S) -> Result<Expression, Vec<ParseError>> {
+    try_parse_with_symbols(content, &SymbolTable::default())
+}
+
+/// Parses the contents of a string like `try_parse` but recognizes the
+/// symbols and expands the macros of the given table.
+/// Macros that are invoked with the wrong number of arguments, that
+/// are nested too deeply or expand to too many tokens are reported as errors.
+pub fn try_parse_with_symbols<S: AsRef<str>>(
+    content: S,
+    symbols: &SymbolTable,
+) -> Result<Expression, Vec<ParseError>> {
+    let mut tokenizer = Tokenizer::with_symbols(content, symbols);
+    let tokens = tokenizer.parse_spanned();
+    let mut tree_parser = TreeParser::new(tokens);
+    let expression = tree_parser.parse();
+
+    let errors = tokenizer
+        .errors()
+        .iter()
+        .chain(tree_parser.errors())
+        .cloned()
+        .collect::<Vec<ParseError>>();
+
+    if errors.is_empty() {
+        Ok(expression)
+    } else {
+        Err(errors)
+    }
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,117 @@
+use crate::span::Span;
+use crate::tokens::{Grouping, Token};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// An error that was encountered while parsing an AsciiMath expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// The given token requires an operand that wasn't provided (e.g. `x^` or `frac{a}`)
+    MissingOperand(Token),
+    /// The given grouping token has no matching opening or closing counterpart
+    UnbalancedGrouping(Grouping),
+    /// A `"text"` literal that is missing the closing quote
+    UnterminatedText,
+    /// A `color(...)` with a value that is neither a named color nor a hex color
+    UnknownColor(String),
+    /// A macro that was invoked with a different number of arguments than it has parameters
+    MacroArity {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+    /// A macro whose expansion exceeded the macro limit of the symbol table
+    /// or created more tokens than all expansions of an input may create
+    MacroRecursion(String),
+    /// A LaTeX command like `\\foo` that isn't supported by the LaTeX parser
+    UnknownCommand(String),
+    /// A LaTeX environment other than `pmatrix`, `bmatrix` and `cases`
+    UnknownEnvironment(String),
+}
+
+impl ParseError {
+    pub fn new(kind: ParseErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}: ", self.span.start, self.span.end)?;
+        match &self.kind {
+            ParseErrorKind::MissingOperand(token) => write!(f, "missing operand for {:?}", token),
+            ParseErrorKind::UnbalancedGrouping(grouping) => {
+                write!(f, "unbalanced grouping {:?}", grouping)
+            }
+            ParseErrorKind::UnterminatedText => write!(f, "unterminated text literal"),
+            ParseErrorKind::UnknownColor(color) => write!(f, "unknown color '{}'", color),
+            ParseErrorKind::MacroArity {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "macro '{}' expects {} arguments but got {}",
+                name, expected, found
+            ),
+            ParseErrorKind::MacroRecursion(name) => {
+                write!(
+                    f,
+                    "expansion of macro '{}' is nested too deeply or too long",
+                    name
+                )
+            }
+            ParseErrorKind::UnknownCommand(name) => write!(f, "unknown command '\\{}'", name),
+            ParseErrorKind::UnknownEnvironment(name) => {
+                write!(f, "unknown environment '{}'", name)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error that was encountered while reading MathML with `parsing::mathml::parse_mathml`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MathMLError {
+    /// The input isn't well-formed XML
+    Xml(String),
+    /// A named entity like `&foo;` that isn't known
+    UnknownEntity(String),
+    /// An element that has no counterpart in the expression tree
+    UnsupportedElement(String),
+    /// An element with the wrong number of children like an `mfrac` with only one child
+    ChildCount {
+        element: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for MathMLError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MathMLError::Xml(message) => write!(f, "invalid xml: {}", message),
+            MathMLError::UnknownEntity(name) => write!(f, "unknown entity '&{};'", name),
+            MathMLError::UnsupportedElement(name) => write!(f, "unsupported element <{}>", name),
+            MathMLError::ChildCount {
+                element,
+                expected,
+                found,
+            } => write!(
+                f,
+                "<{}> expects {} children but has {}",
+                element, expected, found
+            ),
+        }
+    }
+}
+
+impl Error for MathMLError {}
@@ -0,0 +1,661 @@
+use crate::elements::special::Expression;
+use crate::format::latex::ToLatex;
+use crate::parsing::error::{ParseError, ParseErrorKind};
+use crate::parsing::symbols::SymbolTable;
+use crate::parsing::tree_parser::TreeParser;
+use crate::span::Span;
+use crate::tokens::mappings::{
+    get_accent_mappings, get_arrow_mapping, get_font_mappings, get_function_mappings,
+    get_greek_mappings, get_logical_mappings, get_misc_mappings, get_operation_mappings,
+    get_relation_mapping,
+};
+use crate::tokens::{
+    Accent, Arrow, FontCommand, Grouping, Logical, Misc, Operation, Relation, SpannedToken, Text,
+    Token,
+};
+use std::collections::HashMap;
+
+lazy_static! {
+    static ref COMMANDS: HashMap<String, Token> = latex_commands();
+}
+
+/// Commands that aren't written by `ToLatex` but are common alternatives
+/// to the ones that are
+fn aliases() -> Vec<(&'static str, Token)> {
+    vec![
+        ("\\leq", Token::Relation(Relation::Le)),
+        ("\\geq", Token::Relation(Relation::Ge)),
+        ("\\neq", Token::Relation(Relation::Ne)),
+        ("\\lt", Token::Relation(Relation::Lt)),
+        ("\\gt", Token::Relation(Relation::Gt)),
+        ("\\sup", Token::Operation(Operation::Sup)),
+        ("\\gets", Token::Arrow(Arrow::LeftArrow)),
+        ("\\implies", Token::Logical(Logical::Implies)),
+        ("\\iff", Token::Logical(Logical::Iff)),
+        ("\\lnot", Token::Logical(Logical::Not)),
+        ("\\land", Token::Operation(Operation::Wedge)),
+        ("\\lor", Token::Operation(Operation::Vee)),
+        ("\\int", Token::Misc(Misc::Int)),
+        ("\\oint", Token::Misc(Misc::OInt)),
+        ("\\dots", Token::Misc(Misc::PLDots)),
+        ("\\bar", Token::Accent(Accent::Overline)),
+        ("\\widehat", Token::Accent(Accent::Hat)),
+        ("\\overrightarrow", Token::Accent(Accent::Vec)),
+        ("\\overset", Token::Accent(Accent::OverSet)),
+        ("\\underset", Token::Accent(Accent::UnderSet)),
+        ("\\lbrace", Token::Grouping(Grouping::RBrace)),
+        ("\\rbrace", Token::Grouping(Grouping::LBrace)),
+        ("\\langle", Token::Grouping(Grouping::LAngle)),
+        ("\\rangle", Token::Grouping(Grouping::RAngle)),
+    ]
+}
+
+/// Parses a LaTeX math expression into the same expression tree
+/// that `parse` creates for AsciiMath.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::parsing::latex::parse_latex;
+///
+/// let expression = parse_latex("\\frac{\\alpha}{2} + \\sqrt[3]{x}");
+/// assert!(expression.structurally_eq(&asciimath_rs::parse("frac(alpha)(2) + root(3)(x)")));
+/// ```
+pub fn parse_latex<S: AsRef<str>>(content: S) -> Expression {
+    let mut tokenizer = LatexTokenizer::new(content);
+    let tokens = tokenizer.parse_spanned();
+    let mut tree_parser = TreeParser::new(tokens);
+
+    tree_parser.parse()
+}
+
+/// Parses a LaTeX math expression like `parse_latex` but fails on malformed input
+/// or commands and environments that aren't supported.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::parsing::latex::try_parse_latex;
+///
+/// assert!(try_parse_latex("\\hat{x} + \\mathbb{R}").is_ok());
+/// assert!(try_parse_latex("\\foo{x}").is_err());
+/// ```
+pub fn try_parse_latex<S: AsRef<str>>(content: S) -> Result<Expression, Vec<ParseError>> {
+    let mut tokenizer = LatexTokenizer::new(content);
+    let tokens = tokenizer.parse_spanned();
+    let mut tree_parser = TreeParser::new(tokens);
+    let expression = tree_parser.parse();
+
+    let errors = tokenizer
+        .errors()
+        .iter()
+        .chain(tree_parser.errors())
+        .cloned()
+        .collect::<Vec<ParseError>>();
+
+    if errors.is_empty() {
+        Ok(expression)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Converts LaTeX into the tokens of the equivalent AsciiMath expression
+/// so that the `TreeParser` can build the expression tree.
+/// Braces around the arguments of commands, sub- and superscripts become parentheses
+/// like in `frac(a)(b)`, other braces only group and become `{: :}`.
+pub struct LatexTokenizer {
+    chars: Vec<char>,
+    index: usize,
+    tokens: Vec<SpannedToken>,
+    /// The closing character and token of each open group
+    /// together with the index of its opening token
+    groups: Vec<(char, Grouping, usize)>,
+    /// The number of arguments that are still expected in each open group
+    arguments: Vec<usize>,
+    /// The closing token of each open `\left`
+    fences: Vec<Grouping>,
+    /// The name of each open environment
+    environments: Vec<String>,
+    symbols: SymbolTable,
+    errors: Vec<ParseError>,
+}
+
+impl LatexTokenizer {
+    /// Creates a new LaTeX tokenizer for the given math mode content.
+    /// Example:
+    /// ```
+    /// use asciimath_rs::parsing::latex::LatexTokenizer;
+    /// use asciimath_rs::tokens::{Greek, Misc, Text, Token};
+    ///
+    /// let tokens = LatexTokenizer::new("\\alpha^2").parse();
+    /// assert_eq!(
+    ///     tokens,
+    ///     vec![
+    ///         Token::Greek(Greek::Alpha),
+    ///         Token::Misc(Misc::Pow),
+    ///         Token::Text(Text::Number("2".to_string())),
+    ///     ]
+    /// );
+    /// ```
+    pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            chars: text.as_ref().chars().collect(),
+            index: 0,
+            tokens: Vec::new(),
+            groups: Vec::new(),
+            arguments: vec![0],
+            fences: Vec::new(),
+            environments: Vec::new(),
+            symbols: SymbolTable::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Parses the input string passed on creation of the tokenizer into a list of tokens
+    pub fn parse(&mut self) -> Vec<Token> {
+        self.parse_spanned()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect()
+    }
+
+    /// Parses the input string passed on creation of the tokenizer into a list of tokens
+    /// together with the span of characters each token was parsed from.
+    pub fn parse_spanned(&mut self) -> Vec<SpannedToken> {
+        while let Some(current) = self.current() {
+            let start = self.index;
+            self.index += 1;
+
+            match current {
+                '\\' => self.parse_command(start),
+                '{' => self.open_group(start),
+                '}' => self.close_group(start, '}', Grouping::LBraceHidden),
+                ']' => self.close_group(start, ']', Grouping::LBracket),
+                '^' => self.push_script(Misc::Pow, start),
+                '_' => self.push_script(Misc::Sub, start),
+                '&' if !self.environments.is_empty() => {
+                    self.push(Token::Grouping(Grouping::MSep), start)
+                }
+                c if c.is_whitespace() => {}
+                c if c.is_ascii_digit() => self.parse_number(start),
+                c if c.is_ascii_alphabetic() => {
+                    self.push_argument(Token::Text(Text::Symbol(c.to_string())), start)
+                }
+                '*' => self.push(Token::Operation(Operation::Ast), start),
+                '/' => self.push(Token::Operation(Operation::Slash), start),
+                c => {
+                    let token = self.symbol(c);
+                    self.push_argument(token, start)
+                }
+            }
+        }
+
+        self.tokens.clone()
+    }
+
+    /// Returns the errors that were encountered while parsing
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn current(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, self.index)
+    }
+
+    fn push(&mut self, token: Token, start: usize) {
+        let span = self.span_from(start);
+        self.tokens.push(SpannedToken { token, span });
+    }
+
+    /// Returns the token of a single character like the AsciiMath tokenizer
+    fn symbol(&self, character: char) -> Token {
+        match self.symbols.longest_match(&[character]) {
+            Some((_, token)) => token.clone(),
+            None => Token::Text(Text::Symbol(character.to_string())),
+        }
+    }
+
+    /// Pushes a token that can be the argument of a preceding command
+    fn push_argument(&mut self, token: Token, start: usize) {
+        self.take_argument();
+        self.push(token, start);
+    }
+
+    /// Pushes a token that takes the given number of arguments
+    fn push_operator(&mut self, token: Token, start: usize, arguments: usize) {
+        self.push_argument(token, start);
+        *self.arguments.last_mut().unwrap() += arguments;
+    }
+
+    /// Pushes a sub- or superscript operator which takes the next argument
+    fn push_script(&mut self, script: Misc, start: usize) {
+        self.push(Token::Misc(script), start);
+        *self.arguments.last_mut().unwrap() += 1;
+    }
+
+    /// Marks an expected argument as taken and returns if there was one
+    fn take_argument(&mut self) -> bool {
+        let expected = self.arguments.last_mut().unwrap();
+        if *expected > 0 {
+            *expected -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn open_group(&mut self, start: usize) {
+        let (opening, closing) = if self.take_argument() {
+            (Grouping::RParen, Grouping::LParen)
+        } else {
+            (Grouping::RBraceHidden, Grouping::LBraceHidden)
+        };
+        self.groups.push(('}', closing, self.tokens.len()));
+        self.push(Token::Grouping(opening), start);
+        self.arguments.push(0);
+    }
+
+    /// Closes the innermost group if it's closed by the given character.
+    /// Otherwise the fallback token is used which the `TreeParser` reports if it's unbalanced.
+    /// Braces that only group a single token like in `{x}^2` are removed.
+    fn close_group(&mut self, start: usize, character: char, fallback: Grouping) {
+        match self.groups.last() {
+            Some((closing, _, _)) if *closing == character => {
+                let (_, grouping, opening) = self.groups.pop().unwrap();
+                self.arguments.pop();
+                if grouping == Grouping::LBraceHidden && self.tokens.len() - opening <= 2 {
+                    self.tokens.remove(opening);
+                    return;
+                }
+                self.push(Token::Grouping(grouping), start);
+                // the optional index of `\sqrt[n]{x}` is followed by the radicand
+                if character == ']' {
+                    *self.arguments.last_mut().unwrap() += 1;
+                }
+            }
+            _ => self.push(Token::Grouping(fallback), start),
+        }
+    }
+
+    /// Parses a number. Digits in an argument like `x^23` are parsed one at a time.
+    fn parse_number(&mut self, start: usize) {
+        if !self.take_argument() {
+            while self
+                .current()
+                .is_some_and(|c| c.is_ascii_digit() || c == '.')
+            {
+                self.index += 1;
+            }
+        }
+        let number = self.chars[start..self.index].iter().collect();
+        self.push(Token::Text(Text::Number(number)), start);
+    }
+
+    fn parse_command(&mut self, start: usize) {
+        let name = self.parse_command_name();
+
+        match name.as_str() {
+            "\\" if !self.environments.is_empty() => self.parse_row_separator(start),
+            "\\" => self.push(Token::Text(Text::NewLine), start),
+            "," | ";" | ":" | "!" => {}
+            " " => self.push_argument(Token::Misc(Misc::EPipes), start),
+            "{" => self.push_argument(Token::Grouping(Grouping::RBrace), start),
+            "}" => self.push(Token::Grouping(Grouping::LBrace), start),
+            "frac" | "dfrac" | "tfrac" => {
+                self.push_operator(Token::Misc(Misc::LatexFrac), start, 2)
+            }
+            "sqrt" => self.parse_sqrt(start),
+            "left" => self.parse_left(start),
+            "right" => self.parse_right(start),
+            "begin" => self.parse_begin(start),
+            "end" => self.parse_end(start),
+            "text" | "textrm" | "mbox" | "mathrm" => {
+                let text = self.parse_raw_argument();
+                self.push_argument(Token::Text(Text::Plain(text)), start);
+            }
+            "operatorname" | "operatorname*" => {
+                let operator = self.parse_raw_argument();
+                // operators like `\operatorname{sin}` are also written as their own command
+                let token = COMMANDS
+                    .get(&format!("\\{}{{{}}}", name, operator))
+                    .or_else(|| {
+                        COMMANDS
+                            .get(&format!("\\{}", operator))
+                            .filter(|token| is_operator_name(token))
+                    })
+                    .cloned()
+                    .unwrap_or(Token::Text(Text::Plain(operator)));
+                self.push_argument(token, start);
+            }
+            "color" | "textcolor" => {
+                let color = self.parse_raw_argument();
+                self.push_operator(Token::Accent(Accent::Color(color)), start, 1);
+            }
+            _ => {
+                if let Some(font) = font_command(&name) {
+                    self.parse_font(font, start);
+                } else if let Some(token) = COMMANDS.get(&format!("\\{}", name)).cloned() {
+                    let arguments = match &token {
+                        Token::Accent(Accent::OverSet) | Token::Accent(Accent::UnderSet) => 2,
+                        Token::Accent(_) => 1,
+                        _ => 0,
+                    };
+                    self.push_operator(token, start, arguments);
+                } else {
+                    self.errors.push(ParseError::new(
+                        ParseErrorKind::UnknownCommand(name.clone()),
+                        self.span_from(start),
+                    ));
+                    self.push_argument(Token::Text(Text::Symbol(name)), start);
+                }
+            }
+        }
+    }
+
+    /// Parses the name of a command after the backslash which is either a
+    /// sequence of letters or a single other character like in `\{`
+    fn parse_command_name(&mut self) -> String {
+        let start = self.index;
+        while self.current().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.index += 1;
+        }
+        if self.index == start {
+            self.index = (start + 1).min(self.chars.len());
+        } else if self.current() == Some('*') {
+            self.index += 1;
+        }
+
+        self.chars[start..self.index].iter().collect()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.current().is_some_and(char::is_whitespace) {
+            self.index += 1;
+        }
+    }
+
+    /// Parses the content of a braced argument without interpreting it
+    /// like the name of a color. Arguments without braces are a single character.
+    fn parse_raw_argument(&mut self) -> String {
+        self.skip_whitespace();
+        if self.current() != Some('{') {
+            let argument = self.current().map(String::from).unwrap_or_default();
+            self.index = (self.index + 1).min(self.chars.len());
+            return argument;
+        }
+        self.index += 1;
+        let start = self.index;
+        let mut depth = 0;
+
+        while let Some(c) = self.current() {
+            match c {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            self.index += 1;
+        }
+        let argument = self.chars[start..self.index].iter().collect();
+        if self.current().is_none() {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::UnbalancedGrouping(Grouping::RBrace),
+                Span::new(start - 1, start),
+            ));
+        }
+        self.index = (self.index + 1).min(self.chars.len());
+
+        argument
+    }
+
+    /// Parses `\sqrt{x}` into `sqrt(x)` and `\sqrt[n]{x}` into `root(n)(x)`
+    fn parse_sqrt(&mut self, start: usize) {
+        self.skip_whitespace();
+        if self.current() == Some('[') {
+            self.push_argument(Token::Misc(Misc::Root), start);
+            let bracket = self.index;
+            self.index += 1;
+            self.groups.push((']', Grouping::LParen, self.tokens.len()));
+            self.push(Token::Grouping(Grouping::RParen), bracket);
+            self.arguments.push(0);
+        } else {
+            self.push_operator(Token::Misc(Misc::Sqrt), start, 1);
+        }
+    }
+
+    /// Parses the delimiter after `\left` or `\right`
+    fn parse_delimiter(&mut self) -> String {
+        self.skip_whitespace();
+        match self.current() {
+            Some('\\') => {
+                self.index += 1;
+                format!("\\{}", self.parse_command_name())
+            }
+            Some(c) => {
+                self.index += 1;
+                c.to_string()
+            }
+            None => String::new(),
+        }
+    }
+
+    fn parse_left(&mut self, start: usize) {
+        let delimiter = self.parse_delimiter();
+        let grouping = match delimiter.as_str() {
+            "(" => Grouping::RParen,
+            "[" => Grouping::RBracket,
+            "\\{" | "\\lbrace" => Grouping::RBrace,
+            "\\langle" | "⟨" => Grouping::LAngle,
+            "|" | "\\vert" | "\\lvert" => Grouping::Abs,
+            "\\|" | "\\Vert" | "\\lVert" | "‖" => Grouping::Norm,
+            "\\lfloor" | "⌊" => Grouping::Floor,
+            "\\lceil" | "⌈" => Grouping::Ceil,
+            _ => Grouping::RBraceHidden,
+        };
+        self.take_argument();
+        self.push(Token::Grouping(grouping.clone()), start);
+
+        // `abs`, `norm`, `floor` and `ceil` are followed by parentheses
+        if matches!(
+            grouping,
+            Grouping::Abs | Grouping::Norm | Grouping::Floor | Grouping::Ceil
+        ) {
+            self.push(Token::Grouping(Grouping::RParen), start);
+            self.fences.push(Grouping::LParen);
+        } else {
+            self.fences.push(grouping);
+        }
+    }
+
+    /// Parses the delimiter after `\right`. Bars and other delimiters that only open groups
+    /// in AsciiMath are kept as symbols unless they close the matching `\left`.
+    fn parse_right(&mut self, start: usize) {
+        let delimiter = self.parse_delimiter();
+        let closing = match delimiter.as_str() {
+            ")" => Ok(Grouping::LParen),
+            "]" => Ok(Grouping::LBracket),
+            "\\}" | "\\rbrace" => Ok(Grouping::LBrace),
+            "\\rangle" | "⟩" => Ok(Grouping::RAngle),
+            "|" | "\\vert" | "\\rvert" => Err('|'),
+            "\\|" | "\\Vert" | "\\rVert" | "‖" => Err('‖'),
+            "\\rfloor" | "⌋" => Err('⌋'),
+            "\\rceil" | "⌉" => Err('⌉'),
+            _ => Ok(Grouping::LBraceHidden),
+        };
+        let grouping = match (self.fences.pop(), closing) {
+            (Some(Grouping::LParen), _) => Grouping::LParen,
+            (_, Ok(closing)) => closing,
+            (_, Err(symbol)) => {
+                let token = self.symbol(symbol);
+                self.push(token, start);
+                Grouping::LBraceHidden
+            }
+        };
+        self.push(Token::Grouping(grouping), start);
+    }
+
+    /// Parses the beginning of an environment. Matrices become the rows of
+    /// `((a, b), (c, d))` or `[[a, b], [c, d]]` and cases become `{(a, b), (c, d):}`.
+    fn parse_begin(&mut self, start: usize) {
+        let name = self.parse_raw_argument();
+        let (opening, row) = match name.as_str() {
+            "pmatrix" => (Grouping::RParen, Grouping::RParen),
+            "bmatrix" => (Grouping::RBracket, Grouping::RBracket),
+            "cases" => (Grouping::RBrace, Grouping::RParen),
+            _ => {
+                self.errors.push(ParseError::new(
+                    ParseErrorKind::UnknownEnvironment(name.clone()),
+                    self.span_from(start),
+                ));
+                self.environments.push(name);
+                return;
+            }
+        };
+        self.take_argument();
+        self.push(Token::Grouping(opening), start);
+        self.push(Token::Grouping(row), start);
+        self.environments.push(name);
+    }
+
+    fn parse_end(&mut self, start: usize) {
+        let name = self.parse_raw_argument();
+        let (row, closing) = match name.as_str() {
+            "pmatrix" => (Grouping::LParen, Grouping::LParen),
+            "bmatrix" => (Grouping::LBracket, Grouping::LBracket),
+            "cases" => (Grouping::LParen, Grouping::LBraceHidden),
+            _ => {
+                self.environments.pop();
+                return;
+            }
+        };
+        self.environments.pop();
+        self.push(Token::Grouping(row), start);
+        self.push(Token::Grouping(closing), start);
+    }
+
+    /// Parses a `\\` that separates the rows of an environment.
+    /// A separator after the last row is ignored.
+    fn parse_row_separator(&mut self, start: usize) {
+        let index = self.index;
+        self.skip_whitespace();
+        let at_end = self.chars[self.index..].starts_with(&['\\', 'e', 'n', 'd']);
+        self.index = index;
+        if at_end {
+            return;
+        }
+        let (closing, opening) = match self.environments.last().map(String::as_str) {
+            Some("pmatrix") | Some("cases") => (Grouping::LParen, Grouping::RParen),
+            Some("bmatrix") => (Grouping::LBracket, Grouping::RBracket),
+            _ => {
+                self.push(Token::Text(Text::NewLine), start);
+                return;
+            }
+        };
+        self.push(Token::Grouping(closing), start);
+        self.push(Token::Grouping(Grouping::MSep), start);
+        self.push(Token::Grouping(opening), start);
+    }
+
+    /// Parses font commands like `\mathbb{x}` into formatted text.
+    /// Commands that `ToLatex` writes for symbols like `\mathbb{R}` become those symbols.
+    fn parse_font(&mut self, font: FontCommand, start: usize) {
+        let text = self.parse_raw_argument();
+        self.take_argument();
+
+        match COMMANDS.get(&format!("{}{{{}}}", font.to_latex(), text)) {
+            Some(token) => self.push(token.clone(), start),
+            None => {
+                self.push(Token::Font(font), start);
+                self.push(Token::Text(Text::Plain(text)), start);
+            }
+        }
+    }
+}
+
+/// Returns if the token is an operator that can be written with `\operatorname`
+fn is_operator_name(token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Function(_)
+            | Token::Operation(Operation::Lim)
+            | Token::Operation(Operation::Sup)
+            | Token::Operation(Operation::Inf)
+    )
+}
+
+fn font_command(name: &str) -> Option<FontCommand> {
+    get_font_mappings()
+        .into_iter()
+        .flat_map(|mapping| mapping.into_values())
+        .find(|font| font.to_latex() == format!("\\{}", name))
+}
+
+/// Creates the mapping from the LaTeX written for each token to the token.
+/// If several tokens are written the same, the one with the shortest AsciiMath spelling
+/// is used like in the MathML importer.
+fn latex_commands() -> HashMap<String, Token> {
+    let mut tokens: Vec<(usize, Token, String)> = Vec::new();
+
+    for mapping in get_greek_mappings() {
+        for (patterns, g) in mapping {
+            tokens.push((patterns[0].len(), Token::Greek(g.clone()), g.to_latex()));
+        }
+    }
+    for mapping in get_relation_mapping() {
+        for (patterns, r) in mapping {
+            tokens.push((patterns[0].len(), Token::Relation(r.clone()), r.to_latex()));
+        }
+    }
+    for mapping in get_arrow_mapping() {
+        for (patterns, a) in mapping {
+            tokens.push((patterns[0].len(), Token::Arrow(a.clone()), a.to_latex()));
+        }
+    }
+    for mapping in get_logical_mappings() {
+        for (patterns, l) in mapping {
+            tokens.push((patterns[0].len(), Token::Logical(l.clone()), l.to_latex()));
+        }
+    }
+    for mapping in get_operation_mappings() {
+        for (patterns, o) in mapping {
+            tokens.push((patterns[0].len(), Token::Operation(o.clone()), o.to_latex()));
+        }
+    }
+    for mapping in get_misc_mappings() {
+        for (patterns, m) in mapping {
+            tokens.push((patterns[0].len(), Token::Misc(m.clone()), m.to_latex()));
+        }
+    }
+    for mapping in get_accent_mappings() {
+        for (patterns, a) in mapping {
+            tokens.push((patterns[0].len(), Token::Accent(a.clone()), a.to_latex()));
+        }
+    }
+    for mapping in get_function_mappings() {
+        for (pattern, f) in mapping {
+            tokens.push((pattern.len(), Token::Function(f.clone()), f.to_latex()));
+        }
+    }
+    tokens.sort_by(|(a_length, a, _), (b_length, b, _)| {
+        a_length
+            .cmp(b_length)
+            .then(a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    let mut mapping = HashMap::new();
+
+    for (alias, token) in aliases() {
+        mapping.insert(alias.to_string(), token);
+    }
+    for (_, token, latex) in tokens {
+        if latex.starts_with('\\') && latex.len() > 2 && !latex.contains(' ') {
+            mapping.entry(latex).or_insert(token);
+        }
+    }
+
+    mapping
+}
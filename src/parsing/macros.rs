@@ -0,0 +1,253 @@
+use crate::parsing::error::{ParseError, ParseErrorKind};
+use crate::parsing::symbols::SymbolTable;
+use crate::parsing::tokenizer::Tokenizer;
+use crate::span::Span;
+use crate::tokens::{Grouping, Macro, SpannedToken, Text, Token};
+
+/// The maximum number of tokens that all macro expansions of an input may create
+pub(crate) const EXPANSION_LIMIT: usize = 100_000;
+
+/// Replaces the invocations of macros in the tokens with the tokens of their bodies.
+/// The tokens of a body get the span of the invocation while the tokens
+/// of the arguments keep their own spans.
+/// Every expansion takes the tokens it creates from the shared budget and the
+/// expansion stops with an error once the budget is used up or a macro is nested deeper
+/// than the macro limit.
+pub(crate) fn expand(
+    tokens: Vec<SpannedToken>,
+    symbols: &SymbolTable,
+    depth: usize,
+    budget: &mut usize,
+    errors: &mut Vec<ParseError>,
+) -> Vec<SpannedToken> {
+    if !tokens.iter().any(|t| matches!(t.token, Token::Macro(_))) {
+        return tokens;
+    }
+    let mut expanded = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let spanned = &tokens[index];
+        index += 1;
+        let definition = match &spanned.token {
+            Token::Macro(definition) => definition,
+            _ => {
+                expanded.push(spanned.clone());
+                continue;
+            }
+        };
+        let mut span = spanned.span;
+        let arguments = if definition.parameters.is_empty() {
+            Vec::new()
+        } else {
+            match parse_arguments(&tokens, index) {
+                Ok((arguments, end)) => {
+                    span.end = tokens[end].span.end;
+                    index = end + 1;
+                    arguments
+                }
+                Err(opening) => {
+                    errors.push(ParseError::new(
+                        ParseErrorKind::UnbalancedGrouping(Grouping::RParen),
+                        opening,
+                    ));
+                    break;
+                }
+            }
+        };
+        if arguments.len() != definition.parameters.len() {
+            errors.push(ParseError::new(
+                ParseErrorKind::MacroArity {
+                    name: definition.name.clone(),
+                    expected: definition.parameters.len(),
+                    found: arguments.len(),
+                },
+                span,
+            ));
+            continue;
+        }
+        // the expansion of the input is aborted after the first macro that is
+        // nested too deeply or exceeds the budget so that the error is only reported once
+        if *budget == 0 {
+            continue;
+        }
+        let body = if depth < symbols.macro_limit() {
+            substitute(definition, &arguments, symbols, span, budget, errors)
+        } else {
+            None
+        };
+        match body {
+            Some(body) => expanded.append(&mut expand(body, symbols, depth + 1, budget, errors)),
+            None => {
+                *budget = 0;
+                errors.push(ParseError::new(
+                    ParseErrorKind::MacroRecursion(definition.name.clone()),
+                    span,
+                ));
+            }
+        }
+    }
+
+    expanded
+}
+
+/// Parses the arguments in the parentheses starting at the given index and returns
+/// them together with the index of the closing parenthesis.
+/// Returns the span of the opening parenthesis if it isn't closed.
+fn parse_arguments(
+    tokens: &[SpannedToken],
+    start: usize,
+) -> Result<(Vec<&[SpannedToken]>, usize), Span> {
+    if !matches!(
+        tokens.get(start).map(|t| &t.token),
+        Some(Token::Grouping(Grouping::RParen))
+    ) {
+        return Ok((Vec::new(), start.saturating_sub(1)));
+    }
+    let mut arguments = Vec::new();
+    let mut argument_start = start + 1;
+    let mut depth = 0usize;
+
+    for (index, spanned) in tokens.iter().enumerate().skip(start + 1) {
+        match &spanned.token {
+            Token::Grouping(g) if is_opening(g) => depth += 1,
+            Token::Grouping(Grouping::LParen) if depth == 0 => {
+                let last = trim(&tokens[argument_start..index]);
+                if !arguments.is_empty() || !last.is_empty() {
+                    arguments.push(last);
+                }
+                return Ok((arguments, index));
+            }
+            Token::Grouping(g) if is_closing(g) => depth = depth.saturating_sub(1),
+            Token::Grouping(Grouping::MSep) if depth == 0 => {
+                arguments.push(trim(&tokens[argument_start..index]));
+                argument_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    Err(tokens[start].span)
+}
+
+/// Tokenizes the body of a macro and replaces its parameters with the arguments.
+/// Returns `None` if the body has more tokens than are left in the budget.
+fn substitute(
+    definition: &Macro,
+    arguments: &[&[SpannedToken]],
+    symbols: &SymbolTable,
+    span: Span,
+    budget: &mut usize,
+    errors: &mut Vec<ParseError>,
+) -> Option<Vec<SpannedToken>> {
+    let mut body_symbols = symbols.clone();
+    for parameter in &definition.parameters {
+        body_symbols =
+            body_symbols.with_pattern(parameter, Token::Text(Text::Symbol(parameter.clone())));
+    }
+    let mut tokenizer = Tokenizer::with_symbols(&definition.body, &body_symbols);
+    let body = tokenizer.tokenize();
+    errors.extend(
+        tokenizer
+            .errors()
+            .iter()
+            .map(|e| ParseError::new(e.kind.clone(), span)),
+    );
+    let mut tokens = Vec::with_capacity(body.len());
+
+    for spanned in body {
+        let parameter = match &spanned.token {
+            Token::Text(Text::Symbol(s)) => definition.parameters.iter().position(|p| p == s),
+            _ => None,
+        };
+        match parameter {
+            Some(position) => wrap_argument(arguments[position], &mut tokens),
+            None => tokens.push(SpannedToken {
+                token: spanned.token,
+                span,
+            }),
+        }
+    }
+    *budget = budget.checked_sub(tokens.len())?;
+
+    Some(tokens)
+}
+
+/// Adds the tokens of an argument. Arguments that aren't a single token or
+/// group are wrapped in hidden braces to keep them together.
+fn wrap_argument(argument: &[SpannedToken], tokens: &mut Vec<SpannedToken>) {
+    if argument.len() <= 1 || is_enclosed(argument) {
+        tokens.extend_from_slice(argument);
+        return;
+    }
+    let span = Span::new(
+        argument[0].span.start,
+        argument[argument.len() - 1].span.end,
+    );
+    tokens.push(SpannedToken {
+        token: Token::Grouping(Grouping::RBraceHidden),
+        span,
+    });
+    tokens.extend_from_slice(argument);
+    tokens.push(SpannedToken {
+        token: Token::Grouping(Grouping::LBraceHidden),
+        span,
+    });
+}
+
+/// Returns if the argument is a group whose opening token is closed by its last token
+fn is_enclosed(argument: &[SpannedToken]) -> bool {
+    let mut depth = 0usize;
+
+    for (index, spanned) in argument.iter().enumerate() {
+        match &spanned.token {
+            Token::Grouping(g) if is_opening(g) => depth += 1,
+            Token::Grouping(g) if is_closing(g) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    return index == argument.len() - 1;
+                }
+            }
+            _ if depth == 0 => return false,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Removes the whitespace around an argument
+fn trim(argument: &[SpannedToken]) -> &[SpannedToken] {
+    let is_content = |t: &SpannedToken| t.token != Token::Text(Text::Whitespace);
+    match argument.iter().position(is_content) {
+        Some(start) => {
+            let end = argument.iter().rposition(is_content).unwrap();
+            &argument[start..=end]
+        }
+        None => &argument[0..0],
+    }
+}
+
+fn is_opening(grouping: &Grouping) -> bool {
+    matches!(
+        grouping,
+        Grouping::RParen
+            | Grouping::RBracket
+            | Grouping::RBrace
+            | Grouping::RBraceHidden
+            | Grouping::LAngle
+            | Grouping::RXPar
+    )
+}
+
+fn is_closing(grouping: &Grouping) -> bool {
+    matches!(
+        grouping,
+        Grouping::LParen
+            | Grouping::LBracket
+            | Grouping::LBrace
+            | Grouping::LBraceHidden
+            | Grouping::RAngle
+            | Grouping::LXPar
+    )
+}
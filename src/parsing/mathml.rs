@@ -0,0 +1,859 @@
+use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
+use crate::elements::group::{
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, NonEnclosed, Norm,
+    Parentheses, Vector, XGroup,
+};
+use crate::elements::literal::{Literal, Number, PlainText, Symbol};
+use crate::elements::special::{
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
+};
+use crate::elements::Element;
+use crate::format::mathml::ToMathML;
+use crate::parsing::error::MathMLError;
+use crate::parsing::tokenizer::Tokenizer;
+use crate::span::Span;
+use crate::tokens::mappings::{
+    get_arrow_mapping, get_font_mappings, get_function_mappings, get_greek_mappings,
+    get_logical_mappings, get_misc_mappings, get_operation_mappings, get_relation_mapping,
+};
+use crate::tokens::{Accent, FontCommand, Grouping, Text, Token};
+use crate::utils::Boxed;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+pub type MathMLResult<T> = Result<T, MathMLError>;
+
+/// The named entities written by `ToMathML` and the invisible operators
+/// of MathML that aren't part of HTML 4
+const ENTITIES: &[(&str, &str)] = &[
+    ("ApplyFunction", "\u{2061}"),
+    ("af", "\u{2061}"),
+    ("InvisibleTimes", "\u{2062}"),
+    ("it", "\u{2062}"),
+    ("InvisibleComma", "\u{2063}"),
+    ("ic", "\u{2063}"),
+    ("Copf", "ℂ"),
+    ("Nopf", "ℕ"),
+    ("Qopf", "ℚ"),
+    ("Ropf", "ℝ"),
+    ("Zopf", "ℤ"),
+    ("Rarrtl", "⤖"),
+    ("Star", "⋆"),
+    ("because", "∵"),
+    ("bowtie", "⋈"),
+    ("compfn", "∘"),
+    ("conint", "∮"),
+    ("diamond", "⋄"),
+    ("dtdot", "⋱"),
+    ("lbrace", "{"),
+    ("rbrace", "}"),
+    ("ltimes", "⋉"),
+    ("rtimes", "⋊"),
+    ("odot", "⊙"),
+    ("plus", "+"),
+    ("pr", "≺"),
+    ("prcue", "≼"),
+    ("sc", "≻"),
+    ("sccue", "≽"),
+    ("setminus", "∖"),
+    ("top", "⊤"),
+    ("vdash", "⊢"),
+    ("vDash", "⊨"),
+    ("xcap", "⋂"),
+    ("xcup", "⋃"),
+    ("xvee", "⋁"),
+    ("xwedge", "⋀"),
+];
+
+/// The pairs of delimiters of groups without a `fence` attribute
+const DELIMITERS: &[(&str, &str)] = &[
+    ("(", ")"),
+    ("[", "]"),
+    ("{", "}"),
+    ("⟨", "⟩"),
+    ("(x", "x)"),
+    ("|", "|"),
+    ("⌊", "⌋"),
+    ("⌈", "⌉"),
+    ("||", "||"),
+];
+
+const FUNCTION_APPLICATION: &str = "\u{2061}";
+/// Invisible times, separator and plus that have no counterpart in the expression tree
+const INVISIBLE_OPERATORS: &[&str] = &["\u{2062}", "\u{2063}", "\u{2064}"];
+
+lazy_static! {
+    static ref TOKENS: HashMap<String, Token> = mathml_tokens();
+}
+
+/// Parses Presentation MathML into an expression. The input can either be a
+/// `<math>` element or a single MathML element like the output of `ToMathML`.
+/// Entity names written by `ToMathML` are mapped back to the tokens they were created from,
+/// other symbols are recognized like the tokenizer does.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::parsing::mathml::parse_mathml;
+///
+/// let expression = parse_mathml(
+///     "<math><msup><mi>&alpha;</mi><mn>2</mn></msup><mo>&le;</mo><mn>1</mn></math>",
+/// ).unwrap();
+/// assert!(expression.structurally_eq(&asciimath_rs::parse("alpha^2 <= 1")));
+/// ```
+pub fn parse_mathml(source: &str) -> MathMLResult<Expression> {
+    let source = replace_entities(source)?;
+    let document = Document::parse(&source).map_err(|e| MathMLError::Xml(e.to_string()))?;
+    let root = document.root_element();
+    let mut nodes = if name(root) == "math" {
+        children(root)
+    } else {
+        vec![root]
+    };
+
+    // the output of `ToMathML` for an expression is a single row
+    loop {
+        match nodes[..] {
+            [node] if name(node) == "semantics" => {
+                nodes = children(node).into_iter().take(1).collect()
+            }
+            [node] if name(node) == "mrow" && !is_group(&children(node)) => nodes = children(node),
+            _ => break,
+        }
+    }
+
+    expression(&nodes)
+}
+
+/// Converts a list of nodes into an expression binding functions
+/// to the elements after a function application operator
+fn expression(nodes: &[Node]) -> MathMLResult<Expression> {
+    let mut children = Vec::new();
+    let mut apply = false;
+
+    for node in nodes {
+        if name(*node) == "mo" {
+            let text = text(*node);
+            if text == FUNCTION_APPLICATION {
+                apply = true;
+                continue;
+            }
+            if INVISIBLE_OPERATORS.contains(&text.as_str()) {
+                continue;
+            }
+        }
+        let element = match element(*node)? {
+            Some(element) => element,
+            None => continue,
+        };
+        match children.pop() {
+            Some(function) if apply => children.append(&mut apply_function(function, element)),
+            Some(previous) => children.extend(vec![previous, element]),
+            None => children.push(element),
+        }
+        apply = false;
+    }
+
+    Ok(Expression {
+        children,
+        span: Span::default(),
+    })
+}
+
+/// Converts a MathML node into an element. Nodes without a
+/// counterpart in the expression tree like empty identifiers return `None`.
+fn element(node: Node) -> MathMLResult<Option<Element>> {
+    let element = match name(node) {
+        "mi" => token_element(&text(node), false),
+        "mo" => token_element(&text(node), true),
+        "mn" => Some(Element::Literal(Literal::Number(Number {
+            number: text(node),
+            span: Span::default(),
+        }))),
+        "mtext" | "ms" => Some(Element::Literal(Literal::Text(PlainText {
+            text: text(node).replace('\u{a0}', " "),
+            formatting: node.attribute("mathvariant").and_then(font_command),
+            span: Span::default(),
+        }))),
+        "mspace" if node.attribute("linebreak") == Some("newline") => {
+            Some(Element::Literal(Literal::NewLine(Span::default())))
+        }
+        "mspace" | "annotation" | "annotation-xml" => None,
+        "mrow" => row(node)?,
+        "semantics" => match children(node).first() {
+            Some(child) => element(*child)?,
+            None => None,
+        },
+        "mstyle" => match node.attribute("mathcolor") {
+            Some(color) => Some(Element::Accent(ExpressionAccent::Color(Color {
+                color: color.to_string(),
+                inner: inner(&children(node))?.boxed(),
+                span: Span::default(),
+            }))),
+            None => Some(inner(&children(node))?),
+        },
+        "mfrac" => {
+            let children = arguments(node, 2)?;
+            Some(Element::Special(Special::Frac(Frac {
+                top: required(children[0])?.boxed(),
+                bottom: required(children[1])?.boxed(),
+                span: Span::default(),
+            })))
+        }
+        "menclose" => match node.attribute("notation") {
+            Some(notation) if notation.split_whitespace().any(|n| n == "updiagonalstrike") => {
+                Some(Element::Accent(ExpressionAccent::Generic(GenericAccent {
+                    inner: match children(node)[..] {
+                        [child] => wrapped(child)?,
+                        ref nodes => inner(nodes)?,
+                    }
+                    .boxed(),
+                    accent: Accent::Cancel,
+                    span: Span::default(),
+                })))
+            }
+            // other notations like boxes have no counterpart and only keep their content
+            _ => Some(inner(&children(node))?),
+        },
+        "msqrt" => Some(Element::Special(Special::Sqrt(Sqrt {
+            inner: inner(&children(node))?.boxed(),
+            span: Span::default(),
+        }))),
+        "mroot" => {
+            let children = arguments(node, 2)?;
+            Some(Element::Special(Special::Root(Root {
+                base: required(children[1])?.boxed(),
+                inner: required(children[0])?.boxed(),
+                span: Span::default(),
+            })))
+        }
+        "msup" => {
+            let children = arguments(node, 2)?;
+            Some(pow(required(children[0])?, required(children[1])?))
+        }
+        "msub" => {
+            let children = arguments(node, 2)?;
+            Some(sub(required(children[0])?, required(children[1])?))
+        }
+        "msubsup" => {
+            let children = arguments(node, 3)?;
+            let base = sub(required(children[0])?, required(children[1])?);
+            Some(pow(base, required(children[2])?))
+        }
+        "munder" | "mover" | "munderover" => Some(scripts(node)?),
+        "mtable" => Some(Element::Group(Group::Matrix(Matrix {
+            inner: table(node)?,
+            span: Span::default(),
+        }))),
+        other => return Err(MathMLError::UnsupportedElement(other.to_string())),
+    };
+
+    Ok(element)
+}
+
+/// Converts an identifier or operator into a literal or an operator without limits
+fn token_element(text: &str, is_operator: bool) -> Option<Element> {
+    let span = Span::default();
+    let special = match text {
+        "" => return None,
+        "∑" => Special::Sum(Sum {
+            top: None,
+            bottom: None,
+            span,
+        }),
+        "∏" => Special::Prod(Prod {
+            top: None,
+            bottom: None,
+            span,
+        }),
+        "∫" => Special::Integral(Integral {
+            top: None,
+            bottom: None,
+            span,
+        }),
+        "∮" => Special::OIntegral(OIntegral {
+            top: None,
+            bottom: None,
+            span,
+        }),
+        "," => return Some(Element::Group(Group::MSep(span))),
+        _ => match limit_operator(text, is_operator) {
+            Some(operator) => Special::Limit(Limit {
+                operator,
+                top: None,
+                bottom: None,
+                span,
+            }),
+            None => return Some(Element::Literal(token_literal(text))),
+        },
+    };
+
+    Some(Element::Special(special))
+}
+
+/// Returns the literal of the token that is rendered as the text
+/// or a symbol if there's none
+fn token_literal(text: &str) -> Literal {
+    let span = Span::default();
+    if let Some(literal) = TOKENS.get(text).and_then(|t| literal(t.clone(), span)) {
+        return literal;
+    }
+    if let [token] = &Tokenizer::new(text).parse()[..] {
+        if let Some(literal) = literal(token.clone(), span) {
+            return literal;
+        }
+    }
+
+    Literal::Symbol(Symbol {
+        symbol: text.to_string(),
+        span,
+    })
+}
+
+fn literal(token: Token, span: Span) -> Option<Literal> {
+    let literal = match token {
+        Token::Greek(g) => Literal::Greek(g, span),
+        Token::Relation(r) => Literal::Relation(r, span),
+        Token::Arrow(a) => Literal::Arrow(a, span),
+        Token::Logical(l) => Literal::Logical(l, span),
+        Token::Operation(o) => Literal::Operation(o, span),
+        Token::Misc(m) => Literal::Misc(m, span),
+        Token::Function(f) => Literal::Function(f, span),
+        Token::Text(Text::Symbol(symbol)) => Literal::Symbol(Symbol { symbol, span }),
+        Token::Text(Text::Number(number)) => Literal::Number(Number { number, span }),
+        _ => return None,
+    };
+
+    Some(literal)
+}
+
+fn limit_operator(text: &str, is_operator: bool) -> Option<LimitOperator> {
+    let operator = match text {
+        "lim" => LimitOperator::Lim,
+        "Lim" => LimitOperator::BigLim,
+        "sup" => LimitOperator::Sup,
+        "inf" => LimitOperator::Inf,
+        // identifiers are the functions
+        "max" if is_operator => LimitOperator::Max,
+        "min" if is_operator => LimitOperator::Min,
+        _ => return None,
+    };
+
+    Some(operator)
+}
+
+/// Converts a row into a group, a non enclosed group or the only element of the row
+fn row(node: Node) -> MathMLResult<Option<Element>> {
+    let nodes = children(node);
+    if is_group(&nodes) {
+        return group(&nodes).map(|g| Some(Element::Group(g)));
+    }
+    // a row that only contains an expression is written for non enclosed groups
+    if let [child] = nodes[..] {
+        if name(child) == "mrow" && !is_group(&children(child)) {
+            return Ok(Some(non_enclosed(expression(&children(child))?)));
+        }
+    }
+    let mut expression = expression(&nodes)?;
+
+    Ok(match expression.children.len() {
+        0 => None,
+        1 => expression.children.pop(),
+        _ => Some(non_enclosed(expression)),
+    })
+}
+
+/// Returns if the nodes are a group enclosed by a pair of delimiters
+fn is_group(nodes: &[Node]) -> bool {
+    match nodes {
+        [open, table] if is_cases(*open, *table) => true,
+        [open, .., close] if name(*open) == "mo" && name(*close) == "mo" => {
+            let (open_text, close_text) = (text(*open), text(*close));
+            if is_fence(*open) && is_fence(*close) {
+                grouping(&open_text, true).is_some() && grouping(&close_text, false).is_some()
+            } else {
+                DELIMITERS.contains(&(open_text.as_str(), close_text.as_str()))
+                    && is_balanced(&nodes[1..nodes.len() - 1], &open_text, &close_text)
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_cases(open: Node, table: Node) -> bool {
+    name(open) == "mo" && text(open) == "{" && name(table) == "mtable"
+}
+
+fn is_fence(node: Node) -> bool {
+    node.attribute("fence") == Some("true")
+}
+
+/// Checks that the delimiters in a row are balanced so that
+/// rows like `(a) + (b)` aren't taken as one group.
+/// Identical delimiters like `|a| + |b|` can't be paired, so the row
+/// is only a group if they don't occur between the outer ones.
+fn is_balanced(nodes: &[Node], open: &str, close: &str) -> bool {
+    let mut operators = nodes.iter().filter(|n| name(**n) == "mo").map(|n| text(*n));
+    if open == close {
+        return operators.all(|text| text != open);
+    }
+    let mut depth = 0;
+
+    for text in operators {
+        if text == open {
+            depth += 1;
+        } else if text == close {
+            if depth == 0 {
+                return false;
+            }
+            depth -= 1;
+        }
+    }
+
+    depth == 0
+}
+
+/// Converts a row that was checked with `is_group` into a group
+fn group(nodes: &[Node]) -> MathMLResult<Group> {
+    let span = Span::default();
+    if let [open, table] = nodes {
+        if is_cases(*open, *table) {
+            return Ok(Group::Cases(Cases {
+                inner: self::table(*table)?,
+                span,
+            }));
+        }
+    }
+    let (open, close) = (text(nodes[0]), text(nodes[nodes.len() - 1]));
+    let content = &nodes[1..nodes.len() - 1];
+
+    if let [table] = content {
+        if name(*table) == "mtable" {
+            match (open.as_str(), close.as_str()) {
+                ("(", ")") => {
+                    return Ok(Group::Vector(Vector {
+                        inner: self::table(*table)?,
+                        span,
+                    }))
+                }
+                ("[", "]") => {
+                    return Ok(Group::Matrix(Matrix {
+                        inner: self::table(*table)?,
+                        span,
+                    }))
+                }
+                _ => {}
+            }
+        }
+    }
+    let inner = contents(content)?.boxed();
+
+    if is_fence(nodes[0]) {
+        return Ok(Group::Fenced(Fenced {
+            left: grouping(&open, true).unwrap(),
+            right: grouping(&close, false).unwrap(),
+            inner,
+            span,
+        }));
+    }
+    let group = match open.as_str() {
+        "(" => Group::Parentheses(Parentheses { inner, span }),
+        "[" => Group::Brackets(Brackets { inner, span }),
+        "{" => Group::Braces(Braces { inner, span }),
+        "⟨" => Group::Angles(Angles { inner, span }),
+        "(x" => Group::XGroup(XGroup { inner, span }),
+        "|" => Group::Abs(Abs { inner, span }),
+        "⌊" => Group::Floor(Floor { inner, span }),
+        "⌈" => Group::Ceil(Ceil { inner, span }),
+        _ => Group::Norm(Norm { inner, span }),
+    };
+
+    Ok(group)
+}
+
+/// Returns the grouping of a fence delimiter
+fn grouping(text: &str, opening: bool) -> Option<Grouping> {
+    let grouping = match (text, opening) {
+        ("(", true) => Grouping::RParen,
+        (")", false) => Grouping::LParen,
+        ("[", true) => Grouping::RBracket,
+        ("]", false) => Grouping::LBracket,
+        ("{", true) => Grouping::RBrace,
+        ("}", false) => Grouping::LBrace,
+        ("", true) => Grouping::RBraceHidden,
+        ("", false) => Grouping::LBraceHidden,
+        ("⟨", true) => Grouping::LAngle,
+        ("⟩", false) => Grouping::RAngle,
+        ("(x", true) => Grouping::RXPar,
+        ("x)", false) => Grouping::LXPar,
+        ("|", _) => Grouping::Abs,
+        ("⌊", _) | ("⌋", _) => Grouping::Floor,
+        ("⌈", _) | ("⌉", _) => Grouping::Ceil,
+        ("||", _) => Grouping::Norm,
+        _ => return None,
+    };
+
+    Some(grouping)
+}
+
+/// Converts the rows of a table into the cells of a matrix
+fn table(node: Node) -> MathMLResult<Vec<Vec<Expression>>> {
+    children(node)
+        .into_iter()
+        .filter(|row| matches!(name(*row), "mtr" | "mlabeledtr"))
+        .map(|row| {
+            children(row)
+                .into_iter()
+                .filter(|cell| name(*cell) == "mtd")
+                .map(|cell| contents(&children(cell)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Converts the contents of a group or cell that `ToMathML` writes as a single row
+fn contents(nodes: &[Node]) -> MathMLResult<Expression> {
+    match nodes {
+        [node] if name(*node) == "mrow" && !is_group(&children(*node)) => {
+            expression(&children(*node))
+        }
+        _ => expression(nodes),
+    }
+}
+
+/// Converts the children of an element that takes a single operand
+/// like `msqrt` which has an inferred row if there are several children
+fn inner(nodes: &[Node]) -> MathMLResult<Element> {
+    match nodes {
+        [node] => required(*node),
+        _ => {
+            let mut expression = expression(nodes)?;
+            Ok(match expression.children.len() {
+                0 => Element::Null,
+                1 => expression.children.pop().unwrap(),
+                _ => non_enclosed(expression),
+            })
+        }
+    }
+}
+
+/// Converts the operand of an accent or over- and underset which `ToMathML`
+/// wraps in a row
+fn wrapped(node: Node) -> MathMLResult<Element> {
+    match children(node)[..] {
+        [child] if name(node) == "mrow" => required(child),
+        _ => required(node),
+    }
+}
+
+/// Converts `munder`, `mover` and `munderover` into operators with limits,
+/// accents or over- and undersets
+fn scripts(node: Node) -> MathMLResult<Element> {
+    let tag = name(node);
+    let nodes = arguments(node, if tag == "munderover" { 3 } else { 2 })?;
+    let (bottom, top) = match tag {
+        "munder" => (Some(nodes[1]), None),
+        "mover" => (None, Some(nodes[1])),
+        _ => (Some(nodes[1]), Some(nodes[2])),
+    };
+    let limit = |node: Option<Node>| -> MathMLResult<Option<Box<Element>>> {
+        node.map(|n| required(n).map(Boxed::boxed)).transpose()
+    };
+    let span = Span::default();
+
+    if let Some(Element::Special(special)) = element(nodes[0])? {
+        let (top, bottom) = (limit(top)?, limit(bottom)?);
+        let special = match special {
+            Special::Sum(_) => Some(Special::Sum(Sum { top, bottom, span })),
+            Special::Prod(_) => Some(Special::Prod(Prod { top, bottom, span })),
+            Special::Integral(_) => Some(Special::Integral(Integral { top, bottom, span })),
+            Special::OIntegral(_) => Some(Special::OIntegral(OIntegral { top, bottom, span })),
+            Special::Limit(l) => Some(Special::Limit(Limit {
+                operator: l.operator,
+                top,
+                bottom,
+                span,
+            })),
+            _ => None,
+        };
+        if let Some(special) = special {
+            return Ok(Element::Special(special));
+        }
+    }
+    if tag != "munderover" && name(nodes[1]) == "mo" {
+        if let Some(accent) = accent(&text(nodes[1]), tag == "mover") {
+            return Ok(Element::Accent(ExpressionAccent::Generic(GenericAccent {
+                inner: wrapped(nodes[0])?.boxed(),
+                accent,
+                span,
+            })));
+        }
+    }
+    let accent = match tag {
+        "mover" => ExpressionAccent::OverSet(OverSet {
+            top: wrapped(nodes[1])?.boxed(),
+            bottom: wrapped(nodes[0])?.boxed(),
+            span,
+        }),
+        "munder" => ExpressionAccent::UnderSet(UnderSet {
+            top: wrapped(nodes[0])?.boxed(),
+            bottom: wrapped(nodes[1])?.boxed(),
+            span,
+        }),
+        _ => ExpressionAccent::OverSet(OverSet {
+            top: wrapped(nodes[2])?.boxed(),
+            bottom: Element::Accent(ExpressionAccent::UnderSet(UnderSet {
+                top: wrapped(nodes[0])?.boxed(),
+                bottom: wrapped(nodes[1])?.boxed(),
+                span,
+            }))
+            .boxed(),
+            span,
+        }),
+    };
+
+    Ok(Element::Accent(accent))
+}
+
+/// Returns the accent that is drawn with the given text above or below its operand
+fn accent(text: &str, over: bool) -> Option<Accent> {
+    let accents = if over {
+        vec![
+            Accent::Hat,
+            Accent::Overline,
+            Accent::Vec,
+            Accent::Dot,
+            Accent::DDot,
+            Accent::OverBrace,
+        ]
+    } else {
+        vec![Accent::Underline, Accent::UnderBrace]
+    };
+    let accent = accents
+        .into_iter()
+        .find(|a| decode(&a.to_mathml()).as_deref() == Some(text));
+    if accent.is_some() {
+        return accent;
+    }
+
+    match (text, over) {
+        ("^", true) => Some(Accent::Hat),
+        ("‾", true) | ("¯", true) => Some(Accent::Overline),
+        ("⃗", true) => Some(Accent::Vec),
+        ("˙", true) => Some(Accent::Dot),
+        ("¨", true) => Some(Accent::DDot),
+        ("_", false) | ("̲", false) => Some(Accent::Underline),
+        _ => None,
+    }
+}
+
+/// Binds a function to its argument. Elements that aren't functions
+/// are kept next to the argument.
+fn apply_function(function: Element, argument: Element) -> Vec<Element> {
+    let (head, exponent, subscript) = match &function {
+        Element::Special(Special::Pow(p)) => match &*p.base {
+            Element::Special(Special::Sub(s)) => {
+                (&*s.base, Some(p.exp.clone()), Some(s.lower.clone()))
+            }
+            base => (base, Some(p.exp.clone()), None),
+        },
+        Element::Special(Special::Sub(s)) => (&*s.base, None, Some(s.lower.clone())),
+        head => (head, None, None),
+    };
+    match head {
+        Element::Literal(Literal::Function(f, _)) => {
+            vec![Element::Special(Special::FunctionApplication(
+                FunctionApplication {
+                    function: f.clone(),
+                    exponent,
+                    subscript,
+                    argument: argument.boxed(),
+                    span: Span::default(),
+                },
+            ))]
+        }
+        _ => vec![function, argument],
+    }
+}
+
+fn pow(base: Element, exp: Element) -> Element {
+    Element::Special(Special::Pow(Pow {
+        base: base.boxed(),
+        exp: exp.boxed(),
+        span: Span::default(),
+    }))
+}
+
+fn sub(base: Element, lower: Element) -> Element {
+    Element::Special(Special::Sub(Sub {
+        base: base.boxed(),
+        lower: lower.boxed(),
+        span: Span::default(),
+    }))
+}
+
+fn non_enclosed(inner: Expression) -> Element {
+    Element::Group(Group::NonEnclosed(NonEnclosed {
+        inner: inner.boxed(),
+        span: Span::default(),
+    }))
+}
+
+fn font_command(variant: &str) -> Option<FontCommand> {
+    get_font_mappings()
+        .into_iter()
+        .flat_map(|mapping| mapping.into_values())
+        .find(|font| font.to_mathml() == variant)
+}
+
+/// Converts a node that is required as operand
+fn required(node: Node) -> MathMLResult<Element> {
+    Ok(element(node)?.unwrap_or(Element::Null))
+}
+
+/// Returns the children of a node that needs to have the given number of children
+fn arguments<'a, 'input>(
+    node: Node<'a, 'input>,
+    count: usize,
+) -> MathMLResult<Vec<Node<'a, 'input>>> {
+    let children = children(node);
+    if children.len() == count {
+        Ok(children)
+    } else {
+        Err(MathMLError::ChildCount {
+            element: name(node).to_string(),
+            expected: count,
+            found: children.len(),
+        })
+    }
+}
+
+fn children<'a, 'input>(node: Node<'a, 'input>) -> Vec<Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element()).collect()
+}
+
+fn name<'a>(node: Node<'a, '_>) -> &'a str {
+    node.tag_name().name()
+}
+
+/// Returns the text of a token element without the surrounding whitespace
+fn text(node: Node) -> String {
+    node.descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect::<String>()
+        .trim_matches(|c: char| c.is_ascii_whitespace())
+        .to_string()
+}
+
+/// Replaces named entities with character references because XML only
+/// knows the entities `lt`, `gt`, `amp`, `quot` and `apos`
+fn replace_entities(source: &str) -> MathMLResult<String> {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let name = rest
+            .find(';')
+            .map(|end| &rest[1..end])
+            .filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_alphanumeric()));
+
+        match name {
+            Some(name) if !matches!(name, "lt" | "gt" | "amp" | "quot" | "apos") => {
+                let value =
+                    entity(name).ok_or_else(|| MathMLError::UnknownEntity(name.to_string()))?;
+                for c in value.chars() {
+                    result.push_str(&format!("&#x{:x};", c as u32));
+                }
+                rest = &rest[name.len() + 2..];
+            }
+            _ => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+fn entity(name: &str) -> Option<String> {
+    ENTITIES
+        .iter()
+        .find(|(entity, _)| *entity == name)
+        .map(|(_, value)| value.to_string())
+        .or_else(|| htmlescape::decode_html(&format!("&{};", name)).ok())
+}
+
+/// Returns the text of a MathML snippet written by `ToMathML`
+fn decode(mathml: &str) -> Option<String> {
+    let source = replace_entities(&format!("<mrow>{}</mrow>", mathml)).ok()?;
+    let document = Document::parse(&source).ok()?;
+
+    Some(text(document.root_element()))
+}
+
+/// Creates the mapping from the text of the MathML written for each token to the token.
+/// If several tokens are written the same, the one with the shortest AsciiMath spelling
+/// is used so that `&#8594;` becomes `->` and not `rarr`.
+fn mathml_tokens() -> HashMap<String, Token> {
+    let mut tokens: Vec<(usize, Token, String)> = Vec::new();
+
+    for mapping in get_greek_mappings() {
+        for (patterns, g) in mapping {
+            tokens.push((patterns[0].len(), Token::Greek(g.clone()), g.to_mathml()));
+        }
+    }
+    for mapping in get_relation_mapping() {
+        for (patterns, r) in mapping {
+            tokens.push((patterns[0].len(), Token::Relation(r.clone()), r.to_mathml()));
+        }
+    }
+    for mapping in get_arrow_mapping() {
+        for (patterns, a) in mapping {
+            tokens.push((patterns[0].len(), Token::Arrow(a.clone()), a.to_mathml()));
+        }
+    }
+    for mapping in get_logical_mappings() {
+        for (patterns, l) in mapping {
+            tokens.push((patterns[0].len(), Token::Logical(l.clone()), l.to_mathml()));
+        }
+    }
+    for mapping in get_operation_mappings() {
+        for (patterns, o) in mapping {
+            tokens.push((
+                patterns[0].len(),
+                Token::Operation(o.clone()),
+                o.to_mathml(),
+            ));
+        }
+    }
+    for mapping in get_misc_mappings() {
+        for (patterns, m) in mapping {
+            tokens.push((patterns[0].len(), Token::Misc(m.clone()), m.to_mathml()));
+        }
+    }
+    for mapping in get_function_mappings() {
+        for (pattern, f) in mapping {
+            tokens.push((pattern.len(), Token::Function(f.clone()), f.to_mathml()));
+        }
+    }
+    tokens.sort_by(|(a_length, a, _), (b_length, b, _)| {
+        a_length
+            .cmp(b_length)
+            .then(a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    let mut mapping = HashMap::new();
+
+    for (_, token, mathml) in tokens {
+        if let Some(text) = decode(&mathml).filter(|t| !t.is_empty()) {
+            mapping.entry(text).or_insert(token);
+        }
+    }
+
+    mapping
+}
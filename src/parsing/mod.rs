@@ -1,2 +1,8 @@
+pub mod error;
+pub mod latex;
+pub(crate) mod macros;
+pub mod mathml;
+pub mod symbols;
 pub mod tokenizer;
 pub mod tree_parser;
+pub(crate) mod trie;
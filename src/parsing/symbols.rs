@@ -0,0 +1,105 @@
+use crate::parsing::trie::TokenTrie;
+use crate::tokens::{CustomSymbol, Macro, Token};
+use std::sync::Arc;
+
+/// The default nesting limit of macro expansions
+const MACRO_LIMIT: usize = 32;
+
+/// The symbols recognized by the tokenizer. The default table contains
+/// all built-in token mappings and new symbols can be registered on top of them.
+/// If a pattern is registered that already exists, the new token replaces the old one.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::format::mathml::ToMathML;
+/// use asciimath_rs::parsing::symbols::SymbolTable;
+/// use asciimath_rs::parsing::tokenizer::Tokenizer;
+/// use asciimath_rs::parsing::tree_parser::TreeParser;
+/// use asciimath_rs::tokens::{CustomSymbol, SymbolCategory, Token};
+///
+/// let symbols = SymbolTable::default()
+///     .with_symbol(CustomSymbol::new("hbar", SymbolCategory::Identifier, "&#x210F;", "\\hbar", "ℏ"))
+///     .with_symbol(CustomSymbol::new("-:>", SymbolCategory::Arrow, "&#x21A6;", "\\mapsto", "↦"));
+/// let tokens = Tokenizer::with_symbols("hbar -:> 0", &symbols).parse();
+/// let expression = TreeParser::new(tokens).parse();
+/// assert_eq!(expression.to_mathml(), "<mrow><mi>&#x210F;</mi><mo>&#x21A6;</mo><mn>0</mn></mrow>");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    trie: Arc<TokenTrie>,
+    macro_limit: usize,
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        lazy_static! {
+            static ref BUILT_IN: SymbolTable = SymbolTable {
+                trie: Arc::new(TokenTrie::from_mappings()),
+                macro_limit: MACRO_LIMIT,
+            };
+        }
+        BUILT_IN.clone()
+    }
+}
+
+impl SymbolTable {
+    /// Creates a table without any symbols
+    pub fn empty() -> Self {
+        Self {
+            trie: Arc::new(TokenTrie::new()),
+            macro_limit: MACRO_LIMIT,
+        }
+    }
+
+    /// Registers a custom symbol that is written as its name
+    pub fn with_symbol(self, symbol: CustomSymbol) -> Self {
+        let name = symbol.name.clone();
+        self.with_pattern(&name, Token::Custom(symbol))
+    }
+
+    /// Registers a macro that is expanded by the tokenizer.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use asciimath_rs::format::asciimath::ToAsciiMath;
+    /// use asciimath_rs::parsing::symbols::SymbolTable;
+    /// use asciimath_rs::tokens::Macro;
+    ///
+    /// let symbols = SymbolTable::default().with_macro(Macro::new("Ex", &["X"], "bb\"E\"[X]"));
+    /// let expression = asciimath_rs::parse_with_symbols("Ex(Y)", &symbols);
+    /// assert_eq!(expression.to_asciimath(), "bb \"E\" [Y]");
+    /// ```
+    pub fn with_macro(self, definition: Macro) -> Self {
+        let name = definition.name.clone();
+        self.with_pattern(&name, Token::Macro(definition))
+    }
+
+    /// Sets how deeply macros may be nested in the expansion of a macro
+    /// before the expansion is aborted with an error
+    pub fn with_macro_limit(mut self, limit: usize) -> Self {
+        self.macro_limit = limit;
+
+        self
+    }
+
+    /// Returns how deeply macros may be nested
+    pub fn macro_limit(&self) -> usize {
+        self.macro_limit
+    }
+
+    /// Registers an input string for any token, e.g. an
+    /// alternative spelling of a built-in symbol
+    pub fn with_pattern(mut self, pattern: &str, token: Token) -> Self {
+        Arc::make_mut(&mut self.trie).insert(pattern, token);
+
+        self
+    }
+
+    /// Returns the token of the longest pattern the characters start with
+    /// together with the number of characters of the pattern
+    pub(crate) fn longest_match(&self, chars: &[char]) -> Option<(usize, &Token)> {
+        self.trie.longest_match(chars)
+    }
+}
@@ -1,21 +1,19 @@
+use crate::parsing::error::{ParseError, ParseErrorKind};
+use crate::parsing::macros::{expand, EXPANSION_LIMIT};
+use crate::parsing::symbols::SymbolTable;
+use crate::span::Span;
 use crate::tokens::constants::accents::G_COLOR;
 use crate::tokens::constants::grouping::T_LPAREN;
-use crate::tokens::constants::misc::{A_TEXT, G_NEWLINE, G_NUMALLOWED};
-use crate::tokens::constants::TokenPattern;
-use crate::tokens::mappings::{
-    get_accent_mappings, get_arrow_mapping, get_font_mappings, get_function_mappings,
-    get_greek_mappings, get_grouping_mappings, get_logical_mappings, get_misc_mappings,
-    get_operation_mappings, get_relation_mapping,
-};
-use crate::tokens::{
-    Accent, Arrow, FontCommand, Function, Greek, Grouping, Logical, Misc, Operation, Relation,
-    Text, Token,
-};
+use crate::tokens::constants::misc::{A_TEXT, G_NUMALLOWED, G_T_TEX_GROUP};
+use crate::tokens::{Accent, Grouping, SpannedToken, Text, Token};
 use charred::tapemachine::CharTapeMachine;
-use std::collections::HashMap;
 
 pub struct Tokenizer {
     ctm: CharTapeMachine,
+    chars: Vec<char>,
+    length: usize,
+    symbols: SymbolTable,
+    errors: Vec<ParseError>,
 }
 
 impl Tokenizer {
@@ -29,217 +27,158 @@ impl Tokenizer {
     /// let tokens = tokenizer.parse();
     /// ```
     pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self::with_symbols(text, &SymbolTable::default())
+    }
+
+    /// Creates a new AsciiMath tokenizer that recognizes the symbols of the given table
+    /// instead of only the built-in ones.
+    /// Example:
+    /// ```
+    /// use asciimath_rs::parsing::symbols::SymbolTable;
+    /// use asciimath_rs::parsing::tokenizer::Tokenizer;
+    /// use asciimath_rs::tokens::{CustomSymbol, SymbolCategory, Token};
+    ///
+    /// let hbar = CustomSymbol::new("hbar", SymbolCategory::Identifier, "&#x210F;", "\\hbar", "ℏ");
+    /// let symbols = SymbolTable::default().with_symbol(hbar.clone());
+    /// let tokens = Tokenizer::with_symbols("hbar", &symbols).parse();
+    /// assert_eq!(tokens, vec![Token::Custom(hbar)]);
+    /// ```
+    pub fn with_symbols<S: AsRef<str>>(text: S, symbols: &SymbolTable) -> Self {
         let mut chars = text.as_ref().chars().collect::<Vec<char>>();
+        let length = chars.len();
         chars.push('\n');
         Self {
-            ctm: CharTapeMachine::new(chars),
+            ctm: CharTapeMachine::new(chars.clone()),
+            chars,
+            length,
+            symbols: symbols.clone(),
+            errors: Vec::new(),
         }
     }
 
     /// Parses the input string passed on creation of the tokenizer into a list of tokens
     pub fn parse(&mut self) -> Vec<Token> {
-        let mut tokens = Vec::<Token>::new();
+        self.parse_spanned()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect()
+    }
+
+    /// Parses the input string passed on creation of the tokenizer into a list of tokens
+    /// together with the span of characters each token was parsed from.
+    /// Example:
+    /// ```
+    /// use asciimath_rs::parsing::tokenizer::Tokenizer;
+    ///
+    /// let mut tokenizer = Tokenizer::new("a <= b");
+    /// let tokens = tokenizer.parse_spanned();
+    /// assert_eq!((tokens[2].span.start, tokens[2].span.end), (2, 4));
+    /// ```
+    pub fn parse_spanned(&mut self) -> Vec<SpannedToken> {
+        let tokens = self.tokenize();
+        let mut budget = EXPANSION_LIMIT;
+
+        expand(tokens, &self.symbols, 0, &mut budget, &mut self.errors)
+    }
+
+    /// Parses the input string into tokens without expanding macros
+    pub(crate) fn tokenize(&mut self) -> Vec<SpannedToken> {
+        let mut tokens = Vec::<SpannedToken>::new();
         self.ctm.seek_whitespace();
 
         while !self.ctm.check_eof() {
-            if let Some(grouping) = self.parse_grouping() {
-                tokens.push(Token::Grouping(grouping))
-            } else if let Some(arrow) = self.parse_arrows() {
-                tokens.push(Token::Arrow(arrow))
-            } else if let Some(logical) = self.parse_logical() {
-                tokens.push(Token::Logical(logical))
-            } else if let Some(relation) = self.parse_relation() {
-                tokens.push(Token::Relation(relation))
-            } else if let Some(operation) = self.parse_operation() {
-                tokens.push(Token::Operation(operation))
-            } else if let Some(misc) = self.parse_misc() {
-                tokens.push(Token::Misc(misc))
-            } else if let Some(accent) = self.parse_accent() {
-                tokens.push(Token::Accent(accent))
-            } else if let Some(greek) = self.parse_greek() {
-                tokens.push(Token::Greek(greek))
-            } else if let Some(font) = self.parse_font_command() {
-                tokens.push(Token::Font(font))
-            } else if let Some(function) = self.parse_function() {
-                tokens.push(Token::Function(function))
+            let start = self.ctm.get_index();
+            let token = if let Some(text) = self.parse_latex_text(start) {
+                Token::Text(text)
+            } else if let Some(color) = self.parse_color(start) {
+                Token::Accent(color)
+            } else if let Some(token) = self.parse_mapped() {
+                token
             } else if let Some(whitespace) = self.parse_whitespace() {
-                tokens.push(Token::Text(whitespace))
-            } else if let Some(text) = self.parse_text() {
-                tokens.push(Token::Text(text))
+                Token::Text(whitespace)
+            } else if let Some(text) = self.parse_text(start) {
+                Token::Text(text)
             } else if let Some(number) = self.parse_number() {
-                tokens.push(Token::Text(number))
+                Token::Text(number)
             } else {
-                tokens.push(Token::Text(Text::Symbol(
-                    self.ctm.get_current().to_string(),
-                )))
-            }
+                Token::Text(Text::Symbol(self.ctm.get_current().to_string()))
+            };
+            tokens.push(SpannedToken {
+                token,
+                span: self.span_from(start),
+            });
             let _ = self.ctm.seek_one();
         }
         // stripping the whitespace at the end
-        if let Some(Token::Text(Text::Whitespace)) = tokens.last() {
+        if let Some(Token::Text(Text::Whitespace)) = tokens.last().map(|t| &t.token) {
             tokens.pop().unwrap();
         }
 
         tokens
     }
 
-    fn parse_misc(&mut self) -> Option<Misc> {
-        lazy_static! {
-            static ref MISC_MAPPINGS: Vec<HashMap<TokenPattern, Misc>> = get_misc_mappings();
-        }
-        for mapping in MISC_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
-        }
-        None
-    }
+    /// Parses the input string like `parse` but returns the errors encountered
+    /// while tokenizing instead of the tokens if there were any.
+    pub fn try_parse(&mut self) -> Result<Vec<Token>, Vec<ParseError>> {
+        let tokens = self.parse();
 
-    fn parse_operation(&mut self) -> Option<Operation> {
-        lazy_static! {
-            static ref OPERATION_MAPPINGS: Vec<HashMap<TokenPattern, Operation>> =
-                get_operation_mappings();
-        }
-        for mapping in OPERATION_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
+        if self.errors.is_empty() {
+            Ok(tokens)
+        } else {
+            Err(self.errors.clone())
         }
-        None
     }
 
-    fn parse_relation(&mut self) -> Option<Relation> {
-        lazy_static! {
-            static ref RELATION_MAPPINGS: Vec<HashMap<TokenPattern, Relation>> =
-                get_relation_mapping();
-        }
-        for mapping in RELATION_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
-        }
-        None
+    /// Returns the errors that were encountered while parsing
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
     }
 
-    fn parse_logical(&mut self) -> Option<Logical> {
-        lazy_static! {
-            static ref LOGICAL_MAPPINGS: Vec<HashMap<TokenPattern, Logical>> =
-                get_logical_mappings();
-        }
-        for mapping in LOGICAL_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
-        }
-        None
+    /// Returns the span from the given start index to the current character
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(start, (self.ctm.get_index() + 1).min(self.length))
     }
 
-    fn parse_grouping(&mut self) -> Option<Grouping> {
-        lazy_static! {
-            static ref GROUPING_MAPPINGS: Vec<HashMap<TokenPattern, Grouping>> =
-                get_grouping_mappings();
-        }
-        for mapping in GROUPING_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
-        }
-        None
-    }
+    /// Parses the token with the longest pattern starting at the current character
+    fn parse_mapped(&mut self) -> Option<Token> {
+        let index = self.ctm.get_index();
+        let (length, token) = self
+            .symbols
+            .longest_match(&self.chars[index..self.length])?;
+        self.ctm.rewind(index + length - 1);
 
-    fn parse_arrows(&mut self) -> Option<Arrow> {
-        lazy_static! {
-            static ref ARROW_MAPPINGS: Vec<HashMap<TokenPattern, Arrow>> = get_arrow_mapping();
-        }
-        for mapping in ARROW_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
-            }
-        }
-        None
+        Some(token.clone())
     }
 
-    fn parse_accent(&mut self) -> Option<Accent> {
+    fn parse_color(&mut self, start: usize) -> Option<Accent> {
         if self.ctm.check_any_str_sequence(G_COLOR) {
-            self.ctm.seek_one().unwrap();
-            Some(Accent::Color(
-                self.ctm.get_string_until_any(&[T_LPAREN], &[]).unwrap(),
-            ))
-        } else {
-            lazy_static! {
-                static ref ACCENT_MAPPINGS: Vec<HashMap<TokenPattern, Accent>> =
-                    get_accent_mappings();
-            }
-            for mapping in ACCENT_MAPPINGS.iter() {
-                for key in mapping.keys() {
-                    if self.ctm.check_any_str_sequence(*key) {
-                        return Some(mapping[key].clone());
-                    }
-                }
-            }
-            None
-        }
-    }
+            let mut string = String::new();
+            let mut terminated = false;
 
-    fn parse_greek(&mut self) -> Option<Greek> {
-        lazy_static! {
-            static ref GREEK_MAPPINGS: Vec<HashMap<TokenPattern, Greek>> = get_greek_mappings();
-        }
-        for mapping in GREEK_MAPPINGS.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_any_str_sequence(*key) {
-                    return Some(mapping[key].clone());
+            while let Some(ch) = self.ctm.next_char() {
+                if ch == T_LPAREN {
+                    terminated = true;
+                    break;
                 }
-            }
-        }
-        None
-    }
-
-    fn parse_font_command(&mut self) -> Option<FontCommand> {
-        lazy_static! {
-            static ref FONTC_MAPPING: Vec<HashMap<&'static str, FontCommand>> = get_font_mappings();
-        }
-        for mapping in FONTC_MAPPING.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_str_sequence(*key) {
-                    return Some(mapping[key].clone());
+                // the newline appended to the input isn't part of the color
+                if self.ctm.get_index() >= self.length {
+                    break;
                 }
+                string.push(ch);
             }
-        }
-        None
-    }
-
-    fn parse_function(&mut self) -> Option<Function> {
-        lazy_static! {
-            static ref FUNCTION_MAPPING: Vec<HashMap<&'static str, Function>> =
-                get_function_mappings();
-        }
-        for mapping in FUNCTION_MAPPING.iter() {
-            for key in mapping.keys() {
-                if self.ctm.check_str_sequence(*key) {
-                    return Some(mapping[key].clone());
-                }
+            if !terminated {
+                self.errors.push(ParseError::new(
+                    ParseErrorKind::UnbalancedGrouping(Grouping::RParen),
+                    self.span_from(start),
+                ));
             }
+            Some(Accent::Color(string))
+        } else {
+            None
         }
-        None
     }
 
     fn parse_whitespace(&mut self) -> Option<Text> {
-        if self.ctm.check_any_str_sequence(G_NEWLINE) {
-            return Some(Text::NewLine);
-        }
         if self.ctm.get_current().is_whitespace() {
             self.ctm.seek_whitespace();
             self.ctm.rewind(self.ctm.get_index() - 1);
@@ -249,16 +188,58 @@ impl Tokenizer {
         }
     }
 
-    fn parse_text(&mut self) -> Option<Text> {
+    fn parse_text(&mut self, start: usize) -> Option<Text> {
         if self.ctm.check_char(&A_TEXT) {
             let mut string = String::new();
+            let mut terminated = false;
 
             while let Some(ch) = self.ctm.next_char() {
                 if ch == A_TEXT {
+                    terminated = true;
+                    break;
+                }
+                // the newline appended to the input isn't part of the text
+                if self.ctm.get_index() >= self.length {
+                    break;
+                }
+                string.push(ch);
+            }
+            if !terminated {
+                self.errors.push(ParseError::new(
+                    ParseErrorKind::UnterminatedText,
+                    self.span_from(start),
+                ));
+            }
+            Some(Text::Plain(string))
+        } else {
+            None
+        }
+    }
+
+    /// Parses `text(...)` and `mbox(...)` into plain text with the raw content
+    /// of the parentheses
+    fn parse_latex_text(&mut self, start: usize) -> Option<Text> {
+        if self.ctm.check_any_str_sequence(G_T_TEX_GROUP) {
+            let mut string = String::new();
+            let mut terminated = false;
+
+            while let Some(ch) = self.ctm.next_char() {
+                if ch == T_LPAREN {
+                    terminated = true;
+                    break;
+                }
+                // the newline appended to the input isn't part of the text
+                if self.ctm.get_index() >= self.length {
                     break;
                 }
                 string.push(ch);
             }
+            if !terminated {
+                self.errors.push(ParseError::new(
+                    ParseErrorKind::UnterminatedText,
+                    self.span_from(start),
+                ));
+            }
             Some(Text::Plain(string))
         } else {
             None
@@ -270,7 +251,7 @@ impl Tokenizer {
             let mut string = self.ctm.get_current().to_string();
 
             while let Some(ch) = self.ctm.next_char() {
-                if ch.is_numeric() || self.ctm.check_any(&G_NUMALLOWED) {
+                if ch.is_numeric() || self.ctm.check_any(G_NUMALLOWED) {
                     string.push(ch);
                 } else {
                     break;
@@ -1,24 +1,36 @@
 use crate::elements::accent::{Color, ExpressionAccent, GenericAccent, OverSet, UnderSet};
 use crate::elements::group::{
-    Abs, Angles, Braces, Brackets, Ceil, Floor, Group, Matrix, Norm, Parentheses, Vector, XGroup,
+    Abs, Angles, Braces, Brackets, Cases, Ceil, Fenced, Floor, Group, Matrix, Norm, Parentheses,
+    Vector, XGroup,
 };
 use crate::elements::literal::{Literal, Number, PlainText, Symbol};
 use crate::elements::special::{
-    Expression, Frac, Integral, OIntegral, Pow, Prod, Root, Special, Sqrt, Sub, Sum,
+    Expression, Frac, FunctionApplication, Integral, Limit, LimitOperator, OIntegral, Pow, Prod,
+    Root, Special, Sqrt, Sub, Sum,
 };
 use crate::elements::Element;
-use crate::tokens::{Accent, FontCommand, Grouping, Misc, Operation, Text, Token};
+use crate::parsing::error::{ParseError, ParseErrorKind};
+use crate::span::Span;
+use crate::tokens::constants::accents::G_NAMED_COLORS;
+use crate::tokens::{
+    Accent, FontCommand, Function, Grouping, Misc, Operation, Relation, SpannedToken,
+    SymbolCategory, Text, Token,
+};
 use crate::utils::Boxed;
 
 pub struct TreeParser {
-    tokens: Vec<Token>,
+    tokens: Vec<SpannedToken>,
     index: usize,
     group_return: bool,
+    depth: usize,
+    errors: Vec<ParseError>,
 }
 
 impl TreeParser {
     /// Creates a new TreeParser that takes tokens parsed by the Tokenizer. The parse method
     /// can be used to create the Expression tree.
+    /// The spans of the elements in the tree are only set when the tokens were parsed
+    /// with `Tokenizer::parse_spanned`.
     /// Example:
     /// ```
     /// use asciimath_rs::parsing::tokenizer::Tokenizer;
@@ -32,11 +44,13 @@ impl TreeParser {
     /// let mut tree_parser = TreeParser::new(tokens);
     /// tree_parser.parse();
     /// ```
-    pub fn new(tokens: Vec<Token>) -> Self {
+    pub fn new<T: Into<SpannedToken>>(tokens: Vec<T>) -> Self {
         Self {
-            tokens,
+            tokens: tokens.into_iter().map(T::into).collect(),
             index: 0,
             group_return: false,
+            depth: 0,
+            errors: Vec::new(),
         }
     }
 
@@ -46,20 +60,52 @@ impl TreeParser {
         self.parse_expression()
     }
 
+    /// Creates an expression tree like `parse` but returns the errors encountered
+    /// while parsing instead of the expression if there were any.
+    /// Example:
+    /// ```
+    /// use asciimath_rs::parsing::tokenizer::Tokenizer;
+    /// use asciimath_rs::parsing::tree_parser::TreeParser;
+    ///
+    /// let mut tokenizer = Tokenizer::new("frac{a}");
+    /// let tokens = tokenizer.parse();
+    ///
+    /// let mut tree_parser = TreeParser::new(tokens);
+    /// assert!(tree_parser.try_parse().is_err());
+    /// ```
+    pub fn try_parse(&mut self) -> Result<Expression, Vec<ParseError>> {
+        let expression = self.parse();
+
+        if self.errors.is_empty() {
+            Ok(expression)
+        } else {
+            Err(self.errors.clone())
+        }
+    }
+
+    /// Returns the errors that were encountered while parsing
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
     fn remove_whitespace(&mut self) {
         self.tokens = self
             .tokens
             .iter()
             .filter_map(|t| {
-                if let Token::Text(Text::Whitespace) = t {
+                if let Token::Text(Text::Whitespace) = t.token {
                     None
                 } else {
                     Some(t.clone())
                 }
             })
             .collect();
+        let end = self.tokens.last().map(|t| t.span.end).unwrap_or(0);
         // add null end token to ensure that everything got parsed
-        self.tokens.push(Token::End)
+        self.tokens.push(SpannedToken {
+            token: Token::End,
+            span: Span::new(end, end),
+        })
     }
 
     fn step(&mut self) -> bool {
@@ -77,28 +123,41 @@ impl TreeParser {
     }
 
     fn peek(&mut self) -> Option<&Token> {
-        self.tokens.get(self.index + 1)
+        self.tokens.get(self.index + 1).map(|t| &t.token)
     }
 
     fn current_token(&self) -> &Token {
-        self.tokens.get(self.index).unwrap()
+        &self.tokens.get(self.index).unwrap().token
+    }
+
+    fn current_span(&self) -> Span {
+        self.tokens.get(self.index).unwrap().span
+    }
+
+    /// Returns the span from the token at the given index to the current token
+    fn span_from(&self, index: usize) -> Span {
+        self.tokens
+            .get(index)
+            .unwrap()
+            .span
+            .merge(&self.current_span())
     }
 
     fn parse_expression(&mut self) -> Expression {
         let mut expression = Expression::default();
+        let start_index = self.index;
+        let mut end_index = None;
 
         while !self.end_reached() {
             if let Some(element) = self.parse_element() {
                 // parse elements that are based on the previous one
-                if let Some(pow) = self.parse_pow_element(&element) {
-                    expression.add_child(Element::Special(Special::Pow(pow)))
-                } else if let Some(frac) = self.parse_frac_element(&element) {
+                let element = self.parse_scripts(element);
+                if let Some(frac) = self.parse_frac_element(&element) {
                     expression.add_child(Element::Special(Special::Frac(frac)))
-                } else if let Some(sub) = self.parse_sub_element(&element) {
-                    expression.add_child(Element::Special(Special::Sub(sub)))
                 } else {
                     expression.add_child(element);
                 }
+                end_index = Some(self.index);
             }
             if self.group_return {
                 break;
@@ -106,19 +165,32 @@ impl TreeParser {
             self.step();
         }
         self.group_return = false;
+        let start = self.tokens.get(start_index).unwrap().span;
+        expression.span = match end_index {
+            Some(end_index) => start.merge(&self.tokens.get(end_index).unwrap().span),
+            None => Span::new(start.start, start.start),
+        };
 
         expression
     }
 
     fn parse_element(&mut self) -> Option<Element> {
         let token = self.current_token().clone();
+        let span = self.current_span();
+        let has_sub = matches!(self.peek(), Some(Token::Misc(Misc::Sub)));
         match token {
-            Token::Arrow(a) => Some(Element::Literal(Literal::Arrow(a))),
-            Token::Logical(l) => Some(Element::Literal(Literal::Logical(l))),
-            Token::Relation(r) => Some(Element::Literal(Literal::Relation(r))),
-            Token::Greek(g) => Some(Element::Literal(Literal::Greek(g))),
-            Token::Function(f) => Some(Element::Literal(Literal::Function(f))),
-            Token::Text(t) => self.parse_text(t).map(Element::Literal),
+            Token::Arrow(a) => Some(Element::Literal(Literal::Arrow(a, span))),
+            Token::Logical(l) => Some(Element::Literal(Literal::Logical(l, span))),
+            // `sup`, `max` and `min` are only limit operators when they have a lower limit
+            Token::Relation(Relation::SupSet) if has_sub => {
+                Some(self.parse_limit(LimitOperator::Sup))
+            }
+            Token::Function(Function::Max) if has_sub => Some(self.parse_limit(LimitOperator::Max)),
+            Token::Function(Function::Min) if has_sub => Some(self.parse_limit(LimitOperator::Min)),
+            Token::Relation(r) => Some(Element::Literal(Literal::Relation(r, span))),
+            Token::Greek(g) => Some(Element::Literal(Literal::Greek(g, span))),
+            Token::Function(f) => Some(self.parse_function(f)),
+            Token::Text(t) => self.parse_text(t, span).map(Element::Literal),
             Token::Operation(op) => Some(self.parse_operation(op)),
             Token::Misc(m) => Some(self.parse_misc(m)),
             Token::Grouping(g) => {
@@ -126,269 +198,429 @@ impl TreeParser {
                     Some(Element::Group(group))
                 } else if let Some(group) = self.parse_vector() {
                     Some(Element::Group(group))
+                } else if let Some(group) = self.parse_cases() {
+                    Some(Element::Group(group))
                 } else {
                     self.parse_group(g).map(Element::Group)
                 }
             }
             Token::Font(f) => self.parse_formatted_text(f).map(Element::Literal),
-            Token::Accent(a) => self.parse_accent(a).map(Element::Accent),
+            Token::Accent(a) => Some(Element::Accent(self.parse_accent(a))),
+            Token::Custom(c) => Some(Element::Literal(Literal::Custom(c, span))),
             _ => None,
         }
     }
 
-    fn parse_accent(&mut self, token: Accent) -> Option<ExpressionAccent> {
+    fn parse_accent(&mut self, token: Accent) -> ExpressionAccent {
+        let operator = Token::Accent(token.clone());
+        let start_index = self.index;
+
         match token {
             Accent::OverSet => {
                 self.step();
-                let top = self.parse_element()?.to_non_enclosed().boxed();
+                let top = self.parse_operand(&operator).to_non_enclosed().boxed();
                 self.step();
-                let bottom = self.parse_element()?.to_non_enclosed().boxed();
-                Some(ExpressionAccent::OverSet(OverSet { top, bottom }))
+                let bottom = self.parse_operand(&operator).to_non_enclosed().boxed();
+                let span = self.span_from(start_index);
+                ExpressionAccent::OverSet(OverSet { top, bottom, span })
             }
             Accent::UnderSet => {
                 self.step();
-                let bottom = self.parse_element()?.to_non_enclosed().boxed();
+                let bottom = self.parse_operand(&operator).to_non_enclosed().boxed();
                 self.step();
-                let top = self.parse_element()?.to_non_enclosed().boxed();
-                Some(ExpressionAccent::UnderSet(UnderSet { top, bottom }))
+                let top = self.parse_operand(&operator).to_non_enclosed().boxed();
+                let span = self.span_from(start_index);
+                ExpressionAccent::UnderSet(UnderSet { top, bottom, span })
             }
             Accent::Color(color) => {
+                if !is_valid_color(&color) {
+                    self.errors.push(ParseError::new(
+                        ParseErrorKind::UnknownColor(color.clone()),
+                        self.current_span(),
+                    ));
+                }
                 self.step();
-                let inner = self.parse_element()?.to_non_enclosed().boxed();
-                Some(ExpressionAccent::Color(Color { color, inner }))
+                let inner = self.parse_operand(&operator).to_non_enclosed().boxed();
+                let span = self.span_from(start_index);
+                ExpressionAccent::Color(Color { color, inner, span })
             }
             _ => {
                 self.step();
-                Some(ExpressionAccent::Generic(GenericAccent {
-                    inner: self.parse_element()?.to_non_enclosed().boxed(),
+                let inner = self.parse_operand(&operator).to_non_enclosed().boxed();
+                ExpressionAccent::Generic(GenericAccent {
+                    inner,
                     accent: token,
-                }))
+                    span: self.span_from(start_index),
+                })
             }
         }
     }
 
     fn parse_formatted_text(&mut self, token: FontCommand) -> Option<Literal> {
         let next_token = self.peek().cloned();
+        let start_index = self.index;
         if let Some(Token::Text(Text::Plain(p))) = next_token {
             self.step();
             Some(Literal::Text(PlainText {
                 text: p,
                 formatting: Some(token),
+                span: self.span_from(start_index),
             }))
         } else {
             Some(Literal::Symbol(Symbol {
                 symbol: token.to_string(),
+                span: self.current_span(),
             }))
         }
     }
 
-    fn parse_text(&self, token: Text) -> Option<Literal> {
+    fn parse_text(&self, token: Text, span: Span) -> Option<Literal> {
         match token {
-            Text::Symbol(s) => Some(Literal::Symbol(Symbol { symbol: s })),
-            Text::Number(n) => Some(Literal::Number(Number { number: n })),
+            Text::Symbol(s) => Some(Literal::Symbol(Symbol { symbol: s, span })),
+            Text::Number(n) => Some(Literal::Number(Number { number: n, span })),
             Text::Plain(p) => Some(Literal::Text(PlainText {
                 text: p,
                 formatting: None,
+                span,
             })),
-            Text::NewLine => Some(Literal::NewLine),
+            Text::NewLine => Some(Literal::NewLine(span)),
             _ => None,
         }
     }
 
     fn parse_operation(&mut self, token: Operation) -> Element {
+        let start_index = self.index;
+
         match token {
-            Operation::Sum => Element::Special(Special::Sum(Sum {
-                bottom: self.parse_sub(),
-                top: self.parse_pow(),
-            })),
-            Operation::Prod => Element::Special(Special::Prod(Prod {
-                bottom: self.parse_sub(),
-                top: self.parse_pow(),
-            })),
-            _ => Element::Literal(Literal::Operation(token)),
+            Operation::Sum => {
+                let bottom = self.parse_sub();
+                let top = self.parse_pow();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Sum(Sum { bottom, top, span }))
+            }
+            Operation::Prod => {
+                let bottom = self.parse_sub();
+                let top = self.parse_pow();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Prod(Prod { bottom, top, span }))
+            }
+            Operation::Lim => self.parse_limit(LimitOperator::Lim),
+            Operation::BigLim => self.parse_limit(LimitOperator::BigLim),
+            Operation::Sup => self.parse_limit(LimitOperator::Sup),
+            Operation::Inf => self.parse_limit(LimitOperator::Inf),
+            _ => Element::Literal(Literal::Operation(token, self.current_span())),
         }
     }
 
     fn parse_misc(&mut self, token: Misc) -> Element {
+        let operator = Token::Misc(token.clone());
+        let start_index = self.index;
+
         match token {
             Misc::LatexFrac => {
                 self.step();
-                Element::Special(Special::Frac(Frac {
-                    top: self.parse_element().unwrap_or(Element::Null).boxed(),
-                    bottom: self.parse_element().unwrap_or(Element::Null).boxed(),
-                }))
+                let top = self.parse_operand(&operator).boxed();
+                self.step();
+                let bottom = self.parse_operand(&operator).boxed();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Frac(Frac { top, bottom, span }))
             }
             Misc::Sqrt => {
                 self.step();
-                Element::Special(Special::Sqrt(Sqrt {
-                    inner: self.parse_element().unwrap_or(Element::Null).boxed(),
-                }))
+                let inner = self.parse_operand(&operator).boxed();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Sqrt(Sqrt { inner, span }))
             }
             Misc::Root => {
                 self.step();
-                let base = self.parse_element().unwrap_or(Element::Null).boxed();
+                let base = self.parse_operand(&operator).boxed();
                 self.step();
-                let inner = self.parse_element().unwrap_or(Element::Null).boxed();
-                Element::Special(Special::Root(Root { base, inner }))
+                let inner = self.parse_operand(&operator).boxed();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Root(Root { base, inner, span }))
             }
-            Misc::Int => Element::Special(Special::Integral(Integral {
-                bottom: self.parse_sub(),
-                top: self.parse_pow(),
-            })),
-            Misc::OInt => Element::Special(Special::OIntegral(OIntegral {
-                bottom: self.parse_sub(),
-                top: self.parse_pow(),
-            })),
-            _ => Element::Literal(Literal::Misc(token)),
+            Misc::Int => {
+                let bottom = self.parse_sub();
+                let top = self.parse_pow();
+                let span = self.span_from(start_index);
+                Element::Special(Special::Integral(Integral { bottom, top, span }))
+            }
+            Misc::OInt => {
+                let bottom = self.parse_sub();
+                let top = self.parse_pow();
+                let span = self.span_from(start_index);
+                Element::Special(Special::OIntegral(OIntegral { bottom, top, span }))
+            }
+            _ => Element::Literal(Literal::Misc(token, self.current_span())),
         }
     }
 
     fn parse_matrix(&mut self) -> Option<Group> {
-        let token = self.current_token().clone();
         let start_index = self.index;
 
-        if let Token::Grouping(Grouping::RBracket) = token {
-            let mut expressions = Vec::new();
+        self.parse_rows(Grouping::RBracket, Grouping::RBracket, Grouping::LBracket)
+            .map(|inner| {
+                Group::Matrix(Matrix {
+                    inner,
+                    span: self.span_from(start_index),
+                })
+            })
+    }
 
-            while !self.end_reached() {
-                if let Some(Token::Grouping(Grouping::RBracket)) = self.peek() {
-                    self.step();
-                    self.step();
-                    expressions.push(self.parse_expression());
+    fn parse_vector(&mut self) -> Option<Group> {
+        let start_index = self.index;
 
-                    if let Token::Grouping(Grouping::LBracket) = self.current_token() {
-                        self.step();
-                    }
-                    if let Token::Grouping(Grouping::LBracket) = self.current_token() {
-                        break;
-                    }
-                } else {
-                    break;
-                }
-            }
-            // Remapping the expression into a matrix
-            let expression_matrix = self.transform_vec_to_matrix(expressions);
+        self.parse_rows(Grouping::RParen, Grouping::RParen, Grouping::LParen)
+            .map(|inner| {
+                Group::Vector(Vector {
+                    inner,
+                    span: self.span_from(start_index),
+                })
+            })
+    }
 
-            if !self.validate_matrix(&expression_matrix) {
-                self.index = start_index;
-                None
-            } else {
-                Some(Group::Matrix(Matrix {
-                    inner: expression_matrix,
-                }))
-            }
-        } else {
-            None
-        }
+    /// Parses piecewise definitions like `{(x, if x >= 0), (-x, if x < 0):}`
+    fn parse_cases(&mut self) -> Option<Group> {
+        let start_index = self.index;
+
+        self.parse_rows(Grouping::RBrace, Grouping::RParen, Grouping::LBraceHidden)
+            .map(|inner| {
+                Group::Cases(Cases {
+                    inner,
+                    span: self.span_from(start_index),
+                })
+            })
     }
 
-    fn parse_vector(&mut self) -> Option<Group> {
-        let token = self.current_token().clone();
+    /// Parses rows enclosed by the given row grouping inside the opening and closing grouping.
+    /// The rows are split into cells on each MSep.
+    /// If the tokens at the current position aren't a valid table the position is reset
+    /// and `None` is returned.
+    fn parse_rows(
+        &mut self,
+        opening: Grouping,
+        row_opening: Grouping,
+        closing: Grouping,
+    ) -> Option<Vec<Vec<Expression>>> {
         let start_index = self.index;
+        let error_count = self.errors.len();
 
-        if let Token::Grouping(Grouping::RParen) = token {
-            let mut expressions = Vec::new();
+        if *self.current_token() != Token::Grouping(opening) {
+            return None;
+        }
+        let mut expressions = Vec::new();
+        let mut closed = false;
 
-            while !self.end_reached() {
-                if let Some(Token::Grouping(Grouping::RParen)) = self.peek() {
-                    self.step();
-                    self.step();
-                    expressions.push(self.parse_expression());
+        while !self.end_reached() {
+            if self.peek() == Some(&Token::Grouping(row_opening.clone())) {
+                self.step();
+                let opening_span = self.current_span();
+                self.step();
+                expressions.push(self.parse_enclosed_expression(row_opening.clone(), opening_span));
 
-                    if let Token::Grouping(Grouping::LParen) = self.current_token() {
-                        self.step();
-                    }
-                    if let Token::Grouping(Grouping::LParen) = self.current_token() {
-                        break;
-                    }
-                } else {
+                if *self.current_token() == Token::Grouping(closing_grouping(&row_opening)) {
+                    self.step();
+                }
+                if *self.current_token() == Token::Grouping(closing.clone()) {
+                    closed = true;
                     break;
                 }
-            }
-            let expression_matrix = self.transform_vec_to_matrix(expressions);
-
-            if !self.validate_matrix(&expression_matrix) {
-                self.index = start_index;
-                None
             } else {
-                Some(Group::Vector(Vector {
-                    inner: expression_matrix,
-                }))
+                break;
             }
-        } else {
+        }
+        // Remapping the expression into a matrix
+        let expression_matrix = self.transform_vec_to_matrix(expressions);
+
+        if !closed || !self.validate_matrix(&expression_matrix) {
+            // discard errors of the failed attempt as the tokens get parsed again
+            self.errors.truncate(error_count);
+            self.index = start_index;
             None
+        } else {
+            Some(expression_matrix)
         }
     }
 
     fn parse_group(&mut self, token: Grouping) -> Option<Group> {
-        match token {
-            Grouping::RParen => {
-                self.step();
-                let inner = self.parse_expression().boxed();
-                Some(Group::Parentheses(Parentheses { inner }))
-            }
-            Grouping::RBrace => {
-                self.step();
-                let inner = self.parse_expression().boxed();
-
-                Some(Group::Braces(Braces { inner }))
-            }
-            Grouping::RBracket => {
-                self.step();
-                let inner = self.parse_expression().boxed();
+        let start_index = self.index;
+        let opening_span = self.current_span();
 
-                Some(Group::Brackets(Brackets { inner }))
-            }
-            Grouping::RAngle => {
+        match token {
+            Grouping::RParen
+            | Grouping::RBracket
+            | Grouping::RBrace
+            | Grouping::RBraceHidden
+            | Grouping::LAngle => {
                 self.step();
-                let inner = self.parse_expression().boxed();
-
-                Some(Group::Angles(Angles { inner }))
+                let inner = self
+                    .parse_enclosed_expression(token.clone(), opening_span)
+                    .boxed();
+                let span = self.span_from(start_index);
+                let right = match self.current_token() {
+                    Token::Grouping(closing) => closing.clone(),
+                    _ => closing_grouping(&token),
+                };
+
+                Some(match (token, right) {
+                    (Grouping::RParen, Grouping::LParen) => {
+                        Group::Parentheses(Parentheses { inner, span })
+                    }
+                    (Grouping::RBracket, Grouping::LBracket) => {
+                        Group::Brackets(Brackets { inner, span })
+                    }
+                    (Grouping::RBrace, Grouping::LBrace) => Group::Braces(Braces { inner, span }),
+                    (Grouping::LAngle, Grouping::RAngle) => Group::Angles(Angles { inner, span }),
+                    (left, right) => Group::Fenced(Fenced {
+                        left,
+                        right,
+                        inner,
+                        span,
+                    }),
+                })
             }
             Grouping::RXPar => {
                 self.step();
-                let inner = self.parse_expression().boxed();
+                let inner = self
+                    .parse_enclosed_expression(Grouping::RXPar, opening_span)
+                    .boxed();
 
-                Some(Group::XGroup(XGroup { inner }))
+                Some(Group::XGroup(XGroup {
+                    inner,
+                    span: self.span_from(start_index),
+                }))
             }
             Grouping::Abs => {
                 self.step();
                 self.step();
-                let inner = self.parse_expression().boxed();
-                Some(Group::Abs(Abs { inner }))
+                let inner = self
+                    .parse_enclosed_expression(Grouping::Abs, opening_span)
+                    .boxed();
+                Some(Group::Abs(Abs {
+                    inner,
+                    span: self.span_from(start_index),
+                }))
             }
             Grouping::Floor => {
                 self.step();
                 self.step();
-                let inner = self.parse_expression().boxed();
-                Some(Group::Floor(Floor { inner }))
+                let inner = self
+                    .parse_enclosed_expression(Grouping::Floor, opening_span)
+                    .boxed();
+                Some(Group::Floor(Floor {
+                    inner,
+                    span: self.span_from(start_index),
+                }))
             }
             Grouping::Ceil => {
                 self.step();
                 self.step();
-                let inner = self.parse_expression().boxed();
-                Some(Group::Ceil(Ceil { inner }))
+                let inner = self
+                    .parse_enclosed_expression(Grouping::Ceil, opening_span)
+                    .boxed();
+                Some(Group::Ceil(Ceil {
+                    inner,
+                    span: self.span_from(start_index),
+                }))
             }
             Grouping::Norm => {
                 self.step();
                 self.step();
-                let inner = self.parse_expression().boxed();
-                Some(Group::Norm(Norm { inner }))
+                let inner = self
+                    .parse_enclosed_expression(Grouping::Norm, opening_span)
+                    .boxed();
+                Some(Group::Norm(Norm {
+                    inner,
+                    span: self.span_from(start_index),
+                }))
             }
-            Grouping::MSep => Some(Group::MSep),
+            Grouping::MSep => Some(Group::MSep(opening_span)),
             _ => {
+                if self.depth == 0 {
+                    self.errors.push(ParseError::new(
+                        ParseErrorKind::UnbalancedGrouping(token),
+                        opening_span,
+                    ));
+                }
                 self.group_return = true;
                 None
             }
         }
     }
 
+    /// Parses a function with its optional subscript and exponent and applies it to the
+    /// following element. Functions without an argument stay literals.
+    fn parse_function(&mut self, function: Function) -> Element {
+        let start_index = self.index;
+        let span = self.current_span();
+        let subscript = self.parse_sub();
+        let exponent = self.parse_pow();
+
+        if self.peek().is_some_and(starts_operand) {
+            self.step();
+            let argument = self.parse_operand(&Token::Function(function.clone()));
+            // scripts after the argument belong to it (`sin x^2` is `sin(x^2)`) unless
+            // it's in parentheses, then they belong to the application (`sin(x)^2`)
+            let argument = match argument {
+                Element::Group(Group::Parentheses(_)) => argument,
+                argument => self.parse_scripts(argument),
+            }
+            .boxed();
+            let span = self.span_from(start_index);
+
+            return Element::Special(Special::FunctionApplication(FunctionApplication {
+                function,
+                exponent,
+                subscript,
+                argument,
+                span,
+            }));
+        }
+        let mut element = Element::Literal(Literal::Function(function, span));
+
+        if let Some(lower) = subscript {
+            let span = element.span().merge(&lower.span());
+            element = Element::Special(Special::Sub(Sub {
+                base: element.boxed(),
+                lower,
+                span,
+            }));
+        }
+        if let Some(exp) = exponent {
+            let span = element.span().merge(&exp.span());
+            element = Element::Special(Special::Pow(Pow {
+                base: element.boxed(),
+                exp,
+                span,
+            }));
+        }
+
+        element
+    }
+
+    fn parse_limit(&mut self, operator: LimitOperator) -> Element {
+        let start_index = self.index;
+        let bottom = self.parse_sub();
+        let top = self.parse_pow();
+        let span = self.span_from(start_index);
+
+        Element::Special(Special::Limit(Limit {
+            operator,
+            bottom,
+            top,
+            span,
+        }))
+    }
+
     fn parse_sub(&mut self) -> Option<Box<Element>> {
         if let Some(Token::Misc(Misc::Sub)) = self.peek() {
             self.step();
             self.step();
-            self.parse_element()
-                .map(|element| element.to_non_enclosed().boxed())
+            Some(
+                self.parse_operand(&Token::Misc(Misc::Sub))
+                    .to_non_enclosed()
+                    .boxed(),
+            )
         } else {
             None
         }
@@ -398,25 +630,74 @@ impl TreeParser {
         if let Some(Token::Misc(Misc::Pow)) = self.peek() {
             self.step();
             self.step();
-            self.parse_element()
-                .map(|element| element.to_non_enclosed().boxed())
+            Some(
+                self.parse_operand(&Token::Misc(Misc::Pow))
+                    .to_non_enclosed()
+                    .boxed(),
+            )
         } else {
             None
         }
     }
 
+    /// Parses the element at the current position as the operand of the given token.
+    /// If there's no element a missing operand error is recorded and `Element::Null`
+    /// is returned instead.
+    fn parse_operand(&mut self, operator: &Token) -> Element {
+        self.parse_element().unwrap_or_else(|| {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::MissingOperand(operator.clone()),
+                self.current_span(),
+            ));
+            Element::Null
+        })
+    }
+
+    /// Parses an expression that is enclosed by the given opening grouping token.
+    /// If the end is reached before a closing token was found an unbalanced grouping error
+    /// is recorded.
+    fn parse_enclosed_expression(&mut self, opening: Grouping, opening_span: Span) -> Expression {
+        self.depth += 1;
+        let expression = self.parse_expression();
+        self.depth -= 1;
+
+        if let Token::End = self.current_token() {
+            self.errors.push(ParseError::new(
+                ParseErrorKind::UnbalancedGrouping(opening),
+                opening_span,
+            ));
+        }
+
+        expression
+    }
+
+    /// Applies the subscripts and exponents following the element to it,
+    /// `x_1^2` is the power of `x_1`
+    fn parse_scripts(&mut self, mut element: Element) -> Element {
+        loop {
+            if let Some(sub) = self.parse_sub_element(&element) {
+                element = Element::Special(Special::Sub(sub));
+            } else if let Some(pow) = self.parse_pow_element(&element) {
+                element = Element::Special(Special::Pow(pow));
+            } else {
+                return element;
+            }
+        }
+    }
+
     // tries to parse a pow element
     fn parse_pow_element(&mut self, previous: &Element) -> Option<Pow> {
         if let Some(Token::Misc(Misc::Pow)) = self.peek() {
             self.step();
             self.step();
+            let exp = self
+                .parse_operand(&Token::Misc(Misc::Pow))
+                .to_non_enclosed()
+                .boxed();
             Some(Pow {
                 base: previous.clone().boxed(),
-                exp: self
-                    .parse_element()
-                    .unwrap_or(Element::Null)
-                    .to_non_enclosed()
-                    .boxed(),
+                exp,
+                span: previous.span().merge(&self.current_span()),
             })
         } else {
             None
@@ -428,13 +709,14 @@ impl TreeParser {
         if let Some(Token::Misc(Misc::Sub)) = self.peek() {
             self.step();
             self.step();
+            let lower = self
+                .parse_operand(&Token::Misc(Misc::Sub))
+                .to_non_enclosed()
+                .boxed();
             Some(Sub {
                 base: previous.clone().boxed(),
-                lower: self
-                    .parse_element()
-                    .unwrap_or(Element::Null)
-                    .to_non_enclosed()
-                    .boxed(),
+                lower,
+                span: previous.span().merge(&self.current_span()),
             })
         } else {
             None
@@ -446,9 +728,14 @@ impl TreeParser {
         if let Some(Token::Misc(Misc::AsciiFrac)) = self.peek() {
             self.step();
             self.step();
+            let bottom = self
+                .parse_operand(&Token::Misc(Misc::AsciiFrac))
+                .to_non_enclosed()
+                .boxed();
             Some(Frac {
                 top: previous.to_non_enclosed().boxed(),
-                bottom: self.parse_element()?.to_non_enclosed().boxed(),
+                bottom,
+                span: previous.span().merge(&self.current_span()),
             })
         } else {
             None
@@ -463,9 +750,14 @@ impl TreeParser {
                 let children = e.children.clone();
                 let mut expressions = Vec::new();
 
-                for elements in children.split(|e| e == &Element::Group(Group::MSep)) {
+                for elements in children.split(|e| matches!(e, Element::Group(Group::MSep(_)))) {
+                    let span = match (elements.first(), elements.last()) {
+                        (Some(first), Some(last)) => first.span().merge(&last.span()),
+                        _ => Span::default(),
+                    };
                     expressions.push(Expression {
                         children: elements.to_vec(),
+                        span,
                     })
                 }
                 expressions
@@ -487,3 +779,66 @@ impl TreeParser {
         }
     }
 }
+
+/// Returns if the token starts an element that a function can be applied to
+fn starts_operand(token: &Token) -> bool {
+    match token {
+        Token::Text(t) => !matches!(t, Text::Whitespace | Text::NewLine),
+        Token::Greek(_) | Token::Function(_) | Token::Font(_) | Token::Accent(_) => true,
+        Token::Custom(c) => matches!(
+            c.category,
+            SymbolCategory::Identifier | SymbolCategory::Number
+        ),
+        Token::Grouping(g) => matches!(
+            g,
+            Grouping::RParen
+                | Grouping::RBracket
+                | Grouping::RBrace
+                | Grouping::RBraceHidden
+                | Grouping::LAngle
+                | Grouping::Abs
+                | Grouping::Floor
+                | Grouping::Ceil
+                | Grouping::Norm
+        ),
+        Token::Misc(m) => matches!(
+            m,
+            Misc::LatexFrac
+                | Misc::Sqrt
+                | Misc::Root
+                | Misc::Del
+                | Misc::Grad
+                | Misc::EmptySet
+                | Misc::Infty
+                | Misc::Aleph
+                | Misc::Complex
+                | Misc::Natural
+                | Misc::Rational
+                | Misc::Real
+                | Misc::Integer
+        ),
+        _ => false,
+    }
+}
+
+/// Returns the closing counterpart of the given opening grouping
+fn closing_grouping(opening: &Grouping) -> Grouping {
+    match opening {
+        Grouping::RParen => Grouping::LParen,
+        Grouping::RBracket => Grouping::LBracket,
+        Grouping::RBrace => Grouping::LBrace,
+        Grouping::RBraceHidden => Grouping::LBraceHidden,
+        Grouping::LAngle => Grouping::RAngle,
+        Grouping::RXPar => Grouping::LXPar,
+        other => other.clone(),
+    }
+}
+
+/// Checks if the given color is either a named color or a hex color like `#f00` or `#ff0000`
+fn is_valid_color(color: &str) -> bool {
+    if let Some(hex) = color.strip_prefix('#') {
+        [3, 4, 6, 8].contains(&hex.len()) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    } else {
+        G_NAMED_COLORS.contains(&color.to_lowercase().as_str())
+    }
+}
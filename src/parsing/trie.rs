@@ -0,0 +1,135 @@
+use crate::tokens::constants::misc::G_NEWLINE;
+use crate::tokens::mappings::{
+    get_accent_mappings, get_arrow_mapping, get_font_mappings, get_function_mappings,
+    get_greek_mappings, get_grouping_mappings, get_logical_mappings, get_misc_mappings,
+    get_operation_mappings, get_relation_mapping,
+};
+use crate::tokens::{Text, Token};
+
+/// A prefix tree of the patterns of all tokens that is used to find the
+/// longest pattern at a position of the input.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenTrie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    /// The indices of the child nodes sorted by their character
+    children: Vec<(char, usize)>,
+    token: Option<Token>,
+}
+
+impl TokenTrie {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    /// Creates a trie with the patterns of all token mappings
+    pub fn from_mappings() -> Self {
+        let mut trie = Self::new();
+
+        for mapping in get_grouping_mappings() {
+            trie.extend(mapping, Token::Grouping);
+        }
+        for mapping in get_arrow_mapping() {
+            trie.extend(mapping, Token::Arrow);
+        }
+        for mapping in get_logical_mappings() {
+            trie.extend(mapping, Token::Logical);
+        }
+        for mapping in get_relation_mapping() {
+            trie.extend(mapping, Token::Relation);
+        }
+        for mapping in get_operation_mappings() {
+            trie.extend(mapping, Token::Operation);
+        }
+        for mapping in get_misc_mappings() {
+            trie.extend(mapping, Token::Misc);
+        }
+        for mapping in get_accent_mappings() {
+            trie.extend(mapping, Token::Accent);
+        }
+        for mapping in get_greek_mappings() {
+            trie.extend(mapping, Token::Greek);
+        }
+        for mapping in get_font_mappings() {
+            for (pattern, font) in mapping {
+                trie.insert(pattern, Token::Font(font));
+            }
+        }
+        for mapping in get_function_mappings() {
+            for (pattern, function) in mapping {
+                trie.insert(pattern, Token::Function(function));
+            }
+        }
+        for pattern in G_NEWLINE {
+            trie.insert(pattern, Token::Text(Text::NewLine));
+        }
+
+        trie
+    }
+
+    /// Adds all patterns of a mapping with the token created by the given function
+    fn extend<T, I, F>(&mut self, mapping: I, token: F)
+    where
+        I: IntoIterator<Item = (&'static [&'static str], T)>,
+        T: Clone,
+        F: Fn(T) -> Token,
+    {
+        for (patterns, value) in mapping {
+            for pattern in patterns {
+                self.insert(pattern, token(value.clone()));
+            }
+        }
+    }
+
+    /// Adds a pattern to the trie replacing the token of the pattern if it already exists
+    pub fn insert(&mut self, pattern: &str, token: Token) {
+        let mut node = 0;
+
+        for ch in pattern.chars() {
+            node = match self.child(node, ch) {
+                Ok(child) => child,
+                Err(position) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[node].children.insert(position, (ch, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].token = Some(token);
+    }
+
+    /// Returns the token of the longest pattern the characters start with
+    /// together with the number of characters of the pattern
+    pub fn longest_match(&self, chars: &[char]) -> Option<(usize, &Token)> {
+        let mut node = 0;
+        let mut longest = None;
+
+        for (i, ch) in chars.iter().enumerate() {
+            node = match self.child(node, *ch) {
+                Ok(child) => child,
+                Err(_) => break,
+            };
+            if let Some(token) = &self.nodes[node].token {
+                longest = Some((i + 1, token));
+            }
+        }
+
+        longest
+    }
+
+    /// Returns the index of the child node with the given character or the position
+    /// a child with that character needs to be inserted at
+    fn child(&self, node: usize, ch: char) -> Result<usize, usize> {
+        let children = &self.nodes[node].children;
+
+        children
+            .binary_search_by_key(&ch, |(c, _)| *c)
+            .map(|position| children[position].1)
+    }
+}
@@ -0,0 +1,40 @@
+use crate::span::Span;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// An error that was encountered while building the semantic tree of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticError {
+    pub kind: SemanticErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SemanticErrorKind {
+    /// An operator that is missing one of its operands (e.g. `2 +` or `= 1`)
+    MissingOperand,
+    /// An element that doesn't fit into the tree because the table has no precedence
+    /// for implicit multiplication. Contains the AsciiMath source of the element.
+    UnexpectedElement(String),
+}
+
+impl SemanticError {
+    pub fn new(kind: SemanticErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for SemanticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}: ", self.span.start, self.span.end)?;
+        match &self.kind {
+            SemanticErrorKind::MissingOperand => write!(f, "missing operand"),
+            SemanticErrorKind::UnexpectedElement(element) => {
+                write!(f, "unexpected '{}'", element)
+            }
+        }
+    }
+}
+
+impl Error for SemanticError {}
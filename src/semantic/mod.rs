@@ -0,0 +1,299 @@
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::special::{Expression, Special};
+use crate::elements::Element;
+use crate::format::asciimath::ToAsciiMath;
+use crate::semantic::error::{SemanticError, SemanticErrorKind};
+use crate::semantic::precedence::{Associativity, Operator, PrecedenceTable};
+use crate::span::Span;
+use crate::tokens::{Grouping, SymbolCategory};
+use crate::utils::Boxed;
+
+pub mod error;
+pub mod precedence;
+
+type SemanticResult<T> = Result<T, SemanticError>;
+
+/// A node of the precedence aware tree of an expression
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum Node {
+    /// An element that isn't an operator like a number, a fraction or a function application
+    Operand(Element),
+    Unary(Unary),
+    Binary(Binary),
+    Apply(Apply),
+    Tuple(Tuple),
+}
+
+/// A prefix operator like `-` in `-x`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Unary {
+    pub operator: Operator,
+    pub operand: Box<Node>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Binary {
+    pub operator: Operator,
+    pub left: Box<Node>,
+    pub right: Box<Node>,
+    pub span: Span,
+}
+
+/// A sum, product, integral, limit or a function without an argument of its own
+/// applied to the node following it like `sum_(i=1)^n i^2` or `sin -x`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Apply {
+    pub operator: Element,
+    pub operand: Box<Node>,
+    pub span: Span,
+}
+
+/// Comma separated nodes like the contents of `(a, b)`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tuple {
+    pub items: Vec<Node>,
+    pub span: Span,
+}
+
+impl Node {
+    /// Returns the span of the source the node was built from
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Operand(e) => e.span(),
+            Node::Unary(u) => u.span,
+            Node::Binary(b) => b.span,
+            Node::Apply(a) => a.span,
+            Node::Tuple(t) => t.span,
+        }
+    }
+}
+
+impl Boxed for Node {}
+
+/// Builds the precedence aware tree of the flat children of an expression.
+///
+/// Operators are taken from the `Operation`, `Relation`, `Logical` and `Arrow` literals
+/// and bind their operands according to the given table. Adjacent operands are
+/// joined with `Operator::ImplicitMultiplication`. Parentheses and `{: :}` groups
+/// are replaced by the tree of their contents and comma separated contents become a `Tuple`.
+/// All other elements are kept as operands and their expressions can be built separately.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::semantic::precedence::{Operator, PrecedenceTable};
+/// use asciimath_rs::semantic::{build, Node};
+/// use asciimath_rs::tokens::Operation;
+///
+/// let expression = asciimath_rs::parse("a + b * c");
+/// let node = build(&expression, &PrecedenceTable::default()).unwrap();
+///
+/// if let Node::Binary(sum) = node {
+///     assert_eq!(sum.operator, Operator::Operation(Operation::Plus));
+///     assert!(matches!(*sum.right, Node::Binary(_)));
+/// }
+/// ```
+pub fn build(expression: &Expression, table: &PrecedenceTable) -> SemanticResult<Node> {
+    build_elements(&expression.children, expression.span, table)
+}
+
+fn build_elements(
+    elements: &[Element],
+    span: Span,
+    table: &PrecedenceTable,
+) -> SemanticResult<Node> {
+    let is_separator = |e: &Element| matches!(e, Element::Group(Group::MSep(_)));
+
+    if !elements.iter().any(is_separator) {
+        return Builder::new(elements, span, table).build();
+    }
+    let mut items = Vec::new();
+    let mut start = 0;
+
+    for (i, separator) in elements.iter().enumerate().filter(|(_, e)| is_separator(e)) {
+        items.push(Builder::new(&elements[start..i], separator.span(), table).build()?);
+        start = i + 1;
+    }
+    let last = elements[start - 1].span();
+    items.push(Builder::new(&elements[start..], last, table).build()?);
+
+    Ok(Node::Tuple(Tuple { items, span }))
+}
+
+struct Builder<'a> {
+    elements: &'a [Element],
+    index: usize,
+    span: Span,
+    table: &'a PrecedenceTable,
+}
+
+impl<'a> Builder<'a> {
+    fn new(elements: &'a [Element], span: Span, table: &'a PrecedenceTable) -> Self {
+        Self {
+            elements,
+            index: 0,
+            span,
+            table,
+        }
+    }
+
+    fn build(mut self) -> SemanticResult<Node> {
+        let node = self.parse(0)?;
+
+        match self.elements.get(self.index) {
+            Some(element) => Err(SemanticError::new(
+                SemanticErrorKind::UnexpectedElement(element.to_asciimath()),
+                element.span(),
+            )),
+            None => Ok(node),
+        }
+    }
+
+    /// Parses the following elements as long as their operators bind at least
+    /// with the given precedence
+    fn parse(&mut self, min_precedence: u8) -> SemanticResult<Node> {
+        let mut left = self.parse_prefix()?;
+
+        while let Some(element) = self.elements.get(self.index) {
+            let (operator, explicit) = match operator(element) {
+                Some(o) if self.table.infix(&o).is_some() => (o, true),
+                _ => (Operator::ImplicitMultiplication, false),
+            };
+            let binding = match self.table.infix(&operator) {
+                Some(binding) if binding.precedence >= min_precedence => binding,
+                _ => break,
+            };
+            if explicit {
+                self.index += 1;
+            }
+            let right = match binding.associativity {
+                Associativity::Left => self.parse(binding.precedence.saturating_add(1))?,
+                Associativity::Right => self.parse(binding.precedence)?,
+            };
+            let span = left.span().merge(&right.span());
+            left = Node::Binary(Binary {
+                operator,
+                left: left.boxed(),
+                right: right.boxed(),
+                span,
+            });
+        }
+
+        Ok(left)
+    }
+
+    /// Parses an operand together with the prefix operators and big operators before it
+    fn parse_prefix(&mut self) -> SemanticResult<Node> {
+        let element = match self.elements.get(self.index) {
+            Some(element) => element,
+            None => return Err(self.missing_operand()),
+        };
+        self.index += 1;
+
+        if let Some(operator) = operator(element) {
+            if let Some(precedence) = self.table.prefix(&operator) {
+                let operand = self.parse(precedence)?;
+                let span = element.span().merge(&operand.span());
+
+                return Ok(Node::Unary(Unary {
+                    operator,
+                    operand: operand.boxed(),
+                    span,
+                }));
+            }
+            if self.table.infix(&operator).is_some() {
+                return Err(SemanticError::new(
+                    SemanticErrorKind::MissingOperand,
+                    element.span(),
+                ));
+            }
+        }
+        if let Some(precedence) = self.applied_precedence(element) {
+            let operand = self.parse(precedence)?;
+            let span = element.span().merge(&operand.span());
+
+            return Ok(Node::Apply(Apply {
+                operator: element.clone(),
+                operand: operand.boxed(),
+                span,
+            }));
+        }
+
+        operand(element, self.table)
+    }
+
+    /// Returns the precedence of the operand of elements that apply to the following ones
+    fn applied_precedence(&self, element: &Element) -> Option<u8> {
+        match element {
+            Element::Literal(Literal::Function(_, _)) => Some(self.table.application()),
+            Element::Special(Special::Pow(p))
+                if matches!(*p.base, Element::Literal(Literal::Function(_, _))) =>
+            {
+                Some(self.table.application())
+            }
+            Element::Special(Special::Sub(s))
+                if matches!(*s.base, Element::Literal(Literal::Function(_, _))) =>
+            {
+                Some(self.table.application())
+            }
+            Element::Special(Special::Sum(_))
+            | Element::Special(Special::Prod(_))
+            | Element::Special(Special::Integral(_))
+            | Element::Special(Special::OIntegral(_))
+            | Element::Special(Special::Limit(_)) => Some(self.table.big_operator()),
+            _ => None,
+        }
+    }
+
+    /// Returns the error for an operand missing after the last element
+    fn missing_operand(&self) -> SemanticError {
+        let span = match self.elements.last() {
+            Some(last) => Span::new(last.span().end, last.span().end),
+            None => self.span,
+        };
+
+        SemanticError::new(SemanticErrorKind::MissingOperand, span)
+    }
+}
+
+/// Returns the operator of an operator literal
+fn operator(element: &Element) -> Option<Operator> {
+    match element {
+        Element::Literal(Literal::Operation(o, _)) => Some(Operator::Operation(o.clone())),
+        Element::Literal(Literal::Relation(r, _)) => Some(Operator::Relation(r.clone())),
+        Element::Literal(Literal::Logical(l, _)) => Some(Operator::Logical(l.clone())),
+        Element::Literal(Literal::Arrow(a, _)) => Some(Operator::Arrow(a.clone())),
+        Element::Literal(Literal::Custom(c, _))
+            if !matches!(
+                c.category,
+                SymbolCategory::Identifier | SymbolCategory::Number
+            ) =>
+        {
+            Some(Operator::Custom(c.name.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Creates the node of an operand replacing groups that only exist
+/// for grouping with the tree of their contents
+fn operand(element: &Element, table: &PrecedenceTable) -> SemanticResult<Node> {
+    match element {
+        Element::Group(Group::Parentheses(p)) => build(&p.inner, table),
+        Element::Group(Group::NonEnclosed(n)) => build(&n.inner, table),
+        Element::Group(Group::Fenced(f))
+            if f.left == Grouping::RBraceHidden && f.right == Grouping::LBraceHidden =>
+        {
+            build(&f.inner, table)
+        }
+        _ => Ok(Node::Operand(element.clone())),
+    }
+}
@@ -0,0 +1,237 @@
+use crate::tokens::{Arrow, Logical, Operation, Relation};
+
+/// An operator of the semantic tree
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+pub enum Operator {
+    Operation(Operation),
+    Relation(Relation),
+    Logical(Logical),
+    Arrow(Arrow),
+    /// A custom symbol from a `SymbolTable` by its name
+    Custom(String),
+    /// The multiplication of adjacent factors like `2x`
+    ImplicitMultiplication,
+}
+
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// How strongly an infix operator binds its operands
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+pub struct Binding {
+    pub precedence: u8,
+    pub associativity: Associativity,
+}
+
+/// The precedences used to build the semantic tree.
+/// Operators with a higher precedence bind stronger. Operators that
+/// aren't part of the table are treated as operands.
+///
+/// Example:
+///
+/// ```
+/// use asciimath_rs::semantic::precedence::{Associativity, Operator, PrecedenceTable};
+/// use asciimath_rs::tokens::Operation;
+///
+/// // let `xx` bind stronger than implicit multiplication
+/// let table = PrecedenceTable::default().with_infix(
+///     Operator::Operation(Operation::Times),
+///     9,
+///     Associativity::Left,
+/// );
+/// assert_eq!(table.infix(&Operator::Operation(Operation::Times)).unwrap().precedence, 9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecedenceTable {
+    infix: Vec<(Operator, Binding)>,
+    prefix: Vec<(Operator, u8)>,
+    application: u8,
+    big_operator: u8,
+}
+
+impl PrecedenceTable {
+    /// Creates a table without any operators
+    pub fn empty() -> Self {
+        Self {
+            infix: Vec::new(),
+            prefix: Vec::new(),
+            application: u8::MAX,
+            big_operator: u8::MAX,
+        }
+    }
+
+    /// Sets the precedence of an operator written between its operands
+    pub fn with_infix(
+        mut self,
+        operator: Operator,
+        precedence: u8,
+        associativity: Associativity,
+    ) -> Self {
+        let binding = Binding {
+            precedence,
+            associativity,
+        };
+        self.infix.retain(|(o, _)| *o != operator);
+        self.infix.push((operator, binding));
+
+        self
+    }
+
+    /// Sets the precedence of the operand of an operator written before it like `-x`
+    pub fn with_prefix(mut self, operator: Operator, precedence: u8) -> Self {
+        self.prefix.retain(|(o, _)| *o != operator);
+        self.prefix.push((operator, precedence));
+
+        self
+    }
+
+    /// Sets the precedence of the operand of functions without an argument of their own
+    /// like `sin` in `sin -x`
+    pub fn with_application(mut self, precedence: u8) -> Self {
+        self.application = precedence;
+
+        self
+    }
+
+    /// Sets the precedence of the operand of sums, products, integrals and limits
+    pub fn with_big_operator(mut self, precedence: u8) -> Self {
+        self.big_operator = precedence;
+
+        self
+    }
+
+    pub fn infix(&self, operator: &Operator) -> Option<Binding> {
+        self.infix
+            .iter()
+            .find(|(o, _)| o == operator)
+            .map(|(_, binding)| *binding)
+    }
+
+    pub fn prefix(&self, operator: &Operator) -> Option<u8> {
+        self.prefix
+            .iter()
+            .find(|(o, _)| o == operator)
+            .map(|(_, precedence)| *precedence)
+    }
+
+    pub fn application(&self) -> u8 {
+        self.application
+    }
+
+    pub fn big_operator(&self) -> u8 {
+        self.big_operator
+    }
+}
+
+impl Default for PrecedenceTable {
+    /// The usual mathematical precedences from the weakest to the strongest binding:
+    /// `<=>`, `=>` `|--` `|==`, `or`, `and`, `not`, relations and arrows,
+    /// additive operators, multiplicative operators and implicit multiplication,
+    /// unary `+` and `-`.
+    /// Functions apply to a single factor and sums, products, integrals and limits to
+    /// the rest of their term like `evaluation::evaluate` does.
+    fn default() -> Self {
+        use Associativity::{Left, Right};
+
+        let mut table = Self::empty()
+            .with_infix(Operator::Logical(Logical::Iff), 1, Left)
+            .with_infix(Operator::Logical(Logical::Implies), 2, Right)
+            .with_infix(Operator::Logical(Logical::If), 2, Right)
+            .with_infix(Operator::Logical(Logical::VDash), 2, Right)
+            .with_infix(Operator::Logical(Logical::Models), 2, Right)
+            .with_infix(Operator::Logical(Logical::Or), 3, Left)
+            .with_infix(Operator::Logical(Logical::And), 4, Left)
+            .with_prefix(Operator::Logical(Logical::Not), 5);
+
+        for relation in RELATIONS {
+            table = table.with_infix(Operator::Relation(relation.clone()), 6, Left);
+        }
+        for arrow in ARROWS {
+            table = table.with_infix(Operator::Arrow(arrow.clone()), 6, Left);
+        }
+        for operation in ADDITIVE {
+            table = table.with_infix(Operator::Operation(operation.clone()), 7, Left);
+        }
+        for operation in MULTIPLICATIVE {
+            table = table.with_infix(Operator::Operation(operation.clone()), 8, Left);
+        }
+
+        table
+            .with_infix(Operator::ImplicitMultiplication, 8, Left)
+            .with_prefix(Operator::Operation(Operation::Plus), 9)
+            .with_prefix(Operator::Operation(Operation::Minus), 9)
+            .with_application(9)
+            .with_big_operator(8)
+    }
+}
+
+const RELATIONS: &[Relation] = &[
+    Relation::Eq,
+    Relation::Ne,
+    Relation::Lt,
+    Relation::Gt,
+    Relation::Le,
+    Relation::Ge,
+    Relation::Prec,
+    Relation::PrecEq,
+    Relation::Succ,
+    Relation::SuccEq,
+    Relation::In,
+    Relation::NotIn,
+    Relation::SubSet,
+    Relation::SupSet,
+    Relation::SubSetEq,
+    Relation::SupSetEq,
+    Relation::Equiv,
+    Relation::Cong,
+    Relation::Approx,
+    Relation::PropTo,
+];
+
+const ARROWS: &[Arrow] = &[
+    Arrow::UpArrow,
+    Arrow::DownArrow,
+    Arrow::RightArrow,
+    Arrow::To,
+    Arrow::RightArrowTail,
+    Arrow::TwoHeadRightArrow,
+    Arrow::TwoHeadRightArrowTail,
+    Arrow::MapsTo,
+    Arrow::LeftArrow,
+    Arrow::LeftRightArrow,
+    Arrow::BigRightArrow,
+    Arrow::BigLeftArrow,
+    Arrow::BigLeftRightArrow,
+];
+
+const ADDITIVE: &[Operation] = &[
+    Operation::Plus,
+    Operation::Minus,
+    Operation::OPlus,
+    Operation::Backslash,
+    Operation::Cup,
+    Operation::Vee,
+];
+
+const MULTIPLICATIVE: &[Operation] = &[
+    Operation::CDot,
+    Operation::Ast,
+    Operation::Star,
+    Operation::Slash,
+    Operation::Times,
+    Operation::Div,
+    Operation::LTimes,
+    Operation::RTimes,
+    Operation::Bowtie,
+    Operation::Circ,
+    Operation::OTimes,
+    Operation::ODot,
+    Operation::Cap,
+    Operation::Wedge,
+];
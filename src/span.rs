@@ -0,0 +1,60 @@
+use std::ops::Range;
+
+/// A range of characters in the parsed source.
+///
+/// `start` is the index of the first character and `end` the index after
+/// the last character. Both are character (not byte) indices into the source.
+///
+/// Spans are part of the equality of the elements that contain them. Use
+/// `Expression::without_spans` to compare expression trees regardless of the
+/// position they were parsed from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the smallest span that contains both spans
+    pub fn merge(&self, other: &Span) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Returns the number of characters in the span
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Converts the character range into a byte range of the given source string
+    /// Example:
+    /// ```
+    /// use asciimath_rs::span::Span;
+    ///
+    /// let source = "α + β";
+    /// let span = Span::new(4, 5);
+    /// assert_eq!(&source[span.byte_range(source)], "β");
+    /// ```
+    pub fn byte_range(&self, source: &str) -> Range<usize> {
+        let byte_index = |index: usize| {
+            source
+                .char_indices()
+                .nth(index)
+                .map(|(i, _)| i)
+                .unwrap_or_else(|| source.len())
+        };
+
+        byte_index(self.start)..byte_index(self.end)
+    }
+}
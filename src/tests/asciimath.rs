@@ -0,0 +1,128 @@
+use crate::format::asciimath::ToAsciiMath;
+use crate::parse;
+use proptest::prelude::*;
+
+fn assert_round_trip(source: &str) {
+    let expression = parse(source);
+    let printed = expression.to_asciimath();
+    assert_eq!(
+        parse(&printed).without_spans(),
+        expression.without_spans(),
+        "{} printed as {}",
+        source,
+        printed
+    );
+}
+
+#[test]
+fn it_prints_canonical_source() {
+    assert_eq!(parse("a<=b").to_asciimath(), "a <= b");
+    assert_eq!(parse("x^(2)").to_asciimath(), "x ^ {2}");
+    assert_eq!(parse("frac(a)(b)").to_asciimath(), "frac (a) (b)");
+    assert_eq!(parse("(a+b)/c").to_asciimath(), "{a + b} / c");
+    assert_eq!(parse("bb\"A\"").to_asciimath(), "bb \"A\"");
+    assert_eq!(parse("[[1,2],[3,4]]").to_string(), "[[1, 2], [3, 4]]");
+}
+
+#[test]
+fn it_round_trips_literals() {
+    assert_round_trip(
+        "alpha beta gamma Gamma delta Delta epsilon varepsilon zeta eta theta Theta vartheta \
+         iota kappa lambda Lambda mu nu xi Xi pi Pi rho sigma Sigma tau upsilon phi Phi varphi \
+         chi psi Psi omega Omega",
+    );
+    assert_round_trip(
+        "= != < > <= >= -< -<= >- >-= in !in sub sup sube supe -= ~= ~~ prop \
+         and or not => if <=> AA EE _|_ TT |-- |==",
+    );
+    assert_round_trip(
+        "uarr darr rarr -> >-> ->> >->> |-> larr harr rArr lArr hArr \
+         + - * ** *** // setminus xx -: |>< rtimes |><| @ o+ ox o. ^^ ^^^ vv vvv nn nnn uu uuu",
+    );
+    assert_round_trip(
+        "del grad pm O/ oo aleph :. :' |...| |cdots| vdots ddots |\\ | |quad| /_ frown \
+         triangle diamond square |__ rfloor |~ ~| CC NN QQ RR ZZ",
+    );
+    assert_round_trip(
+        "sin cos tan sec csc cot arcsin arccos arctan sinh cosh tanh sech csch coth exp log ln \
+         det dim mod gcd lcm lub glb min max f g",
+    );
+    assert_round_trip("1.5e3 x \"some text\" tt \"mono\" bbb \"R\" a\\\nb");
+    assert_round_trip("text(say \"hi\") mbox( spaced  out ) bb text(a)");
+}
+
+#[test]
+fn it_round_trips_structures() {
+    assert_round_trip("sum_(i=1)^n i^2 + prod_k x_k");
+    assert_round_trip("oint_C f(z) dz");
+    assert_round_trip("frac{a}{b} + a/b + (a)/[b] + frac a (b)");
+    assert_round_trip("sqrt(x) + root 3 (x + 1)");
+    assert_round_trip("hat x + bar(a b) + color(red)(x) + overset(a)(b) + underset a b");
+    assert_round_trip("(: a, b :) + abs(x) + floor(x) + ceil(x) + norm(x) + {a} + [b]");
+    assert_round_trip("[[1,2],[3,4]] ((a),(b)) ((a),(b,c))");
+    assert_round_trip("x^y^z a_b^c");
+    assert_round_trip("{: x, y :} + (a, b] + [c) + {d :} + (: e) + {: :}");
+    assert_round_trip("f(x) = {(x, if x >= 0), (-x, if x < 0):} + {(a), (b):}");
+    assert_round_trip("lim_(x->0) f(x) + Lim_n a_n + sup_(x in S) x + inf_x x");
+    assert_round_trip("sin(2x) + sin^2 x + log_b^2 x + f(x)/2 + sin frac a b + cos sin x");
+    assert_round_trip("max_i a_i + min_(i,j)^n x + max(a, b) + A sup B");
+}
+
+/// Generates AsciiMath sources out of the supported syntax
+fn source() -> impl Strategy<Value = String> {
+    let leaf = prop_oneof![
+        "[a-z]",
+        "[0-9]{1,3}",
+        Just("alpha".to_string()),
+        Just("oo".to_string()),
+        Just("+".to_string()),
+        Just("<=".to_string()),
+        Just("->".to_string()),
+        Just("sin".to_string()),
+        Just("RR".to_string()),
+        Just("\"text\"".to_string()),
+        Just("bb \"B\"".to_string()),
+    ];
+
+    leaf.prop_recursive(4, 32, 4, |inner| {
+        prop_oneof![
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{} {}", a, b)),
+            inner.clone().prop_map(|a| format!("({})", a)),
+            inner.clone().prop_map(|a| format!("[{}]", a)),
+            inner.clone().prop_map(|a| format!("{{{}}}", a)),
+            inner.clone().prop_map(|a| format!("(: {} :)", a)),
+            inner.clone().prop_map(|a| format!("abs({})", a)),
+            inner.clone().prop_map(|a| format!("{{:{}:}}", a)),
+            inner.clone().prop_map(|a| format!("({}]", a)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{}^{}", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("{}_({})", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("({})/({})", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("frac({})({})", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("root({})({})", a, b)),
+            inner.clone().prop_map(|a| format!("sqrt {}", a)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("sum_({})^({})", a, b)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("lim_({}) {}", a, b)),
+            inner.clone().prop_map(|a| format!("hat({})", a)),
+            inner.clone().prop_map(|a| format!("color(blue)({})", a)),
+            (inner.clone(), inner.clone()).prop_map(|(a, b)| format!("overset({})({})", a, b)),
+            (inner.clone(), inner.clone(), inner.clone(), inner.clone())
+                .prop_map(|(a, b, c, d)| format!("[[{}, {}], [{}, {}]]", a, b, c, d)),
+            (inner.clone(), inner.clone(), inner.clone(), inner)
+                .prop_map(|(a, b, c, d)| format!("{{({}, {}), ({}, {}):}}", a, b, c, d)),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn it_round_trips_generated_sources(source in source()) {
+        let expression = parse(&source);
+        let printed = expression.to_asciimath();
+        prop_assert_eq!(
+            parse(&printed).without_spans(),
+            expression.without_spans(),
+            "printed as {}",
+            printed
+        );
+    }
+}
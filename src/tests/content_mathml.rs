@@ -0,0 +1,210 @@
+use crate::format::content_mathml::ToContentMathML;
+use crate::format::error::ContentMathMLErrorKind;
+use crate::parse;
+
+fn content(source: &str) -> String {
+    parse(source)
+        .to_content_mathml()
+        .unwrap_or_else(|e| panic!("{}: {}", source, e))
+}
+
+fn unsupported(source: &str) -> String {
+    match parse(source).to_content_mathml() {
+        Err(e) => match e.kind {
+            ContentMathMLErrorKind::Unsupported(_) => e.to_string(),
+            kind => panic!("{}: unexpected error {:?}", source, kind),
+        },
+        Ok(output) => panic!("{}: unexpected output {}", source, output),
+    }
+}
+
+#[test]
+fn it_converts_arithmetic() {
+    assert_eq!(
+        content("2x + 1"),
+        "<apply><plus/><apply><times/><cn>2</cn><ci>x</ci></apply><cn>1</cn></apply>"
+    );
+    assert_eq!(
+        content("a + b + c"),
+        "<apply><plus/><ci>a</ci><ci>b</ci><ci>c</ci></apply>"
+    );
+    assert_eq!(
+        content("a - b - c"),
+        "<apply><minus/><apply><minus/><ci>a</ci><ci>b</ci></apply><ci>c</ci></apply>"
+    );
+    assert_eq!(content("-x"), "<apply><minus/><ci>x</ci></apply>");
+    assert_eq!(
+        content("frac(a)(b)"),
+        "<apply><divide/><ci>a</ci><ci>b</ci></apply>"
+    );
+    assert_eq!(content("a/b"), content("frac(a)(b)"));
+    assert_eq!(
+        content("x^(n+1)"),
+        "<apply><power/><ci>x</ci><apply><plus/><ci>n</ci><cn>1</cn></apply></apply>"
+    );
+    assert_eq!(
+        content("root(3)(x)"),
+        "<apply><root/><degree><cn>3</cn></degree><ci>x</ci></apply>"
+    );
+    assert_eq!(content("sqrt x"), "<apply><root/><ci>x</ci></apply>");
+    assert_eq!(content("abs(x)"), "<apply><abs/><ci>x</ci></apply>");
+    assert_eq!(content("x_i"), "<ci><msub><mi>x</mi><mi>i</mi></msub></ci>");
+    assert_eq!(content("oo"), "<infinity/>");
+}
+
+#[test]
+fn it_converts_functions() {
+    assert_eq!(content("sin x"), "<apply><sin/><ci>x</ci></apply>");
+    assert_eq!(
+        content("sin^2 x"),
+        "<apply><power/><apply><sin/><ci>x</ci></apply><cn>2</cn></apply>"
+    );
+    assert_eq!(
+        content("log_2 x"),
+        "<apply><log/><logbase><cn>2</cn></logbase><ci>x</ci></apply>"
+    );
+    assert_eq!(
+        content("max(a, b)"),
+        "<apply><max/><ci>a</ci><ci>b</ci></apply>"
+    );
+    assert_eq!(
+        content("f(x)"),
+        "<apply><ci type='function'>f</ci><ci>x</ci></apply>"
+    );
+}
+
+#[test]
+fn it_converts_relations_and_logic() {
+    assert_eq!(
+        content("a = b = c"),
+        "<apply><eq/><ci>a</ci><ci>b</ci><ci>c</ci></apply>"
+    );
+    assert_eq!(
+        content("a < b <= c"),
+        "<apply><and/><apply><lt/><ci>a</ci><ci>b</ci></apply>\
+         <apply><leq/><ci>b</ci><ci>c</ci></apply></apply>"
+    );
+    assert_eq!(
+        content("A supe B"),
+        "<apply><subset/><ci>B</ci><ci>A</ci></apply>"
+    );
+    assert_eq!(
+        content("a and b or not c"),
+        "<apply><or/><apply><and/><ci>a</ci><ci>b</ci></apply>\
+         <apply><not/><ci>c</ci></apply></apply>"
+    );
+    assert_eq!(
+        content("a if b"),
+        "<apply><implies/><ci>b</ci><ci>a</ci></apply>"
+    );
+}
+
+#[test]
+fn it_converts_calculus() {
+    assert_eq!(
+        content("int_0^1 x^2 dx"),
+        "<apply><int/><bvar><ci>x</ci></bvar><lowlimit><cn>0</cn></lowlimit>\
+         <uplimit><cn>1</cn></uplimit><apply><power/><ci>x</ci><cn>2</cn></apply></apply>"
+    );
+    assert_eq!(
+        content("int dx"),
+        "<apply><int/><bvar><ci>x</ci></bvar><cn>1</cn></apply>"
+    );
+    assert_eq!(
+        content("sum_(i=1)^n i"),
+        "<apply><sum/><bvar><ci>i</ci></bvar><lowlimit><cn>1</cn></lowlimit>\
+         <uplimit><ci>n</ci></uplimit><ci>i</ci></apply>"
+    );
+    assert_eq!(
+        content("prod_(p in P) p"),
+        "<apply><product/><bvar><ci>p</ci></bvar>\
+         <domainofapplication><ci>P</ci></domainofapplication><ci>p</ci></apply>"
+    );
+    assert_eq!(
+        content("lim_(x->0) x"),
+        "<apply><limit/><bvar><ci>x</ci></bvar><lowlimit><cn>0</cn></lowlimit><ci>x</ci></apply>"
+    );
+}
+
+#[test]
+fn it_converts_sets() {
+    assert_eq!(content("{1, 2}"), "<set><cn>1</cn><cn>2</cn></set>");
+    assert_eq!(content("{}"), "<set/>");
+    assert_eq!(
+        content("A uu B uu C"),
+        "<apply><union/><ci>A</ci><ci>B</ci><ci>C</ci></apply>"
+    );
+    assert_eq!(
+        content("A nn B"),
+        "<apply><intersect/><ci>A</ci><ci>B</ci></apply>"
+    );
+    assert_eq!(content("x in RR"), "<apply><in/><ci>x</ci><reals/></apply>");
+    assert_eq!(
+        content("(a, b]"),
+        "<interval closure='open-closed'><ci>a</ci><ci>b</ci></interval>"
+    );
+    assert_eq!(
+        content("[0, 1]"),
+        "<interval closure='closed'><cn>0</cn><cn>1</cn></interval>"
+    );
+}
+
+#[test]
+fn it_converts_matrices_and_cases() {
+    assert_eq!(
+        content("((1,2),(3,4))"),
+        "<matrix><matrixrow><cn>1</cn><cn>2</cn></matrixrow>\
+         <matrixrow><cn>3</cn><cn>4</cn></matrixrow></matrix>"
+    );
+    assert_eq!(
+        content("((1),(2))"),
+        "<vector><cn>1</cn><cn>2</cn></vector>"
+    );
+    assert_eq!(
+        content("{(x, if x >= 0),(-x, \"otherwise\"):}"),
+        "<piecewise><piece><ci>x</ci><apply><geq/><ci>x</ci><cn>0</cn></apply></piece>\
+         <otherwise><apply><minus/><ci>x</ci></apply></otherwise></piecewise>"
+    );
+}
+
+#[test]
+fn it_reports_unsupported_constructs() {
+    assert_eq!(
+        unsupported("1 + hat x"),
+        "4..9: 'hat x' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("(a, b)"),
+        "1..5: '(a, b)' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("a -> b"),
+        "0..6: 'a -> b' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("oint f dx"),
+        "0..4: 'oint' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("int x"),
+        "0..5: 'int x' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("sum i"),
+        "0..5: 'sum i' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("dim x"),
+        "0..5: 'dim' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("x!"),
+        "1..2: '!' has no Content MathML counterpart"
+    );
+    assert_eq!(
+        unsupported("f'(x)"),
+        "1..2: ''' has no Content MathML counterpart"
+    );
+    let error = parse("a +").to_content_mathml().unwrap_err();
+    assert_eq!(error.to_string(), "3..3: missing operand");
+}
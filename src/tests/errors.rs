@@ -0,0 +1,144 @@
+use crate::elements::literal::{Literal, Symbol};
+use crate::elements::special::{Expression, Frac, Special};
+use crate::elements::Element;
+use crate::parsing::error::{ParseError, ParseErrorKind};
+use crate::span::Span;
+use crate::tokens::{Accent, Grouping, Misc, Token};
+use crate::utils::Boxed;
+use crate::{parse, try_parse};
+
+#[test]
+fn it_parses_valid_input() {
+    assert_eq!(
+        try_parse("frac{a}{b} + sqrt(2)^3 - color(red)(x) + color(#f0f)(y)"),
+        Ok(parse(
+            "frac{a}{b} + sqrt(2)^3 - color(red)(x) + color(#f0f)(y)"
+        ))
+    );
+    assert!(try_parse("[[1, 2],[3, 4]] + ((1), (2))").is_ok());
+}
+
+#[test]
+fn it_reports_missing_operands() {
+    assert_eq!(
+        try_parse("frac{a}"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::MissingOperand(Token::Misc(Misc::LatexFrac)),
+            Span::new(7, 7)
+        )])
+    );
+    assert_eq!(
+        try_parse("x^"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::MissingOperand(Token::Misc(Misc::Pow)),
+            Span::new(2, 2)
+        )])
+    );
+    assert_eq!(
+        try_parse("root(3)"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::MissingOperand(Token::Misc(Misc::Root)),
+            Span::new(7, 7)
+        )])
+    );
+    assert_eq!(
+        try_parse("(hat)"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::MissingOperand(Token::Accent(Accent::Hat)),
+            Span::new(4, 5)
+        )])
+    );
+}
+
+#[test]
+fn it_still_parses_missing_operands_as_null() {
+    assert_eq!(
+        parse("frac a"),
+        Expression {
+            children: vec![Element::Special(Special::Frac(Frac {
+                top: Element::Literal(Literal::Symbol(Symbol {
+                    symbol: "a".to_string(),
+                    span: Span::new(5, 6)
+                }))
+                .boxed(),
+                bottom: Element::Null.boxed(),
+                span: Span::new(0, 6),
+            }))],
+            span: Span::new(0, 6)
+        }
+    )
+}
+
+#[test]
+fn it_reports_unbalanced_groupings() {
+    assert_eq!(
+        try_parse("(a + (b)"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::UnbalancedGrouping(Grouping::RParen),
+            Span::new(0, 1)
+        )])
+    );
+    assert_eq!(
+        try_parse("a + b]"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::UnbalancedGrouping(Grouping::LBracket),
+            Span::new(5, 6)
+        )])
+    );
+    assert_eq!(
+        try_parse("[[1, 2],[3, 4]"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::UnbalancedGrouping(Grouping::RBracket),
+            Span::new(0, 1)
+        )])
+    );
+}
+
+#[test]
+fn it_reports_unterminated_text() {
+    assert_eq!(
+        try_parse("\"some text"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::UnterminatedText,
+            Span::new(0, 10)
+        )])
+    );
+}
+
+#[test]
+fn it_reports_unclosed_colors() {
+    assert_eq!(
+        try_parse("color(red"),
+        Err(vec![
+            ParseError::new(
+                ParseErrorKind::UnbalancedGrouping(Grouping::RParen),
+                Span::new(0, 9)
+            ),
+            ParseError::new(
+                ParseErrorKind::MissingOperand(Token::Accent(Accent::Color("red".to_string()))),
+                Span::new(9, 9)
+            ),
+        ])
+    );
+}
+
+#[test]
+fn it_reports_unknown_colors() {
+    assert_eq!(
+        try_parse("color(notacolor)(x)"),
+        Err(vec![ParseError::new(
+            ParseErrorKind::UnknownColor("notacolor".to_string()),
+            Span::new(0, 16)
+        )])
+    );
+}
+
+#[test]
+fn it_reports_error_positions() {
+    let errors = try_parse("a + (b^").unwrap_err();
+    let spans = errors
+        .iter()
+        .map(|e| (e.span.start, e.span.end))
+        .collect::<Vec<(usize, usize)>>();
+    assert_eq!(spans, vec![(7, 7), (4, 5)]);
+}
@@ -0,0 +1,119 @@
+use crate::evaluation::error::EvaluationErrorKind;
+use crate::evaluation::evaluate;
+use crate::parse;
+use std::collections::HashMap;
+use std::f64::consts;
+
+fn eval(source: &str) -> f64 {
+    let variables = hashmap! {
+        "x".to_string() => 2.0,
+        "y".to_string() => -3.0,
+        "n".to_string() => 4.0,
+        "alpha".to_string() => 0.5,
+    };
+    evaluate(&parse(source), &variables).unwrap_or_else(|e| panic!("{}: {}", source, e))
+}
+
+fn eval_err(source: &str) -> EvaluationErrorKind {
+    evaluate(&parse(source), &HashMap::new()).unwrap_err().kind
+}
+
+fn assert_close(source: &str, expected: f64) {
+    let value = eval(source);
+    assert!(
+        (value - expected).abs() < 1e-9,
+        "{} evaluated to {} instead of {}",
+        source,
+        value,
+        expected
+    );
+}
+
+#[test]
+fn it_evaluates_arithmetic() {
+    assert_eq!(eval("1 + 2 * 3"), 7.0);
+    assert_eq!(eval("2 - 3 - 4"), -5.0);
+    assert_eq!(eval("2 xx 3 -: 4"), 1.5);
+    assert_eq!(eval("2x + 1"), 5.0);
+    assert_eq!(eval("-x + -(y)"), 1.0);
+    assert_eq!(eval("x y alpha"), -3.0);
+    assert_eq!(eval("1.5e3"), 1500.0);
+    assert_eq!(eval("(1 + 2)[3 - 1]{2}"), 12.0);
+}
+
+#[test]
+fn it_evaluates_specials() {
+    assert_eq!(eval("(x + 1)/(x - 1)"), 3.0);
+    assert_eq!(eval("frac{1}{4} + 1/2"), 0.75);
+    assert_eq!(eval("x^3 + 2^(-1)"), 8.5);
+    assert_eq!(eval("x^y"), 0.125);
+    assert_eq!(eval("sqrt 16 + sqrt(x + 7)"), 7.0);
+    assert_eq!(eval("root 3 (-8)"), -2.0);
+    assert_eq!(eval("abs(y) + floor(2.7) + ceil(-2.7)"), 3.0);
+    assert_eq!(eval("color(red)(x) + 1"), 3.0);
+}
+
+#[test]
+fn it_evaluates_functions() {
+    assert_close("sin(pi/2) + cos 0", 2.0);
+    assert_close("tan(pi/4) + cot(pi/4) + sec 0 + csc(pi/2)", 4.0);
+    assert_close("arcsin 1 + arccos 1 + arctan 0", consts::FRAC_PI_2);
+    assert_close("cosh 0 + sinh 0 + tanh 0 + sech 0", 2.0);
+    assert_close("ln e + log 100 + log_2 8 + exp 0", 7.0);
+    assert_close("sin^2 x + cos^2 x", 1.0);
+    assert_close("sin^2 -x + cos^2 -x", 1.0);
+    assert_close("log_3^2 9", 4.0);
+    assert_close("2 sin(alpha) x", 4.0 * 0.5f64.sin());
+}
+
+#[test]
+fn it_evaluates_sums_and_products() {
+    assert_eq!(eval("sum_(i=1)^n i^2"), 30.0);
+    assert_eq!(eval("sum_(i=1)^n i + 1"), 11.0);
+    assert_eq!(eval("2 sum_(k=0)^3 k x"), 24.0);
+    assert_eq!(eval("prod_(i=1)^5 i"), 120.0);
+    assert_eq!(eval("sum_(i=1)^n prod_(j=1)^i 2"), 30.0);
+    assert_eq!(eval("sum_(i=1)^n sum_(j=1)^i j"), 20.0);
+    assert_eq!(eval("sum_(i=3)^2 i + prod_(i=3)^2 i"), 1.0);
+    assert_eq!(eval("sum_(x=1)^2 x + x"), 5.0);
+}
+
+#[test]
+fn it_reports_errors() {
+    assert_eq!(
+        eval_err("x + 1"),
+        EvaluationErrorKind::UnknownVariable("x".to_string())
+    );
+    assert_eq!(
+        eval_err("1 = 1"),
+        EvaluationErrorKind::Unsupported("=".to_string())
+    );
+    assert_eq!(
+        eval_err("det 2"),
+        EvaluationErrorKind::Unsupported("det".to_string())
+    );
+    assert_eq!(eval_err("2 +"), EvaluationErrorKind::MissingOperand);
+    assert_eq!(eval_err("sin"), EvaluationErrorKind::MissingOperand);
+    assert_eq!(eval_err("sum_(i=1)^3"), EvaluationErrorKind::MissingOperand);
+    assert_eq!(eval_err("sum_i^3 i"), EvaluationErrorKind::InvalidBounds);
+    assert_eq!(
+        eval_err("sum_(i=1)^1.5 i"),
+        EvaluationErrorKind::InvalidBounds
+    );
+    assert_eq!(
+        eval_err("sum_(i=10^17)^(10^17+10) 1"),
+        EvaluationErrorKind::InvalidBounds
+    );
+    assert_eq!(
+        eval_err("sum_(i=1)^(10^12) i"),
+        EvaluationErrorKind::TooManyIterations
+    );
+    // nested series share the limit
+    assert_eq!(
+        eval_err("sum_(i=1)^(10^4) sum_(j=1)^(10^4) 1"),
+        EvaluationErrorKind::TooManyIterations
+    );
+
+    let error = evaluate(&parse("1 + z"), &HashMap::new()).unwrap_err();
+    assert_eq!((error.span.start, error.span.end), (4, 5));
+}
@@ -0,0 +1,99 @@
+use crate::format::latex::ToLatex;
+use crate::parse;
+use crate::parsing::latex::parse_latex;
+
+#[test]
+fn it_renders_fractions() {
+    let expr = parse("frac(a)(b) + 1/2");
+    assert_eq!(expr.to_latex(), "\\frac{a}{b} + \\frac{1}{2}")
+}
+
+#[test]
+fn it_renders_roots() {
+    let expr = parse("root 3 16 + sqrt x");
+    assert_eq!(expr.to_latex(), "\\sqrt[3]{16} + \\sqrt{x}")
+}
+
+#[test]
+fn it_renders_matrices() {
+    let expr = parse("[[1, 2], [3, 4]]");
+    assert_eq!(
+        expr.to_latex(),
+        "\\begin{bmatrix}1 & 2 \\\\ 3 & 4\\end{bmatrix}"
+    );
+    let expr = parse("((a), (b))");
+    assert_eq!(expr.to_latex(), "\\begin{pmatrix}a \\\\ b\\end{pmatrix}");
+    let expr = parse("{(1, x > 0), (0, x <= 0):}");
+    assert_eq!(
+        expr.to_latex(),
+        "\\begin{cases}1 & x > 0 \\\\ 0 & x \\le 0\\end{cases}"
+    )
+}
+
+#[test]
+fn it_renders_greek_letters() {
+    let expr = parse("alpha + Gamma + varphi");
+    assert_eq!(expr.to_latex(), "\\alpha + \\Gamma + \\varphi")
+}
+
+#[test]
+fn it_renders_accents() {
+    let expr = parse("cancel(x) hat y");
+    assert_eq!(expr.to_latex(), "\\cancel{x} \\hat{y}")
+}
+
+#[test]
+fn it_renders_colors() {
+    let expr = parse("color(red)(x) color(#f0a)(y)");
+    assert_eq!(
+        expr.to_latex(),
+        "\\textcolor{red}{x} \\textcolor[HTML]{FF00AA}{y}"
+    )
+}
+
+#[test]
+fn it_renders_font_commands() {
+    let expr = parse("bbb \"R\" fr \"g\"");
+    assert_eq!(expr.to_latex(), "\\mathbb{R} \\mathfrak{g}")
+}
+
+#[test]
+fn it_renders_sums_and_powers() {
+    let expr = parse("sum_(i=1)^n i^2");
+    assert_eq!(expr.to_latex(), "\\sum_{i = 1}^{n} {i}^{2}")
+}
+
+#[test]
+fn it_renders_limits() {
+    let expr = parse("lim_(n->oo) a_n + sup_(x in S) x + Lim_n b");
+    assert_eq!(
+        expr.to_latex(),
+        "\\lim_{n \\to \\infty} {a}_{n} + \\sup_{x \\in S} x + \\operatorname*{Lim}_{n} b"
+    )
+}
+
+#[test]
+fn it_escapes_text() {
+    let expr = parse("\"50% & more\"");
+    assert_eq!(expr.to_latex(), "\\text{50\\% \\& more}");
+    assert_eq!(
+        parse("text(a\\b ^ c ~ {d})").to_latex(),
+        "\\text{a\\textbackslash{}b \\^{} c \\~{} \\{d\\}}"
+    );
+}
+
+#[test]
+fn it_renders_repeated_operators() {
+    assert_eq!(parse("a/b/c").to_latex(), "\\frac{a}{b} / c");
+    assert_eq!(
+        parse_latex("\\left. x \\right|_0^1").to_latex(),
+        "\\left.x |\\right._{0}^{1}"
+    );
+}
+
+#[test]
+fn it_keeps_the_parentheses_of_bases() {
+    assert_eq!(parse("(a+b)^2").to_latex(), "\\left(a + b\\right)^{2}");
+    assert_eq!(parse("(a+b)_i").to_latex(), "\\left(a + b\\right)_{i}");
+    assert_eq!(parse("(a)^(2)").to_latex(), "\\left(a\\right)^{2}");
+}
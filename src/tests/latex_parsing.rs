@@ -0,0 +1,207 @@
+use crate::elements::group::Group;
+use crate::elements::literal::{Literal, PlainText};
+use crate::elements::special::{Limit, LimitOperator, Special};
+use crate::elements::Element;
+use crate::format::latex::ToLatex;
+use crate::format::mathml::ToMathML;
+use crate::parse;
+use crate::parsing::error::ParseErrorKind;
+use crate::parsing::latex::{parse_latex, try_parse_latex, LatexTokenizer};
+use crate::parsing::tokenizer::Tokenizer;
+use crate::span::Span;
+use crate::tokens::{FontCommand, Grouping, Text, Token};
+
+#[test]
+fn it_parses_latex_like_the_equivalent_asciimath() {
+    for (latex, asciimath) in &[
+        ("\\frac{a+b}{c}", "frac(a+b)(c)"),
+        ("\\dfrac12", "frac 1 2"),
+        ("\\sqrt{x} + \\sqrt[3]{x+1}", "sqrt(x) + root(3)(x+1)"),
+        ("x^{2} + y_i + e^{i\\pi}", "x^(2) + y_i + e^(i pi)"),
+        ("x^23", "x^2 3"),
+        ("{x}^{n}", "x^(n)"),
+        ("\\sum_{i=1}^{n} i", "sum_(i=1)^(n) i"),
+        ("\\int_0^1 x \\, dx", "int_0^1 x dx"),
+        ("\\lim_{x \\to 0} \\sin x", "lim_(x->0) sin x"),
+        (
+            "\\alpha \\leq \\beta \\neq \\Gamma",
+            "alpha <= beta != Gamma",
+        ),
+        ("\\hat{x} + \\bar y + \\vec{v}", "hat(x) + bar y + vec(v)"),
+        ("\\color{red}{x}", "color(red)(x)"),
+        ("\\overset{a}{b}", "overset(a)(b)"),
+        ("a \\cdot b \\times c * d / e", "a * b xx c ** d // e"),
+        ("\\forall x \\in \\mathbb{R}", "AA x in RR"),
+        ("\\operatorname{lcm}(a, b)", "lcm(a, b)"),
+        ("\\operatorname{sin} x", "sin x"),
+        ("\\sup_{x \\in S} x", "sup_(x in S) x"),
+    ] {
+        assert_eq!(
+            parse_latex(latex).without_spans(),
+            parse(asciimath).without_spans(),
+            "{}",
+            latex
+        );
+    }
+}
+
+#[test]
+fn it_parses_limit_operators() {
+    assert!(matches!(
+        &parse_latex("\\sup S").children[..],
+        [
+            Element::Special(Special::Limit(Limit {
+                operator: LimitOperator::Sup,
+                bottom: None,
+                ..
+            })),
+            _
+        ]
+    ));
+    assert_eq!(
+        parse_latex("\\operatorname{sup} S").without_spans(),
+        parse_latex("\\sup S").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\operatorname{cup}").children[0],
+        Element::Literal(Literal::Text(PlainText {
+            text: "cup".to_string(),
+            formatting: None,
+            span: Span::new(0, 18),
+        }))
+    );
+}
+
+#[test]
+fn it_parses_fences() {
+    assert_eq!(
+        parse_latex("\\left(a, b\\right]").without_spans(),
+        parse("(a, b]").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left\\{x\\right\\}").without_spans(),
+        parse("{x}").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left\\langle a \\right\\rangle").without_spans(),
+        parse("<<a>>").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left|x\\right|").without_spans(),
+        parse("abs(x)").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left\\|x\\right\\|").without_spans(),
+        parse("norm(x)").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left\\lfloor x \\right\\rfloor").without_spans(),
+        parse("floor(x)").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\left. x \\right|").without_spans(),
+        parse("{:x|:}").without_spans()
+    );
+}
+
+#[test]
+fn it_parses_environments() {
+    assert_eq!(
+        parse_latex("\\begin{pmatrix} 1 & 2 \\\\ 3 & 4 \\end{pmatrix}").without_spans(),
+        parse("((1, 2), (3, 4))").without_spans()
+    );
+    assert_eq!(
+        parse_latex("\\begin{bmatrix} a & b \\\\ c & d \\\\ \\end{bmatrix}").without_spans(),
+        parse("[[a, b], [c, d]]").without_spans()
+    );
+    let cases = parse_latex("\\begin{cases} x & x \\geq 0 \\\\ -x & x < 0 \\end{cases}");
+    assert_eq!(
+        cases.without_spans(),
+        parse("{(x, x >= 0), (-x, x < 0):}").without_spans()
+    );
+    assert!(matches!(cases.children[0], Element::Group(Group::Cases(_))));
+}
+
+#[test]
+fn it_parses_fonts_and_text() {
+    assert_eq!(
+        parse_latex("\\mathbb{N} \\mathbb{x}").children[1],
+        Element::Literal(Literal::Text(PlainText {
+            text: "x".to_string(),
+            formatting: Some(FontCommand::BigOutline),
+            span: Span::new(11, 21),
+        }))
+    );
+    assert_eq!(
+        parse_latex("\\mathbb{N}").without_spans(),
+        parse("NN").without_spans()
+    );
+    assert_eq!(
+        parse_latex("x \\text{ if } y").to_mathml(),
+        "<mrow><mi>x</mi><mtext>&#160;if&#160;</mtext><mi>y</mi></mrow>"
+    );
+}
+
+#[test]
+fn it_tokenizes_latex_with_spans() {
+    let tokens = LatexTokenizer::new("\\frac{a}{b}").parse_spanned();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|t| t.token.clone())
+            .collect::<Vec<Token>>(),
+        Tokenizer::new("frac(a)(b)").parse()
+    );
+    assert_eq!((tokens[0].span.start, tokens[0].span.end), (0, 5));
+    assert_eq!((tokens[2].span.start, tokens[2].span.end), (6, 7));
+    assert_eq!(
+        LatexTokenizer::new("{a+b}").parse()[0],
+        Token::Grouping(Grouping::RBraceHidden)
+    );
+    assert_eq!(
+        LatexTokenizer::new("a \\\\ b").parse()[1],
+        Token::Text(Text::NewLine)
+    );
+}
+
+#[test]
+fn it_reads_the_latex_output_back() {
+    for input in &[
+        "alpha beta Gamma varphi",
+        "a -> b => c <=> d |-> e",
+        "frac(a+b)(c) + sqrt(a+b) + root(3)(x)",
+        "(a, b] + {:x:} + <<a, b>>",
+        "abs(x) + norm(y) + floor(z) + ceil(w)",
+        "[[1, 2], [3, 4]] + ((1, 2), (3, 4))",
+        "{(x, if x >= 0), (-x, if x < 0):}",
+        "sum_(i=1)^n i + int_0^1 f(x) dx + lim_(x->0) x",
+        "sin x + cos^2 y + log_2 z",
+        "hat x + bar y + ul z + overbrace(a+b)",
+        "overset(a)(b) + color(red)(x+y)",
+        "oo del grad O/ RR ZZ",
+    ] {
+        let latex = parse(input).to_latex();
+        assert_eq!(parse_latex(&latex).to_latex(), latex, "{}", input);
+    }
+}
+
+#[test]
+fn it_reports_unknown_commands_and_environments() {
+    let errors = try_parse_latex("\\foo x").unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::UnknownCommand("foo".to_string())
+    );
+    assert_eq!((errors[0].span.start, errors[0].span.end), (0, 4));
+
+    let errors = try_parse_latex("\\begin{align} a & b \\end{align}").unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::UnknownEnvironment("align".to_string())
+    );
+    assert!(matches!(
+        try_parse_latex("\\frac{a}{b").unwrap_err()[0].kind,
+        ParseErrorKind::UnbalancedGrouping(_)
+    ));
+    assert!(try_parse_latex("\\frac{a}{b} + \\sqrt[n]{x}").is_ok());
+}
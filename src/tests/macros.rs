@@ -0,0 +1,125 @@
+use crate::elements::group::Group;
+use crate::elements::Element;
+use crate::parsing::error::ParseErrorKind;
+use crate::parsing::symbols::SymbolTable;
+use crate::span::Span;
+use crate::tokens::Macro;
+use crate::{parse, parse_with_symbols, try_parse_with_symbols};
+
+fn symbols() -> SymbolTable {
+    SymbolTable::default()
+        .with_macro(Macro::new("Ex", &["X"], "bb\"E\"[X]"))
+        .with_macro(Macro::new("perp", &["v"], "vec(v)_(_|_)"))
+        .with_macro(Macro::new("pair", &["first", "second"], "(first, second)"))
+        .with_macro(Macro::new("half", &[], "{:1/2:}"))
+        .with_macro(Macro::new("Var", &["X"], "Ex(X^2) - Ex(X)^2"))
+        .with_macro(Macro::new("loop", &[], "x + loop"))
+}
+
+fn assert_expands(source: &str, expansion: &str) {
+    assert_eq!(
+        try_parse_with_symbols(source, &symbols())
+            .unwrap_or_else(|e| panic!("{:?}", e))
+            .without_spans(),
+        parse(expansion).without_spans(),
+        "{}",
+        source
+    );
+}
+
+#[test]
+fn it_expands_macros() {
+    assert_expands("Ex(X)", "bb\"E\"[X]");
+    assert_expands("perp(u) + perp(w)", "vec(u)_(_|_) + vec(w)_(_|_)");
+    assert_expands("pair(x, (y, z))", "(x, (y, z))");
+    assert_expands("pair( a , b )", "(a, b)");
+    assert_expands("half^2", "{:1/2:}^2");
+    assert_expands("Ex(Y + 1)", "bb\"E\"[{:Y + 1:}]");
+}
+
+#[test]
+fn it_expands_nested_macros() {
+    assert_expands("Var(Y)", "bb\"E\"[{:Y^2:}] - bb\"E\"[Y]^2");
+    assert_expands("Ex(pair(1, 2))", "bb\"E\"[{:(1, 2):}]");
+}
+
+#[test]
+fn it_keeps_the_spans_of_arguments() {
+    let expression = parse_with_symbols("a + Ex(Y)", &symbols());
+    // the tokens of the body are spanned with the invocation
+    assert_eq!(expression.children[2].span(), Span::new(4, 9));
+    match &expression.children[3] {
+        Element::Group(Group::Brackets(b)) => {
+            assert_eq!(b.inner.children[0].span(), Span::new(7, 8))
+        }
+        e => panic!("{:?}", e),
+    }
+}
+
+#[test]
+fn it_reports_wrong_arity() {
+    let errors = try_parse_with_symbols("pair(a) + Ex", &symbols()).unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::MacroArity {
+            name: "pair".to_string(),
+            expected: 2,
+            found: 1
+        }
+    );
+    assert_eq!(errors[0].span, Span::new(0, 7));
+    assert_eq!(
+        errors[1].kind,
+        ParseErrorKind::MacroArity {
+            name: "Ex".to_string(),
+            expected: 1,
+            found: 0
+        }
+    );
+    let errors = try_parse_with_symbols("Ex(a", &symbols()).unwrap_err();
+    assert!(matches!(
+        errors[0].kind,
+        ParseErrorKind::UnbalancedGrouping(_)
+    ));
+}
+
+#[test]
+fn it_limits_recursion() {
+    let errors = try_parse_with_symbols("loop", &symbols()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::MacroRecursion("loop".to_string())
+    );
+
+    // Var expands Ex which is nested two levels deep
+    let symbols = symbols().with_macro_limit(1);
+    let errors = try_parse_with_symbols("Var(Y)", &symbols).unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::MacroRecursion("Ex".to_string())
+    );
+    assert!(try_parse_with_symbols("Ex(Y)", &symbols).is_ok());
+}
+
+#[test]
+fn it_limits_the_size_of_expansions() {
+    // every level doubles the number of invocations
+    let symbols = SymbolTable::default().with_macro(Macro::new("f", &[], "f f"));
+    let errors = try_parse_with_symbols("f", &symbols).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].kind,
+        ParseErrorKind::MacroRecursion("f".to_string())
+    );
+
+    // six levels of ten invocations each stay below the nesting limit
+    let symbols = (0..6).fold(SymbolTable::default(), |symbols, level| {
+        let body = vec![format!("m{}", level + 1); 10].join(" ");
+        symbols.with_macro(Macro::new(&format!("m{}", level), &[], &body))
+    });
+    let errors = try_parse_with_symbols("m0", &symbols).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0].kind, ParseErrorKind::MacroRecursion(_)));
+    assert!(try_parse_with_symbols("m3", &symbols).is_ok());
+}
@@ -1,11 +1,204 @@
-use crate::format::mathml::ToMathML;
+use crate::format::mathml::{DisplayMode, MathMLOptions, ToMathML};
 use crate::parse;
+use crate::parsing::mathml::parse_mathml;
+use std::fmt;
 
 #[test]
 fn it_renders_roots() {
-    let expr = parse("root 3 16".to_string());
+    let expr = parse("root 3 16");
     assert_eq!(
         expr.to_mathml(),
         "<mrow><mroot><mn>16</mn><mn>3</mn></mroot></mrow>"
     )
 }
+
+#[test]
+fn it_renders_fenced_groups() {
+    assert_eq!(
+        parse("(a, b]").to_mathml(),
+        "<mrow><mrow><mo fence='true'>(</mo><mrow><mi>a</mi><mo>,</mo><mi>b</mi></mrow>\
+         <mo fence='true'>]</mo></mrow></mrow>"
+    );
+    assert_eq!(
+        parse("{:x:}").to_mathml(),
+        "<mrow><mrow><mo fence='true'></mo><mrow><mi>x</mi></mrow><mo fence='true'></mo>\
+         </mrow></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_cases() {
+    assert_eq!(
+        parse("{(x, if x >= 0), (-x, if x < 0):}").to_mathml(),
+        "<mrow><mrow><mo>&lbrace;</mo><mtable columnalign='left'>\
+         <mtr><mtd><mrow><mi>x</mi></mrow></mtd>\
+         <mtd><mrow><mo>if</mo><mi>x</mi><mo>&ge;</mo><mn>0</mn></mrow></mtd></mtr>\
+         <mtr><mtd><mrow><mo>&minus;</mo><mi>x</mi></mrow></mtd>\
+         <mtd><mrow><mo>if</mo><mi>x</mi><mo>&lt;</mo><mn>0</mn></mrow></mtd></mtr>\
+         </mtable></mrow></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_function_applications() {
+    assert_eq!(
+        parse("sin^2 x").to_mathml(),
+        "<mrow><mrow><msup><mi>sin</mi><mn>2</mn></msup><mo>&#x2061;</mo><mi>x</mi></mrow></mrow>"
+    );
+    assert_eq!(
+        parse("log_b(x)").to_mathml(),
+        "<mrow><mrow><msub><mi>log</mi><mi>b</mi></msub><mo>&#x2061;</mo>\
+         <mrow><mo>(</mo><mrow><mi>x</mi></mrow><mo>)</mo></mrow></mrow></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_limits() {
+    assert_eq!(
+        parse("lim_(x->oo) f(x)").to_mathml(),
+        "<mrow><munder><mo>lim</mo><mrow><mrow><mi>x</mi><mo>&#8594;</mo><mi>&infin;</mi>\
+         </mrow></mrow></munder><mrow><mi>f</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mrow><mi>x</mi>\
+         </mrow><mo>)</mo></mrow></mrow></mrow>"
+    );
+    assert_eq!(
+        parse("max_i a").to_mathml(),
+        "<mrow><munder><mo>max</mo><mi>i</mi></munder><mi>a</mi></mrow>"
+    );
+    assert_eq!(
+        parse("sum^n").to_mathml(),
+        "<mrow><mover><mi>&sum;</mi><mi>n</mi></mover></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_text_blocks() {
+    assert_eq!(
+        parse("text(if  x) + mbox( y )").to_mathml(),
+        "<mrow><mtext>if  x</mtext><mo>&plus;</mo><mtext>&#160;y&#160;</mtext></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_over_and_under_scripts() {
+    assert_eq!(
+        parse("overset(a)(b)").to_mathml(),
+        "<mrow><mover><mrow><mrow><mrow><mi>b</mi></mrow></mrow></mrow>\
+         <mrow><mrow><mrow><mi>a</mi></mrow></mrow></mrow></mover></mrow>"
+    );
+    assert_eq!(
+        parse("underset(c)(d)").to_mathml(),
+        "<mrow><munder><mrow><mrow><mrow><mi>d</mi></mrow></mrow></mrow>\
+         <mrow><mrow><mrow><mi>c</mi></mrow></mrow></mrow></munder></mrow>"
+    );
+    assert_eq!(
+        parse("ul x").to_mathml(),
+        "<mrow><munder accentunder='true'><mrow><mi>x</mi></mrow><mo>&ndash;</mo></munder></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_logical_symbols() {
+    assert_eq!(
+        parse("AA x EE y").to_mathml(),
+        "<mrow><mo>&forall;</mo><mi>x</mi><mo>&exist;</mo><mi>y</mi></mrow>"
+    );
+}
+
+#[test]
+fn it_renders_unicode_input() {
+    assert_eq!(
+        parse("α ≤ β").to_mathml(),
+        parse("alpha <= beta").to_mathml()
+    );
+    assert_eq!(
+        parse("∑_(i=1)^n √i → ∞").to_mathml(),
+        parse("sum_(i=1)^n sqrt i -> oo").to_mathml()
+    );
+}
+
+#[test]
+fn it_renders_math_documents() {
+    let expression = parse("x^2");
+    assert_eq!(
+        MathMLOptions::default().render(&expression),
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"inline\">\
+         <mrow><msup><mi>x</mi><mn>2</mn></msup></mrow></math>"
+    );
+    assert_eq!(
+        MathMLOptions::default()
+            .with_display(DisplayMode::Block)
+            .with_alttext("x squared")
+            .render(&expression),
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" \
+         alttext=\"x squared\"><mrow><msup><mi>x</mi><mn>2</mn></msup></mrow></math>"
+    );
+}
+
+#[test]
+fn it_annotates_math_documents() {
+    let source = "a < \"b & c\"";
+    let expression = parse(source);
+    let document = MathMLOptions::default()
+        .with_source(source)
+        .render(&expression);
+
+    assert_eq!(
+        document,
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"inline\" \
+         alttext=\"a &lt; &quot;b &amp; c&quot;\"><semantics>\
+         <mrow><mi>a</mi><mo>&lt;</mo><mtext>b &amp; c</mtext></mrow>\
+         <annotation encoding=\"text/x-asciimath\">a &lt; &quot;b &amp; c&quot;</annotation>\
+         </semantics></math>"
+    );
+    assert_eq!(
+        parse_mathml(&document).unwrap().without_spans(),
+        expression.without_spans()
+    );
+
+    let annotation = roxmltree::Document::parse(&document)
+        .unwrap()
+        .descendants()
+        .find(|n| n.has_tag_name("annotation"))
+        .and_then(|n| n.text())
+        .map(str::to_string);
+    assert_eq!(annotation.as_deref(), Some(source));
+}
+
+#[test]
+fn it_writes_into_existing_output() {
+    let expression = parse("[[1, 2], [3, 4]] + sum_(i=1)^n text( x ) + sin^2_3 x");
+    let mut html = "<p>".to_string();
+    expression.write_mathml(&mut html).unwrap();
+    assert_eq!(
+        html,
+        "<p><mrow><mrow><mo>[</mo><mtable><mtr><mtd><mrow><mn>1</mn></mrow></mtd>\
+         <mtd><mrow><mn>2</mn></mrow></mtd></mtr><mtr><mtd><mrow><mn>3</mn></mrow></mtd>\
+         <mtd><mrow><mn>4</mn></mrow></mtd></mtr></mtable><mo>]</mo></mrow><mo>&plus;</mo>\
+         <munderover><mi>&sum;</mi><mrow><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow></mrow>\
+         <mi>n</mi></munderover><mtext>&#160;x&#160;</mtext><mo>&plus;</mo>\
+         <msub><msup><mi>sin</mi><mn>2</mn></msup><mn>3</mn></msub><mi>x</mi></mrow>"
+    );
+
+    let mut html = "<p>".to_string();
+    MathMLOptions::default()
+        .write(&parse("x^2 + text( y )"), &mut html)
+        .unwrap();
+    assert_eq!(
+        html,
+        "<p><math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"inline\">\
+         <mrow><msup><mi>x</mi><mn>2</mn></msup><mo>&plus;</mo><mtext>&#160;y&#160;</mtext></mrow>\
+         </math>"
+    );
+
+    struct Full;
+    impl fmt::Write for Full {
+        fn write_str(&mut self, _: &str) -> fmt::Result {
+            Err(fmt::Error)
+        }
+    }
+    assert_eq!(expression.write_mathml(&mut Full), Err(fmt::Error));
+    assert_eq!(
+        MathMLOptions::default().write(&expression, &mut Full),
+        Err(fmt::Error)
+    );
+}
@@ -0,0 +1,204 @@
+use crate::elements::group::Group;
+use crate::elements::literal::Literal;
+use crate::elements::special::Expression;
+use crate::elements::Element;
+use crate::format::mathml::ToMathML;
+use crate::parse;
+use crate::parsing::error::MathMLError;
+use crate::parsing::mathml::parse_mathml;
+use crate::tokens::{Greek, Relation};
+
+fn assert_round_trip(input: &str) {
+    let expression = parse(input);
+    let mathml = expression.to_mathml();
+    assert_eq!(
+        parse_mathml(&mathml).unwrap().without_spans(),
+        expression.without_spans(),
+        "{} -> {}",
+        input,
+        mathml
+    );
+}
+
+#[test]
+fn it_imports_the_output_of_to_mathml() {
+    for input in &[
+        "a + b - c",
+        "x^2 + y_i",
+        "alpha beta gamma Gamma phi",
+        "a <= b >= c != d",
+        "A sube B nn C uu D",
+        "a -> b => c <=> d |-> e",
+        "AA x EE y not z",
+        "a ** b *** c xx d -: e",
+        "oo del grad O/ RR ZZ NN QQ CC",
+        "1/2 + (a+b)/c",
+        "frac(x)(y)",
+        "sqrt(a+b) + root 3 x",
+        "(a, b) + [c] + {d} + (:e:) + {:f:}",
+        "|x| + ||y|| + |__z__| + |~w~|",
+        "(a, b] + {:x:}",
+        "[[1, 2], [3, 4]]",
+        "((1), (2))",
+        "{(x, if x >= 0), (-x, if x < 0):}",
+        "sum_(i=1)^n i + prod_i i",
+        "int_0^1 f(x) dx + oint_C z",
+        "lim_(x->0) x + max_x x",
+        "sin x + cos^2 y + log_2 z",
+        "hat x + bar y + vec z + dot a + ddot b",
+        "cancel(x) + cancel(a+b)^2",
+        "a_b_c + x^2^3 + x_1^2 + x^2_1",
+        "ul x + overbrace(a+b) + underbrace(c)",
+        "overset(a)(b) + underset(c)(d)",
+        "color(red)(x+y)",
+        "text(hello world) + bb(x) + mathbb(R)",
+        "\"some text\"",
+        "a \\\\ b",
+        "3.14 * 2",
+    ] {
+        assert_round_trip(input);
+    }
+}
+
+#[test]
+fn it_imports_tokens_with_the_same_output() {
+    for input in &["varphi", "rarr", "cdots vdots", "lt"] {
+        let mathml = parse(input).to_mathml();
+        assert_eq!(parse_mathml(&mathml).unwrap().to_mathml(), mathml);
+    }
+}
+
+#[test]
+fn it_imports_unicode_input() {
+    assert_round_trip("α ≤ β → γ");
+    assert_eq!(
+        parse_mathml("<mrow><mi>α</mi><mo>≤</mo><mi>β</mi></mrow>")
+            .unwrap()
+            .without_spans(),
+        parse("alpha <= beta").without_spans()
+    );
+}
+
+#[test]
+fn it_imports_math_documents() {
+    let expression = parse_mathml(
+        "<math xmlns='http://www.w3.org/1998/Math/MathML' display='block'>\
+         <semantics><mrow><mfrac><mn>1</mn><mi>x</mi></mfrac></mrow>\
+         <annotation encoding='text/x-asciimath'>1/x</annotation></semantics></math>",
+    )
+    .unwrap();
+    assert_eq!(expression.without_spans(), parse("1/x").without_spans());
+}
+
+#[test]
+fn it_imports_external_mathml() {
+    assert_eq!(
+        parse_mathml(
+            "<math><msubsup><mi>x</mi><mn>1</mn><mn>2</mn></msubsup>\
+             <mo>&InvisibleTimes;</mo><mi>y</mi></math>"
+        )
+        .unwrap()
+        .to_mathml(),
+        "<mrow><msup><msub><mi>x</mi><mn>1</mn></msub><mn>2</mn></msup><mi>y</mi></mrow>"
+    );
+    assert_eq!(
+        parse_mathml(
+            "<math><mi>sin</mi><mo>&ApplyFunction;</mo><mi>x</mi>\
+             <mo>&#x2062;</mo><mover><mi>v</mi><mo>^</mo></mover></math>"
+        )
+        .unwrap()
+        .without_spans(),
+        parse("sin x hat v").without_spans()
+    );
+    assert_eq!(
+        parse_mathml("<math><msqrt><mi>a</mi><mo>+</mo><mi>b</mi></msqrt></math>")
+            .unwrap()
+            .to_mathml(),
+        "<mrow><msqrt><mrow><mrow><mi>a</mi><mo>&plus;</mo><mi>b</mi></mrow></mrow></msqrt></mrow>"
+    );
+    assert_eq!(
+        parse_mathml(
+            "<math><menclose notation='updiagonalstrike'><mi>x</mi></menclose>\
+             <menclose notation='box'><mi>y</mi></menclose></math>"
+        )
+        .unwrap()
+        .without_spans(),
+        parse("cancel x y").without_spans()
+    );
+}
+
+#[test]
+fn it_maps_entities_back_to_tokens() {
+    let expression =
+        parse_mathml("<math><mi>&phi;</mi><mo>&rarr;</mo><mo>&le;</mo></math>").unwrap();
+    assert_eq!(
+        expression.children[0],
+        Element::Literal(Literal::Greek(Greek::Phi, Default::default()))
+    );
+    assert_eq!(
+        expression.without_spans(),
+        parse("phi -> <=").without_spans()
+    );
+    assert_eq!(
+        parse_mathml("<mo>&sube;</mo>").unwrap().children,
+        vec![Element::Literal(Literal::Relation(
+            Relation::SubSetEq,
+            Default::default()
+        ))]
+    );
+}
+
+#[test]
+fn it_imports_non_enclosed_rows() {
+    let expression =
+        parse_mathml("<math><mrow><mi>a</mi><mi>b</mi></mrow><mi>c</mi></math>").unwrap();
+    assert!(matches!(
+        &expression.children[0],
+        Element::Group(Group::NonEnclosed(_))
+    ));
+    assert_eq!(
+        parse_mathml("<math></math>").unwrap(),
+        Expression::default()
+    );
+}
+
+#[test]
+fn it_imports_rows_with_identical_delimiters() {
+    let expression = parse_mathml(
+        "<math><mrow><mo>|</mo><mi>a</mi><mo>|</mo><mo>+</mo>\
+         <mo>|</mo><mi>b</mi><mo>|</mo></mrow></math>",
+    )
+    .unwrap();
+    assert_eq!(expression.children.len(), 7);
+    assert!(expression.structurally_eq(&parse("|a| + |b|")));
+    assert!(matches!(
+        &parse_mathml("<math><mrow><mo>|</mo><mi>a</mi><mo>|</mo></mrow></math>")
+            .unwrap()
+            .children[..],
+        [Element::Group(Group::Abs(_))]
+    ));
+}
+
+#[test]
+fn it_reports_invalid_mathml() {
+    assert!(matches!(
+        parse_mathml("<math><mi>x</math>"),
+        Err(MathMLError::Xml(_))
+    ));
+    assert_eq!(
+        parse_mathml("<math><mi>&nosuchentity;</mi></math>"),
+        Err(MathMLError::UnknownEntity("nosuchentity".to_string()))
+    );
+    assert_eq!(
+        parse_mathml("<math><mmultiscripts><mi>x</mi></mmultiscripts></math>"),
+        Err(MathMLError::UnsupportedElement("mmultiscripts".to_string()))
+    );
+    assert_eq!(
+        parse_mathml("<math><mfrac><mn>1</mn></mfrac></math>"),
+        Err(MathMLError::ChildCount {
+            element: "mfrac".to_string(),
+            expected: 2,
+            found: 1
+        })
+    );
+}
@@ -1,3 +1,19 @@
+mod asciimath;
+mod content_mathml;
+mod errors;
+mod evaluation;
+mod latex;
+mod latex_parsing;
+mod macros;
 mod mathml;
+mod mathml_parsing;
 mod parsing;
+mod pretty;
+mod semantic;
+#[cfg(feature = "serde")]
+mod serialization;
+mod spans;
+mod symbols;
 mod tokenization;
+mod unicode;
+mod visit;
@@ -1,17 +1,20 @@
-use crate::elements::group::{Brackets, Group, Matrix, Parentheses, Vector};
-use crate::elements::literal::{Literal, Number};
-use crate::elements::special::{Expression, Root, Special, Sum};
+use crate::elements::group::{Brackets, Cases, Fenced, Group, Matrix, Parentheses, Vector};
+use crate::elements::literal::{Literal, Number, Symbol};
+use crate::elements::special::{
+    Expression, FunctionApplication, Limit, LimitOperator, Pow, Root, Special, Sum,
+};
 use crate::elements::Element;
 use crate::parse;
 use crate::parsing::tokenizer::Tokenizer;
 use crate::parsing::tree_parser::TreeParser;
-use crate::tokens::{Function, Operation};
+use crate::span::Span;
+use crate::tokens::{Function, Grouping, Operation, Relation};
 use crate::utils::Boxed;
 
 #[test]
 fn it_parses_into_a_tree1() {
     let expression = "sum_2^3";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     let mut tree_parser = TreeParser::new(tokens.clone());
     let expression = tree_parser.parse();
@@ -20,15 +23,18 @@ fn it_parses_into_a_tree1() {
         bottom: Some(
             Element::Literal(Literal::Number(Number {
                 number: "2".to_string(),
+                span: Span::default(),
             }))
             .boxed(),
         ),
         top: Some(
             Element::Literal(Literal::Number(Number {
                 number: "3".to_string(),
+                span: Span::default(),
             }))
             .boxed(),
         ),
+        span: Span::default(),
     })));
     assert_eq!(expression, test_expression)
 }
@@ -36,36 +42,46 @@ fn it_parses_into_a_tree1() {
 #[test]
 fn it_parses_matrices() {
     assert_eq!(
-        parse("[[1, 2],[3,4]]".to_string()),
+        parse("[[1, 2],[3,4]]").without_spans(),
         Expression {
             children: vec![Element::Group(Group::Matrix(Matrix {
                 inner: vec![
                     vec![
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "1".to_string()
-                            })),]
+                                number: "1".to_string(),
+                                span: Span::default()
+                            })),],
+                            span: Span::default()
                         },
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "2".to_string()
-                            })),]
+                                number: "2".to_string(),
+                                span: Span::default()
+                            })),],
+                            span: Span::default()
                         }
                     ],
                     vec![
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "3".to_string()
-                            })),]
+                                number: "3".to_string(),
+                                span: Span::default()
+                            })),],
+                            span: Span::default()
                         },
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "4".to_string()
-                            })),]
+                                number: "4".to_string(),
+                                span: Span::default()
+                            })),],
+                            span: Span::default()
                         }
                     ]
-                ]
-            }))]
+                ],
+                span: Span::default()
+            }))],
+            span: Span::default()
         }
     );
 }
@@ -73,7 +89,7 @@ fn it_parses_matrices() {
 #[test]
 fn it_rejects_invalid_matrices() {
     assert_eq!(
-        parse("[[1, 3, 4],[3,4]]".to_string()),
+        parse("[[1, 3, 4],[3,4]]").without_spans(),
         Expression {
             children: vec![Element::Group(Group::Brackets(Brackets {
                 inner: Expression {
@@ -82,57 +98,75 @@ fn it_rejects_invalid_matrices() {
                             inner: Expression {
                                 children: vec![
                                     Element::Literal(Literal::Number(Number {
-                                        number: "1".to_string()
+                                        number: "1".to_string(),
+                                        span: Span::default()
                                     })),
-                                    Element::Group(Group::MSep),
+                                    Element::Group(Group::MSep(Span::default())),
                                     Element::Literal(Literal::Number(Number {
-                                        number: "3".to_string()
+                                        number: "3".to_string(),
+                                        span: Span::default()
                                     })),
-                                    Element::Group(Group::MSep),
+                                    Element::Group(Group::MSep(Span::default())),
                                     Element::Literal(Literal::Number(Number {
-                                        number: "4".to_string()
+                                        number: "4".to_string(),
+                                        span: Span::default()
                                     }))
-                                ]
+                                ],
+                                span: Span::default()
                             }
-                            .boxed()
+                            .boxed(),
+                            span: Span::default()
                         })),
-                        Element::Group(Group::MSep),
+                        Element::Group(Group::MSep(Span::default())),
                         Element::Group(Group::Brackets(Brackets {
                             inner: Expression {
                                 children: vec![
                                     Element::Literal(Literal::Number(Number {
-                                        number: "3".to_string()
+                                        number: "3".to_string(),
+                                        span: Span::default()
                                     })),
-                                    Element::Group(Group::MSep),
+                                    Element::Group(Group::MSep(Span::default())),
                                     Element::Literal(Literal::Number(Number {
-                                        number: "4".to_string()
+                                        number: "4".to_string(),
+                                        span: Span::default()
                                     }))
-                                ]
+                                ],
+                                span: Span::default()
                             }
-                            .boxed()
+                            .boxed(),
+                            span: Span::default()
                         }))
-                    ]
+                    ],
+                    span: Span::default()
                 }
-                .boxed()
-            }))]
+                .boxed(),
+                span: Span::default()
+            }))],
+            span: Span::default()
         }
     );
     assert_eq!(
-        parse("[[1]]".to_string()),
+        parse("[[1]]").without_spans(),
         Expression {
             children: vec![Element::Group(Group::Brackets(Brackets {
                 inner: Expression {
                     children: vec![Element::Group(Group::Brackets(Brackets {
                         inner: Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "1".to_string()
-                            })),]
+                                number: "1".to_string(),
+                                span: Span::default()
+                            })),],
+                            span: Span::default()
                         }
-                        .boxed()
-                    })),]
+                        .boxed(),
+                        span: Span::default()
+                    })),],
+                    span: Span::default()
                 }
-                .boxed()
-            }))]
+                .boxed(),
+                span: Span::default()
+            }))],
+            span: Span::default()
         }
     );
 }
@@ -140,111 +174,330 @@ fn it_rejects_invalid_matrices() {
 #[test]
 fn it_parses_vectors() {
     assert_eq!(
-        parse("((1), (2))(1,2) - f".to_string()),
+        parse("((1), (2))(1,2) - f").without_spans(),
         Expression {
             children: vec![
                 Element::Group(Group::Vector(Vector {
                     inner: vec![
                         vec![Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "1".to_string()
-                            }))]
+                                number: "1".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         }],
                         vec![Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "2".to_string()
-                            }))]
+                                number: "2".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         }]
-                    ]
+                    ],
+                    span: Span::default()
                 })),
                 Element::Group(Group::Parentheses(Parentheses {
                     inner: Expression {
                         children: vec![
                             Element::Literal(Literal::Number(Number {
-                                number: "1".to_string()
+                                number: "1".to_string(),
+                                span: Span::default()
                             })),
-                            Element::Group(Group::MSep),
+                            Element::Group(Group::MSep(Span::default())),
                             Element::Literal(Literal::Number(Number {
-                                number: "2".to_string()
+                                number: "2".to_string(),
+                                span: Span::default()
                             }))
-                        ]
+                        ],
+                        span: Span::default()
                     }
-                    .boxed()
+                    .boxed(),
+                    span: Span::default()
                 })),
-                Element::Literal(Literal::Operation(Operation::Minus)),
-                Element::Literal(Literal::Function(Function::F))
-            ]
+                Element::Literal(Literal::Operation(Operation::Minus, Span::default())),
+                Element::Literal(Literal::Function(Function::F, Span::default()))
+            ],
+            span: Span::default()
         }
     );
     assert_eq!(
-        parse("((1, 3), (2, 5))".to_string()),
+        parse("((1, 3), (2, 5))").without_spans(),
         Expression {
             children: vec![Element::Group(Group::Vector(Vector {
                 inner: vec![
                     vec![
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "1".to_string()
-                            }))]
+                                number: "1".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         },
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "3".to_string()
-                            }))]
+                                number: "3".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         }
                     ],
                     vec![
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "2".to_string()
-                            }))]
+                                number: "2".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         },
                         Expression {
                             children: vec![Element::Literal(Literal::Number(Number {
-                                number: "5".to_string()
-                            }))]
+                                number: "5".to_string(),
+                                span: Span::default()
+                            }))],
+                            span: Span::default()
                         }
                     ]
-                ]
-            }))]
+                ],
+                span: Span::default()
+            }))],
+            span: Span::default()
         }
     )
 }
 
 #[test]
 fn it_parses_roots() {
-    let expr = parse("root 3 16".to_string());
+    let expr = parse("root 3 16");
     assert_eq!(
-        expr,
+        expr.without_spans(),
         Expression {
             children: vec![Element::Special(Special::Root(Root {
                 base: Element::Literal(Literal::Number(Number {
-                    number: "3".to_string()
+                    number: "3".to_string(),
+                    span: Span::default()
                 }))
                 .boxed(),
                 inner: Element::Literal(Literal::Number(Number {
-                    number: "16".to_string()
+                    number: "16".to_string(),
+                    span: Span::default()
                 }))
                 .boxed(),
-            }))]
+                span: Span::default(),
+            }))],
+            span: Span::default()
         }
     );
     // test no fail
-    parse("root 3".to_string());
+    parse("root 3");
 }
 
 #[test]
 fn it_parses_functions() {
-    let expr = parse("sin 10".to_string());
+    let number = |n: &str| {
+        Element::Literal(Literal::Number(Number {
+            number: n.to_string(),
+            span: Span::default(),
+        }))
+    };
+    let application = |function, subscript: Option<Element>, exponent: Option<Element>| {
+        Element::Special(Special::FunctionApplication(FunctionApplication {
+            function,
+            exponent: exponent.map(Box::new),
+            subscript: subscript.map(Box::new),
+            argument: number("10").boxed(),
+            span: Span::default(),
+        }))
+    };
+    assert_eq!(
+        parse("sin 10").without_spans().children,
+        vec![application(Function::Sin, None, None)]
+    );
+    assert_eq!(
+        parse("sin^2 10").without_spans().children,
+        vec![application(Function::Sin, None, Some(number("2")))]
+    );
+    assert_eq!(
+        parse("log_2^3 10").without_spans().children,
+        vec![application(
+            Function::Log,
+            Some(number("2")),
+            Some(number("3"))
+        )]
+    );
+    assert!(matches!(
+        &parse("sin(2x)").children[..],
+        [Element::Special(Special::FunctionApplication(FunctionApplication { argument, .. }))]
+            if matches!(**argument, Element::Group(Group::Parentheses(_)))
+    ));
+    assert_eq!(
+        parse("sin + 10").without_spans().children[0],
+        Element::Literal(Literal::Function(Function::Sin, Span::default()))
+    );
+    assert!(matches!(
+        &parse("sin^2").children[..],
+        [Element::Special(Special::Pow(_))]
+    ));
+    // scripts after the argument belong to the argument
+    assert!(matches!(
+        &parse("sin x^2").children[..],
+        [Element::Special(Special::FunctionApplication(FunctionApplication { argument, exponent: None, .. }))]
+            if matches!(**argument, Element::Special(Special::Pow(_)))
+    ));
+    assert!(matches!(
+        &parse("log x_1").children[..],
+        [Element::Special(Special::FunctionApplication(FunctionApplication { argument, subscript: None, .. }))]
+            if matches!(**argument, Element::Special(Special::Sub(_)))
+    ));
+    assert!(matches!(
+        &parse("sin x_1^2").children[..],
+        [Element::Special(Special::FunctionApplication(FunctionApplication { argument, .. }))]
+            if matches!(&**argument, Element::Special(Special::Pow(Pow { base, .. }))
+                if matches!(**base, Element::Special(Special::Sub(_))))
+    ));
+    // unless the argument is in parentheses
+    assert!(matches!(
+        &parse("sin(x)^2").children[..],
+        [Element::Special(Special::Pow(Pow { base, .. }))]
+            if matches!(**base, Element::Special(Special::FunctionApplication(_)))
+    ));
+}
+
+#[test]
+fn it_parses_fenced_groups() {
+    let symbol = |s: &str| {
+        Element::Literal(Literal::Symbol(Symbol {
+            symbol: s.to_string(),
+            span: Span::default(),
+        }))
+    };
+    let fenced = |left, right, children| {
+        Element::Group(Group::Fenced(Fenced {
+            left,
+            right,
+            inner: Expression {
+                children,
+                span: Span::default(),
+            }
+            .boxed(),
+            span: Span::default(),
+        }))
+    };
+    let msep = Element::Group(Group::MSep(Span::default()));
+
+    assert_eq!(
+        parse("{: x, y :}").without_spans().children,
+        vec![fenced(
+            Grouping::RBraceHidden,
+            Grouping::LBraceHidden,
+            vec![symbol("x"), msep.clone(), symbol("y")]
+        )]
+    );
+    assert_eq!(
+        parse("(a, b] [c)").without_spans().children,
+        vec![
+            fenced(
+                Grouping::RParen,
+                Grouping::LBracket,
+                vec![symbol("a"), msep, symbol("b")]
+            ),
+            fenced(Grouping::RBracket, Grouping::LParen, vec![symbol("c")])
+        ]
+    );
     assert_eq!(
-        expr,
+        parse("{x :}").without_spans().children,
+        vec![fenced(
+            Grouping::RBrace,
+            Grouping::LBraceHidden,
+            vec![symbol("x")]
+        )]
+    );
+    assert_eq!(
+        parse("(x)").without_spans().children,
+        vec![Element::Group(Group::Parentheses(Parentheses {
+            inner: Expression {
+                children: vec![symbol("x")],
+                span: Span::default()
+            }
+            .boxed(),
+            span: Span::default()
+        }))]
+    );
+}
+
+#[test]
+fn it_parses_cases() {
+    let number = |n: &str| Expression {
+        children: vec![Element::Literal(Literal::Number(Number {
+            number: n.to_string(),
+            span: Span::default(),
+        }))],
+        span: Span::default(),
+    };
+    assert_eq!(
+        parse("{(1, 2), (3, 4):}").without_spans(),
         Expression {
-            children: vec![
-                Element::Literal(Literal::Function(Function::Sin)),
-                Element::Literal(Literal::Number(Number {
-                    number: "10".to_string()
-                }))
-            ]
+            children: vec![Element::Group(Group::Cases(Cases {
+                inner: vec![
+                    vec![number("1"), number("2")],
+                    vec![number("3"), number("4")]
+                ],
+                span: Span::default()
+            }))],
+            span: Span::default()
         }
-    )
+    );
+    assert!(matches!(
+        parse("{(1, 2), (3, 4)}").children[0],
+        Element::Group(Group::Braces(_))
+    ));
+    assert!(matches!(
+        parse("{(1, 2), (3):}").children[0],
+        Element::Group(Group::Fenced(_))
+    ));
+}
+
+#[test]
+fn it_parses_limits() {
+    let symbol = |s: &str| {
+        Element::Literal(Literal::Symbol(Symbol {
+            symbol: s.to_string(),
+            span: Span::default(),
+        }))
+    };
+    let limit = |operator, bottom: Option<Element>| {
+        Element::Special(Special::Limit(Limit {
+            operator,
+            top: None,
+            bottom: bottom.map(Box::new),
+            span: Span::default(),
+        }))
+    };
+    assert_eq!(
+        parse("max_i x").without_spans().children,
+        vec![limit(LimitOperator::Max, Some(symbol("i"))), symbol("x")]
+    );
+    assert_eq!(
+        parse("lim x").without_spans().children,
+        vec![limit(LimitOperator::Lim, None), symbol("x")]
+    );
+    for (source, operator) in [
+        ("lim_n", LimitOperator::Lim),
+        ("Lim_n", LimitOperator::BigLim),
+        ("sup_n", LimitOperator::Sup),
+        ("inf_n", LimitOperator::Inf),
+        ("min_n", LimitOperator::Min),
+    ] {
+        assert_eq!(
+            parse(source).without_spans().children,
+            vec![limit(operator, Some(symbol("n")))]
+        );
+    }
+    assert_eq!(
+        parse("A sup B").without_spans().children[1],
+        Element::Literal(Literal::Relation(Relation::SupSet, Span::default()))
+    );
+    assert!(matches!(
+        &parse("max(a, b)").children[0],
+        Element::Special(Special::FunctionApplication(f)) if f.function == Function::Max
+    ));
 }
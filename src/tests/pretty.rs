@@ -0,0 +1,57 @@
+use crate::format::pretty::ToLayout;
+use crate::parse;
+
+fn layout(source: &str) -> String {
+    parse(source).to_layout().to_string()
+}
+
+#[test]
+fn it_stacks_fractions() {
+    assert_eq!(layout("x = 1/2"), "     1\nx = ───\n     2");
+    assert_eq!(layout("frac(a+b)(c)"), " a+b\n─────\n  c");
+}
+
+#[test]
+fn it_computes_box_dimensions() {
+    let layout = parse("(1/2 + x)").to_layout();
+    assert_eq!(
+        (layout.width(), layout.height(), layout.baseline()),
+        (7, 3, 1)
+    );
+    assert_eq!(layout.to_string(), "⎛ 1   ⎞\n⎜───+x⎟\n⎝ 2   ⎠");
+}
+
+#[test]
+fn it_draws_radicals() {
+    assert_eq!(layout("sqrt(a+b)"), "  ___\n╲╱a+b");
+    assert_eq!(layout("sqrt(1/2)"), "    ___\n   ╱ 1\n  ╱ ───\n╲╱   2");
+    assert_eq!(layout("root 3 x"), "3  _\n ╲╱x");
+}
+
+#[test]
+fn it_places_scripts() {
+    assert_eq!(layout("x^2 + y_i"), " 2\nx +y\n    i");
+}
+
+#[test]
+fn it_places_limits() {
+    assert_eq!(layout("sum_(i=0)^n i"), "  n\n  ∑  i\ni = 0");
+    assert_eq!(layout("prod_k"), "∏\nk");
+}
+
+#[test]
+fn it_draws_matrices() {
+    assert_eq!(layout("[[1,2],[3,4]]"), "⎡1  2⎤\n⎣3  4⎦");
+    assert_eq!(layout("((a),(b),(c))"), "⎛a⎞\n⎜b⎟\n⎝c⎠");
+    assert_eq!(
+        layout("{(x, x >= 0), (-x, x < 0):}"),
+        "⎧x   x ≥ 0\n⎩−x  x < 0"
+    );
+}
+
+#[test]
+fn it_draws_accents() {
+    assert_eq!(layout("hat x"), "^\nx");
+    assert_eq!(layout("bar(ab)"), "__\nab");
+    assert_eq!(layout("ubrace(abc)"), "abc\n╰─╯");
+}
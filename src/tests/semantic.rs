@@ -0,0 +1,111 @@
+use crate::format::asciimath::ToAsciiMath;
+use crate::parse;
+use crate::semantic::error::SemanticErrorKind;
+use crate::semantic::precedence::{Associativity, Operator, PrecedenceTable};
+use crate::semantic::{build, Node};
+use crate::tokens::Operation;
+
+/// Writes the tree as an s-expression with `.` for implicit multiplication
+fn sexp(node: &Node) -> String {
+    match node {
+        Node::Operand(e) => e.to_asciimath(),
+        Node::Unary(u) => format!("({} {})", operator(&u.operator), sexp(&u.operand)),
+        Node::Binary(b) => format!(
+            "({} {} {})",
+            operator(&b.operator),
+            sexp(&b.left),
+            sexp(&b.right)
+        ),
+        Node::Apply(a) => format!("({} {})", a.operator.to_asciimath(), sexp(&a.operand)),
+        Node::Tuple(t) => format!(
+            "[{}]",
+            t.items.iter().map(sexp).collect::<Vec<String>>().join(", ")
+        ),
+    }
+}
+
+fn operator(operator: &Operator) -> String {
+    match operator {
+        Operator::Operation(o) => o.to_asciimath(),
+        Operator::Relation(r) => r.to_asciimath(),
+        Operator::Logical(l) => l.to_asciimath(),
+        Operator::Arrow(a) => a.to_asciimath(),
+        Operator::Custom(name) => name.clone(),
+        Operator::ImplicitMultiplication => ".".to_string(),
+    }
+}
+
+fn tree(source: &str) -> String {
+    let node = build(&parse(source), &PrecedenceTable::default())
+        .unwrap_or_else(|e| panic!("{}: {}", source, e));
+    sexp(&node)
+}
+
+fn tree_err(source: &str, table: &PrecedenceTable) -> SemanticErrorKind {
+    build(&parse(source), table).unwrap_err().kind
+}
+
+#[test]
+fn it_builds_arithmetic() {
+    assert_eq!(tree("a + b * c"), "(+ a (* b c))");
+    assert_eq!(tree("a - b - c"), "(- (- a b) c)");
+    assert_eq!(tree("2x + 1"), "(+ (. 2 x) 1)");
+    assert_eq!(tree("-a^2 - b"), "(- (- a ^ 2) b)");
+    assert_eq!(tree("x = (a + b) c"), "(= x (. (+ a b) c))");
+    assert_eq!(tree("a/b + sqrt c"), "(+ a / b sqrt c)");
+}
+
+#[test]
+fn it_builds_logic() {
+    assert_eq!(tree("not a and b or c"), "(or (and (not a) b) c)");
+    assert_eq!(tree("p => q => r"), "(=> p (=> q r))");
+    assert_eq!(tree("a < b iff b > a"), "(<=> (< a b) (> b a))");
+}
+
+#[test]
+fn it_applies_big_operators_and_functions() {
+    assert_eq!(
+        tree("sum_(i=1)^n i^2 + 1"),
+        "(+ (sum _ {i = 1} ^ n i ^ 2) 1)"
+    );
+    assert_eq!(tree("sin -x + y"), "(+ (sin (- x)) y)");
+    assert_eq!(tree("2 sin(x) y"), "(. (. 2 sin (x)) y)");
+}
+
+#[test]
+fn it_builds_tuples() {
+    assert_eq!(tree("(a, b + c)"), "[a, (+ b c)]");
+    assert_eq!(tree("f(a, b) = c"), "(= f (a , b) c)");
+}
+
+#[test]
+fn it_uses_the_given_precedences() {
+    let table = PrecedenceTable::default().with_infix(
+        Operator::Operation(Operation::Plus),
+        9,
+        Associativity::Left,
+    );
+    let node = build(&parse("a + b * c"), &table).unwrap();
+    assert_eq!(sexp(&node), "(* (+ a b) c)");
+
+    let table = PrecedenceTable::default().with_infix(
+        Operator::Operation(Operation::Minus),
+        7,
+        Associativity::Right,
+    );
+    let node = build(&parse("a - b - c"), &table).unwrap();
+    assert_eq!(sexp(&node), "(- a (- b c))");
+}
+
+#[test]
+fn it_reports_errors() {
+    let table = PrecedenceTable::default();
+    assert_eq!(tree_err("2 +", &table), SemanticErrorKind::MissingOperand);
+    assert_eq!(tree_err("= 1", &table), SemanticErrorKind::MissingOperand);
+    assert_eq!(tree_err("a,,b", &table), SemanticErrorKind::MissingOperand);
+    assert_eq!(tree_err("", &table), SemanticErrorKind::MissingOperand);
+    assert_eq!(
+        tree_err("2x", &PrecedenceTable::empty()),
+        SemanticErrorKind::UnexpectedElement("x".to_string())
+    );
+}
@@ -0,0 +1,77 @@
+use crate::elements::special::Expression;
+use crate::parse;
+use crate::parsing::tokenizer::Tokenizer;
+use crate::tokens::SpannedToken;
+use serde_json::json;
+
+/// The sources used by the parsing and tokenization tests
+const SOURCES: &[&str] = &[
+    "sum_2^3",
+    "[[1, 2],[3,4]]",
+    "[[1, 3, 4],[3,4]]",
+    "[[1]]",
+    "((1), (2))(1,2) - f",
+    "((1, 3), (2, 5))",
+    "root 3 16",
+    "root 3",
+    "sin 10",
+    "sum_(i=1)^n*sin(x)",
+    "G_(11) = 5.16e6 € * (215)/(170) = 6.53e6",
+    "hat x + color(red)(y) + overset a b + bb \"text\" + (: a :) + abs(x) \\\n",
+];
+
+#[test]
+fn it_round_trips_expressions_through_json() {
+    for source in SOURCES {
+        let expression = parse(source);
+        let json = serde_json::to_string(&expression).unwrap();
+        let deserialized: Expression = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, expression, "{}", source);
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    }
+}
+
+#[test]
+fn it_round_trips_tokens_through_json() {
+    for source in SOURCES {
+        let tokens = Tokenizer::new(source).parse_spanned();
+        let json = serde_json::to_string(&tokens).unwrap();
+        let deserialized: Vec<SpannedToken> = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, tokens, "{}", source);
+        assert_eq!(serde_json::to_string(&deserialized).unwrap(), json);
+    }
+}
+
+#[test]
+fn it_serializes_tagged_enums() {
+    assert_eq!(
+        serde_json::to_value(parse("alpha^2")).unwrap(),
+        json!({
+            "children": [{
+                "type": "Special",
+                "value": {
+                    "type": "Pow",
+                    "value": {
+                        "base": {
+                            "type": "Literal",
+                            "value": {"type": "Greek", "value": ["Alpha", {"start": 0, "end": 5}]}
+                        },
+                        "exp": {
+                            "type": "Literal",
+                            "value": {
+                                "type": "Number",
+                                "value": {"number": "2", "span": {"start": 6, "end": 7}}
+                            }
+                        },
+                        "span": {"start": 0, "end": 7}
+                    }
+                }
+            }],
+            "span": {"start": 0, "end": 7}
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Tokenizer::new("a").parse()).unwrap(),
+        json!([{"type": "Text", "value": {"type": "Symbol", "value": "a"}}])
+    );
+}
@@ -0,0 +1,82 @@
+use crate::elements::group::Group;
+use crate::elements::special::Special;
+use crate::elements::Element;
+use crate::parse;
+use crate::parsing::tokenizer::Tokenizer;
+use crate::span::Span;
+
+fn positions(span: Span) -> (usize, usize) {
+    (span.start, span.end)
+}
+
+#[test]
+fn it_records_token_spans() {
+    let mut tokenizer = Tokenizer::new("sum_(i=1) \"text\" 12.5");
+    let spans = tokenizer
+        .parse_spanned()
+        .into_iter()
+        .map(|t| positions(t.span))
+        .collect::<Vec<(usize, usize)>>();
+    assert_eq!(
+        spans,
+        vec![
+            (0, 3),
+            (3, 4),
+            (4, 5),
+            (5, 6),
+            (6, 7),
+            (7, 8),
+            (8, 9),
+            (9, 10),
+            (10, 16),
+            (16, 17),
+            (17, 21)
+        ]
+    );
+}
+
+#[test]
+fn it_records_element_spans() {
+    let expression = parse("alpha + frac{a}{b}^2 - (x, y)");
+    assert_eq!(positions(expression.span), (0, 29));
+
+    let spans = expression
+        .children
+        .iter()
+        .map(|e| positions(e.span()))
+        .collect::<Vec<(usize, usize)>>();
+    assert_eq!(spans, vec![(0, 5), (6, 7), (8, 20), (21, 22), (23, 29)]);
+
+    if let Element::Special(Special::Pow(pow)) = &expression.children[2] {
+        assert_eq!(positions(pow.base.span()), (8, 18));
+        assert_eq!(positions(pow.exp.span()), (19, 20));
+    } else {
+        panic!("expected a pow element")
+    }
+    if let Element::Group(Group::Parentheses(p)) = &expression.children[4] {
+        assert_eq!(positions(p.inner.span), (24, 28));
+    } else {
+        panic!("expected parentheses")
+    }
+}
+
+#[test]
+fn it_records_matrix_cell_spans() {
+    let expression = parse("[[a, bc],[1, 2]]");
+
+    if let Element::Group(Group::Matrix(m)) = &expression.children[0] {
+        assert_eq!(positions(m.span), (0, 16));
+        assert_eq!(positions(m.inner[0][1].span), (5, 7));
+        assert_eq!(positions(m.inner[1][0].span), (10, 11));
+    } else {
+        panic!("expected a matrix")
+    }
+}
+
+#[test]
+fn it_converts_spans_to_byte_ranges() {
+    let source = "α ≤ β";
+    let expression = parse(source);
+    let last = expression.children.last().unwrap().span();
+    assert_eq!(&source[last.byte_range(source)], "β");
+}
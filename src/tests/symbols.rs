@@ -0,0 +1,128 @@
+use crate::elements::special::Expression;
+use crate::evaluation::evaluate;
+use crate::format::asciimath::ToAsciiMath;
+use crate::format::latex::ToLatex;
+use crate::format::mathml::ToMathML;
+use crate::format::unicode::ToUnicode;
+use crate::parsing::symbols::SymbolTable;
+use crate::parsing::tokenizer::Tokenizer;
+use crate::parsing::tree_parser::TreeParser;
+use crate::semantic::precedence::{Associativity, Operator, PrecedenceTable};
+use crate::semantic::{build, Node};
+use crate::tokens::{
+    Accent, Arrow, CustomSymbol, Operation, Relation, SymbolCategory, Text, Token,
+};
+
+fn symbols() -> SymbolTable {
+    SymbolTable::default()
+        .with_symbol(CustomSymbol::new(
+            "hbar",
+            SymbolCategory::Identifier,
+            "&#x210F;",
+            "\\hbar",
+            "ℏ",
+        ))
+        .with_symbol(CustomSymbol::new(
+            "-:>",
+            SymbolCategory::Arrow,
+            "&#x21A6;",
+            "\\mapsto",
+            "↦",
+        ))
+        .with_symbol(CustomSymbol::new(
+            "otimes",
+            SymbolCategory::Operation,
+            "&#x2297;",
+            "\\otimes",
+            "⊗",
+        ))
+}
+
+fn parse(source: &str) -> Expression {
+    let tokens = Tokenizer::with_symbols(source, &symbols()).parse_spanned();
+    TreeParser::new(tokens).parse()
+}
+
+#[test]
+fn it_tokenizes_custom_symbols() {
+    let tokens = Tokenizer::with_symbols("hbar-:>h", &symbols()).parse();
+    assert_eq!(tokens.len(), 3);
+    assert!(matches!(&tokens[0], Token::Custom(c) if c.name == "hbar"));
+    assert!(matches!(&tokens[1], Token::Custom(c) if c.category == SymbolCategory::Arrow));
+    assert_eq!(tokens[2], Token::Text(Text::Symbol("h".to_string())));
+
+    // the built-in symbols are still recognized and shorter patterns aren't affected
+    let tokens = Tokenizer::with_symbols("-: ->", &symbols()).parse();
+    assert_eq!(tokens[0], Token::Operation(Operation::Div));
+    assert_eq!(tokens[2], Token::Arrow(Arrow::To));
+
+    // without the table the symbols are plain text
+    assert_eq!(
+        Tokenizer::new("hbar").parse(),
+        vec![
+            Token::Text(Text::Symbol("h".to_string())),
+            Token::Accent(Accent::Overline),
+        ]
+    );
+}
+
+#[test]
+fn it_replaces_and_adds_patterns() {
+    let symbols = symbols().with_pattern("≤", Token::Relation(Relation::Le));
+    assert_eq!(
+        Tokenizer::with_symbols("≤", &symbols).parse(),
+        vec![Token::Relation(Relation::Le)]
+    );
+    let tokens = Tokenizer::with_symbols("otimes ox", &symbols).parse();
+    assert!(matches!(&tokens[0], Token::Custom(c) if c.name == "otimes"));
+    assert_eq!(tokens[2], Token::Operation(Operation::OTimes));
+
+    let tokens = Tokenizer::with_symbols("a+b", &SymbolTable::empty()).parse();
+    assert_eq!(tokens[1], Token::Text(Text::Symbol("+".to_string())));
+}
+
+#[test]
+fn it_renders_custom_symbols() {
+    let expression = parse("E = hbar omega -:> 2hbar");
+    assert_eq!(
+        expression.to_mathml(),
+        "<mrow><mi>E</mi><mo>=</mo><mi>&#x210F;</mi><mi>&omega;</mi>\
+         <mo>&#x21A6;</mo><mn>2</mn><mi>&#x210F;</mi></mrow>"
+    );
+    assert_eq!(
+        expression.to_latex(),
+        "E = \\hbar \\omega \\mapsto 2 \\hbar"
+    );
+    assert_eq!(expression.to_unicode(), "E=ℏω↦2ℏ");
+    assert_eq!(expression.to_asciimath(), "E = hbar omega -:> 2 hbar");
+    assert_eq!(
+        parse(&expression.to_asciimath()).without_spans(),
+        expression.without_spans()
+    );
+}
+
+#[test]
+fn it_binds_custom_operators() {
+    let expression = parse("a otimes b + c");
+    // without a precedence the custom operator is an operand
+    let node = build(&expression, &PrecedenceTable::default()).unwrap();
+    assert!(matches!(node, Node::Binary(b)
+        if b.operator == Operator::Operation(Operation::Plus)));
+
+    let table = PrecedenceTable::default().with_infix(
+        Operator::Custom("otimes".to_string()),
+        8,
+        Associativity::Left,
+    );
+    let node = build(&expression, &table).unwrap();
+    assert!(matches!(node, Node::Binary(b)
+        if b.operator == Operator::Operation(Operation::Plus)
+            && matches!(&*b.left, Node::Binary(l)
+                if l.operator == Operator::Custom("otimes".to_string()))));
+}
+
+#[test]
+fn it_evaluates_custom_identifiers() {
+    let variables = hashmap! { "hbar".to_string() => 2.0 };
+    assert_eq!(evaluate(&parse("3 hbar"), &variables), Ok(6.0));
+}
@@ -1,10 +1,17 @@
+use crate::format::unicode::ToUnicode;
 use crate::parsing::tokenizer::Tokenizer;
-use crate::tokens::{Function, Grouping, Misc, Operation, Relation, Text, Token};
+use crate::tokens::mappings::{
+    get_arrow_mapping, get_greek_mappings, get_logical_mappings, get_misc_mappings,
+    get_operation_mappings, get_relation_mapping,
+};
+use crate::tokens::{
+    Accent, Arrow, Function, Greek, Grouping, Logical, Misc, Operation, Relation, Text, Token,
+};
 
 #[test]
 fn it_tokenizes_expressions1() {
     let expression = "sum_(i=1)^n*sin(x)";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     assert_eq!(
         tokens,
@@ -30,7 +37,7 @@ fn it_tokenizes_expressions1() {
 #[test]
 fn it_tokenizes_expressions2() {
     let expression = "G_(11) = 5.16e6 € * (215)/(170) = 6.53e6";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     assert_eq!(
         tokens,
@@ -67,7 +74,7 @@ fn it_tokenizes_expressions2() {
 #[test]
 fn it_tokenizes_expressions3() {
     let expression = "[[1, 2],[3, 4]] // \\\n";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     assert_eq!(
         tokens,
@@ -98,7 +105,7 @@ fn it_tokenizes_expressions3() {
 #[test]
 fn it_tokenizes_text1() {
     let expression = "\"just plain text\"";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     assert_eq!(
         tokens,
@@ -109,7 +116,7 @@ fn it_tokenizes_text1() {
 #[test]
 fn it_tokenizes_text2() {
     let expression = "\"plain text\" * \"plain text 2\" + a";
-    let mut tokenizer = Tokenizer::new(expression.to_string());
+    let mut tokenizer = Tokenizer::new(expression);
     let tokens = tokenizer.parse();
     assert_eq!(
         tokens,
@@ -126,3 +133,203 @@ fn it_tokenizes_text2() {
         ]
     )
 }
+
+#[test]
+fn it_tokenizes_hidden_braces() {
+    let expression = "{:x:} ( x :}";
+    let mut tokenizer = Tokenizer::new(expression);
+    let tokens = tokenizer.parse();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Grouping(Grouping::RBraceHidden),
+            Token::Text(Text::Symbol("x".to_string())),
+            Token::Grouping(Grouping::LBraceHidden),
+            Token::Text(Text::Whitespace),
+            Token::Grouping(Grouping::RXPar),
+        ]
+    )
+}
+
+#[test]
+fn it_tokenizes_text_blocks() {
+    let expression = "text(some  words) mbox(a \"b\")";
+    let mut tokenizer = Tokenizer::new(expression);
+    let tokens = tokenizer.parse();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Text(Text::Plain("some  words".to_string())),
+            Token::Text(Text::Whitespace),
+            Token::Text(Text::Plain("a \"b\"".to_string())),
+        ]
+    );
+    let mut tokenizer = Tokenizer::new("text(abc");
+    assert_eq!(
+        tokenizer.parse(),
+        vec![Token::Text(Text::Plain("abc".to_string()))]
+    );
+    assert_eq!(tokenizer.errors().len(), 1);
+}
+
+#[test]
+fn it_tokenizes_unterminated_text_and_colors() {
+    let mut tokenizer = Tokenizer::new("\"abc");
+    assert_eq!(
+        tokenizer.parse(),
+        vec![Token::Text(Text::Plain("abc".to_string()))]
+    );
+    assert_eq!(tokenizer.errors().len(), 1);
+    let mut tokenizer = Tokenizer::new("color(red");
+    assert_eq!(
+        tokenizer.parse(),
+        vec![Token::Accent(Accent::Color("red".to_string()))]
+    );
+    assert_eq!(tokenizer.errors().len(), 1);
+}
+
+#[test]
+fn it_tokenizes_limit_operators() {
+    let expression = "lim Lim inf in";
+    let mut tokenizer = Tokenizer::new(expression);
+    let tokens = tokenizer.parse();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Operation(Operation::Lim),
+            Token::Text(Text::Whitespace),
+            Token::Operation(Operation::BigLim),
+            Token::Text(Text::Whitespace),
+            Token::Operation(Operation::Inf),
+            Token::Text(Text::Whitespace),
+            Token::Relation(Relation::In),
+        ]
+    )
+}
+
+#[test]
+fn it_tokenizes_longest_matches() {
+    let expression = "infty int delta ddots |><| ->> floor supe";
+    let tokens = Tokenizer::new(expression)
+        .parse()
+        .into_iter()
+        .filter(|t| *t != Token::Text(Text::Whitespace))
+        .collect::<Vec<Token>>();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::Misc(Misc::Infty),
+            Token::Misc(Misc::Int),
+            Token::Greek(Greek::Delta),
+            Token::Misc(Misc::DDots),
+            Token::Operation(Operation::Bowtie),
+            Token::Arrow(Arrow::TwoHeadRightArrow),
+            Token::Grouping(Grouping::Floor),
+            Token::Relation(Relation::SupSetEq),
+        ]
+    );
+    assert_eq!(
+        Tokenizer::new("ddot|><->").parse(),
+        vec![
+            Token::Accent(Accent::DDot),
+            Token::Operation(Operation::LTimes),
+            Token::Arrow(Arrow::To),
+        ]
+    );
+}
+
+#[test]
+fn it_tokenizes_unicode_symbols() {
+    let expression = "α≤β → ∞ ∑√x";
+    assert_eq!(
+        Tokenizer::new(expression).parse(),
+        vec![
+            Token::Greek(Greek::Alpha),
+            Token::Relation(Relation::Le),
+            Token::Greek(Greek::Beta),
+            Token::Text(Text::Whitespace),
+            Token::Arrow(Arrow::To),
+            Token::Text(Text::Whitespace),
+            Token::Misc(Misc::Infty),
+            Token::Text(Text::Whitespace),
+            Token::Operation(Operation::Sum),
+            Token::Misc(Misc::Sqrt),
+            Token::Text(Text::Symbol("x".to_string())),
+        ]
+    );
+    assert_eq!(
+        Tokenizer::new("⇒ ⇔").parse(),
+        vec![
+            Token::Logical(Logical::Implies),
+            Token::Text(Text::Whitespace),
+            Token::Logical(Logical::Iff),
+        ]
+    );
+}
+
+#[test]
+fn it_tokenizes_the_unicode_output_of_all_symbols() {
+    fn assert_unicode<T: ToUnicode, F: Fn(&Token) -> Option<&T>>(value: T, category: F) {
+        let unicode = value.to_unicode();
+        // operators like `and` or `lim` are written as words
+        if unicode.is_ascii() {
+            return;
+        }
+        let tokens = Tokenizer::new(&unicode).parse();
+        assert_eq!(tokens.len(), 1, "{}", unicode);
+        let token = category(&tokens[0]).unwrap_or_else(|| panic!("{}: {:?}", unicode, tokens));
+        assert_eq!(token.to_unicode(), unicode);
+    }
+    for mapping in get_greek_mappings() {
+        for (_, greek) in mapping {
+            assert_unicode(greek, |t| match t {
+                Token::Greek(g) => Some(g),
+                _ => None,
+            });
+        }
+    }
+    for mapping in get_relation_mapping() {
+        for (_, relation) in mapping {
+            assert_unicode(relation, |t| match t {
+                Token::Relation(r) => Some(r),
+                _ => None,
+            });
+        }
+    }
+    for mapping in get_operation_mappings() {
+        for (_, operation) in mapping {
+            assert_unicode(operation, |t| match t {
+                Token::Operation(o) => Some(o),
+                _ => None,
+            });
+        }
+    }
+    for mapping in get_misc_mappings() {
+        for (_, misc) in mapping {
+            assert_unicode(misc, |t| match t {
+                Token::Misc(m) => Some(m),
+                _ => None,
+            });
+        }
+    }
+    // `⇒` and `⇔` are shared by arrows and logical operators
+    for mapping in get_arrow_mapping() {
+        for (_, arrow) in mapping {
+            let unicode = arrow.to_unicode();
+            let tokens = Tokenizer::new(&unicode).parse();
+            match &tokens[..] {
+                [Token::Arrow(a)] => assert_eq!(a.to_unicode(), unicode),
+                [Token::Logical(l)] => assert_eq!(l.to_unicode(), unicode),
+                _ => panic!("{}: {:?}", unicode, tokens),
+            }
+        }
+    }
+    for mapping in get_logical_mappings() {
+        for (_, logical) in mapping {
+            assert_unicode(logical, |t| match t {
+                Token::Logical(l) => Some(l),
+                _ => None,
+            });
+        }
+    }
+}
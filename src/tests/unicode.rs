@@ -0,0 +1,53 @@
+use crate::format::unicode::ToUnicode;
+use crate::parse;
+
+#[test]
+fn it_renders_scripts() {
+    assert_eq!(parse("x^2 + a_(i+1)").to_unicode(), "x²+aᵢ₊₁");
+    assert_eq!(parse("e^(pi/2)").to_unicode(), "e^{π/2}");
+}
+
+#[test]
+fn it_renders_radicals() {
+    assert_eq!(parse("sqrt(a+b)").to_unicode(), "√(a+b)");
+    assert_eq!(
+        parse("sqrt x + root 3 x + root n (x)").to_unicode(),
+        "√x+∛x+ⁿ√x"
+    );
+}
+
+#[test]
+fn it_renders_fractions() {
+    assert_eq!(parse("1/2 + (a+b)/c").to_unicode(), "1/2+(a+b)/c");
+    assert_eq!(parse("frac(x^2)(y)").to_unicode(), "x²/y");
+}
+
+#[test]
+fn it_renders_sums_and_integrals() {
+    assert_eq!(parse("sum_(i=1)^n i").to_unicode(), "∑_{i=1}^{n}i");
+    assert_eq!(parse("oint_C f(z) dz").to_unicode(), "∮_{C}f(z)dz");
+}
+
+#[test]
+fn it_renders_symbols() {
+    assert_eq!(parse("alpha in RR").to_unicode(), "α∈ℝ");
+    assert_eq!(parse("floor(x) -> oo").to_unicode(), "⌊x⌋→∞");
+    assert_eq!(parse("sin x").to_unicode(), "sin x");
+}
+
+#[test]
+fn it_renders_matrices() {
+    assert_eq!(parse("[[1,2],[3,4]]").to_unicode(), "[[1, 2], [3, 4]]");
+    assert_eq!(parse("((a),(b))").to_unicode(), "((a), (b))");
+    assert_eq!(
+        parse("{(x, if x >= 0), (-x, if x < 0):}").to_unicode(),
+        "{(x, if x≥0), (−x, if x<0)"
+    );
+}
+
+#[test]
+fn it_renders_accents_and_fonts() {
+    assert_eq!(parse("hat x").to_unicode(), "x\u{0302}");
+    assert_eq!(parse("bar(ab)").to_unicode(), "a\u{0305}b\u{0305}");
+    assert_eq!(parse("bbb \"RN\" fr \"g\"").to_unicode(), "ℝℕ𝔤");
+}
@@ -0,0 +1,94 @@
+use crate::elements::fold::{walk_element, Fold};
+use crate::elements::group::Group;
+use crate::elements::literal::{Literal, Number};
+use crate::elements::visit::{walk_group, walk_literal_mut, Visit, VisitMut};
+use crate::elements::Element;
+use crate::format::asciimath::ToAsciiMath;
+use crate::parse;
+
+const SOURCE: &str = "sum_(i=1)^n x_i^2 + frac{a}{b} + root 3 (sqrt y) + hat z + color(red)(w) \
+                      + overset a b + underset c d + prod_0^1 + oint_C + (: e :) + abs(f) \
+                      + floor(g) + ceil(h) + norm(j) + [[k, l], [m, n]] + ((o), (p)) + {q} + (r)";
+
+#[derive(Default)]
+struct Symbols(Vec<String>);
+
+impl Visit for Symbols {
+    fn visit_literal(&mut self, literal: &Literal) {
+        if let Literal::Symbol(s) = literal {
+            self.0.push(s.symbol.clone());
+        }
+    }
+}
+
+struct Uppercase;
+
+impl VisitMut for Uppercase {
+    fn visit_literal_mut(&mut self, literal: &mut Literal) {
+        if let Literal::Symbol(s) = literal {
+            s.symbol = s.symbol.to_uppercase();
+        }
+        walk_literal_mut(self, literal);
+    }
+}
+
+struct Identity;
+
+impl Fold for Identity {}
+
+#[test]
+fn it_visits_every_node() {
+    let mut symbols = Symbols::default();
+    symbols.visit_expression(&parse(SOURCE));
+    assert_eq!(symbols.0.join(""), "inxiabyzwabdcCehjklmnopqr");
+}
+
+#[test]
+fn it_overrides_single_nodes() {
+    struct Matrices(usize);
+
+    impl Visit for Matrices {
+        fn visit_group(&mut self, group: &Group) {
+            if let Group::Matrix(_) = group {
+                self.0 += 1;
+            }
+            walk_group(self, group);
+        }
+    }
+    let mut matrices = Matrices(0);
+    matrices.visit_expression(&parse("[[1, 2]] + ([[a], [b]])"));
+    assert_eq!(matrices.0, 2);
+}
+
+#[test]
+fn it_mutates_every_node() {
+    let mut expression = parse("x_i^2 + hat(sqrt y) + abs(z) + [[a, b]]");
+    Uppercase.visit_expression_mut(&mut expression);
+    assert_eq!(expression, parse("X_I^2 + hat(sqrt Y) + abs(Z) + [[A, B]]"));
+}
+
+#[test]
+fn it_folds_trees() {
+    let expression = parse(SOURCE);
+    assert_eq!(Identity.fold_expression(expression.clone()), expression);
+
+    struct ReplaceSymbols;
+
+    impl Fold for ReplaceSymbols {
+        fn fold_element(&mut self, element: Element) -> Element {
+            match element {
+                Element::Literal(Literal::Symbol(s)) => Element::Literal(Literal::Number(Number {
+                    number: "1".to_string(),
+                    span: s.span,
+                })),
+                element => walk_element(self, element),
+            }
+        }
+    }
+    assert_eq!(
+        ReplaceSymbols
+            .fold_expression(parse("x^2 + sqrt(y) + [[a, b]]"))
+            .to_asciimath(),
+        "1 ^ 2 + sqrt (1) + [[1, 1]]"
+    );
+}
@@ -1,13 +1,13 @@
-pub const G_UPARROW: &[&str] = &["uarr", "uparrow"];
-pub const G_DOWNARROW: &[&str] = &["darr", "downarrow"];
+pub const G_UPARROW: &[&str] = &["uarr", "uparrow", "↑"];
+pub const G_DOWNARROW: &[&str] = &["darr", "downarrow", "↓"];
 pub const G_RIGHTARROW: &[&str] = &["rarr", "rightarrow"];
-pub const G_TO: &[&str] = &["->", "to"];
-pub const G_RIGHTARROWTAIL: &[&str] = &[">->", "rightarrowtail"];
-pub const G_TWOHEADRIGHTARROW: &[&str] = &["->>", "twoheadrightarrow"];
-pub const G_TWOHEADRIGHTARROWTAIL: &[&str] = &[">->>", "twoheadrightarrowtail"];
-pub const G_MAPSTO: &[&str] = &["|->", "mapsto"];
-pub const G_LEFTARROW: &[&str] = &["larr", "leftarrow"];
-pub const G_LEFTRIGHTARROW: &[&str] = &["harr", "leftrightarrow"];
+pub const G_TO: &[&str] = &["->", "to", "→"];
+pub const G_RIGHTARROWTAIL: &[&str] = &[">->", "rightarrowtail", "↣"];
+pub const G_TWOHEADRIGHTARROW: &[&str] = &["->>", "twoheadrightarrow", "↠"];
+pub const G_TWOHEADRIGHTARROWTAIL: &[&str] = &[">->>", "twoheadrightarrowtail", "⤖"];
+pub const G_MAPSTO: &[&str] = &["|->", "mapsto", "↦"];
+pub const G_LEFTARROW: &[&str] = &["larr", "leftarrow", "←"];
+pub const G_LEFTRIGHTARROW: &[&str] = &["harr", "leftrightarrow", "↔"];
 pub const G_BIGRIGHTARROW: &[&str] = &["rArr", "Rightarrow"];
-pub const G_BIGLEFTARROW: &[&str] = &["lArr", "Leftarrow"];
+pub const G_BIGLEFTARROW: &[&str] = &["lArr", "Leftarrow", "⇐"];
 pub const G_BIGLEFTRIGHTARROW: &[&str] = &["hArr", "Leftrightarrow"];
@@ -3,4 +3,4 @@ pub const F_BBB: &str = "bbb";
 pub const F_CC: &str = "cc";
 pub const F_TT: &str = "tt";
 pub const F_FR: &str = "fr";
-pub const F_SF: &str = "sf";
\ No newline at end of file
+pub const F_SF: &str = "sf";
@@ -1,36 +1,36 @@
-pub const G_ALPHA: &[&str] = &["alpha"];
-pub const G_BETA: &[&str] = &["beta"];
-pub const G_GAMMA: &[&str] = &["gamma"];
-pub const G_BIGGAMMA: &[&str] = &["Gamma"];
-pub const G_DELTA: &[&str] = &["delta"];
-pub const G_BIGDELTA: &[&str] = &["Delta"];
-pub const G_EPSILON: &[&str] = &["epsilon"];
-pub const G_VAREPSILON: &[&str] = &["varepsilon"];
-pub const G_ZETA: &[&str] = &["zeta"];
-pub const G_ETA: &[&str] = &["eta"];
-pub const G_THETA: &[&str] = &["theta"];
-pub const G_BIGTHETA: &[&str] = &["Theta"];
-pub const G_VARTHETA: &[&str] = &["vartheta"];
-pub const G_IOTA: &[&str] = &["iota"];
-pub const G_KAPPA: &[&str] = &["kappa"];
-pub const G_LAMBDA: &[&str] = &["lambda"];
-pub const G_BIGLAMBDA: &[&str] = &["Lambda"];
-pub const G_MU: &[&str] = &["mu"];
-pub const G_NU: &[&str] = &["nu"];
-pub const G_XI: &[&str] = &["xi"];
-pub const G_BIGXI: &[&str] = &["Xi"];
-pub const G_PI: &[&str] = &["pi"];
-pub const G_BIGPI: &[&str] = &["Pi"];
-pub const G_RHO: &[&str] = &["rho"];
-pub const G_SIGMA: &[&str] = &["sigma"];
-pub const G_BIGSIGMA: &[&str] = &["Sigma"];
-pub const G_TAU: &[&str] = &["tau"];
-pub const G_UPSILON: &[&str] = &["upsilon"];
-pub const G_PHI: &[&str] = &["phi"];
-pub const G_BIGPHI: &[&str] = &["Phi"];
-pub const G_VARPHI: &[&str] = &["varphi"];
-pub const G_CHI: &[&str] = &["chi"];
-pub const G_PSI: &[&str] = &["psi"];
-pub const G_BIGPSI: &[&str] = &["Psi"];
-pub const G_OMEGA: &[&str] = &["omega"];
-pub const G_BIGOMEGA: &[&str] = &["Omega"];
+pub const G_ALPHA: &[&str] = &["alpha", "α"];
+pub const G_BETA: &[&str] = &["beta", "β"];
+pub const G_GAMMA: &[&str] = &["gamma", "γ"];
+pub const G_BIGGAMMA: &[&str] = &["Gamma", "Γ"];
+pub const G_DELTA: &[&str] = &["delta", "δ"];
+pub const G_BIGDELTA: &[&str] = &["Delta", "Δ"];
+pub const G_EPSILON: &[&str] = &["epsilon", "ε"];
+pub const G_VAREPSILON: &[&str] = &["varepsilon", "ϵ"];
+pub const G_ZETA: &[&str] = &["zeta", "ζ"];
+pub const G_ETA: &[&str] = &["eta", "η"];
+pub const G_THETA: &[&str] = &["theta", "θ"];
+pub const G_BIGTHETA: &[&str] = &["Theta", "Θ"];
+pub const G_VARTHETA: &[&str] = &["vartheta", "ϑ"];
+pub const G_IOTA: &[&str] = &["iota", "ι"];
+pub const G_KAPPA: &[&str] = &["kappa", "κ"];
+pub const G_LAMBDA: &[&str] = &["lambda", "λ"];
+pub const G_BIGLAMBDA: &[&str] = &["Lambda", "Λ"];
+pub const G_MU: &[&str] = &["mu", "μ"];
+pub const G_NU: &[&str] = &["nu", "ν"];
+pub const G_XI: &[&str] = &["xi", "ξ"];
+pub const G_BIGXI: &[&str] = &["Xi", "Ξ"];
+pub const G_PI: &[&str] = &["pi", "π"];
+pub const G_BIGPI: &[&str] = &["Pi", "Π"];
+pub const G_RHO: &[&str] = &["rho", "ρ"];
+pub const G_SIGMA: &[&str] = &["sigma", "σ"];
+pub const G_BIGSIGMA: &[&str] = &["Sigma", "Σ"];
+pub const G_TAU: &[&str] = &["tau", "τ"];
+pub const G_UPSILON: &[&str] = &["upsilon", "υ"];
+pub const G_PHI: &[&str] = &["phi", "ϕ"];
+pub const G_BIGPHI: &[&str] = &["Phi", "Φ"];
+pub const G_VARPHI: &[&str] = &["varphi", "φ"];
+pub const G_CHI: &[&str] = &["chi", "χ"];
+pub const G_PSI: &[&str] = &["psi", "ψ"];
+pub const G_BIGPSI: &[&str] = &["Psi", "Ψ"];
+pub const G_OMEGA: &[&str] = &["omega", "ω"];
+pub const G_BIGOMEGA: &[&str] = &["Omega", "Ω"];
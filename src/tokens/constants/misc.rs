@@ -3,42 +3,43 @@ pub const G_T_FRAC: &[&str] = &["frac"];
 
 pub const G_SUB: &[&str] = &["_"];
 pub const G_POW: &[&str] = &["^"];
-pub const G_SQRT: &[&str] = &["sqrt"];
+pub const G_SQRT: &[&str] = &["sqrt", "√"];
 pub const G_ROOT: &[&str] = &["root"];
-pub const G_INT: &[&str] = &["int"];
-pub const G_OINT: &[&str] = &["oint"];
-pub const G_DEL: &[&str] = &["del", "partial"];
-pub const G_GRAD: &[&str] = &["grad", "nbla"];
-pub const G_PM: &[&str] = &["+-", "pm"];
-pub const G_EMPTYSET: &[&str] = &["O/", "emptyset"];
-pub const G_INFTY: &[&str] = &["oo", "infty"];
-pub const G_ALEPH: &[&str] = &["aleph"];
-pub const G_THEREFORE: &[&str] = &[":.", "therefore"];
-pub const G_BECAUSE: &[&str] = &[":'", "because"];
-pub const G_ELDOTS: &[&str] = &["|...|", "|ldots|"];
-pub const G_ECDOTS: &[&str] = &["|cdots|"];
-pub const G_VDOTS: &[&str] = &["vdots"];
-pub const G_DDOTS: &[&str] = &["ddots"];
+pub const G_INT: &[&str] = &["int", "∫"];
+pub const G_OINT: &[&str] = &["oint", "∮"];
+pub const G_DEL: &[&str] = &["del", "partial", "∂"];
+pub const G_GRAD: &[&str] = &["grad", "nbla", "∇"];
+pub const G_PM: &[&str] = &["+-", "pm", "±"];
+pub const G_EMPTYSET: &[&str] = &["O/", "emptyset", "∅"];
+pub const G_INFTY: &[&str] = &["oo", "infty", "∞"];
+pub const G_ALEPH: &[&str] = &["aleph", "ℵ"];
+pub const G_THEREFORE: &[&str] = &[":.", "therefore", "∴"];
+pub const G_BECAUSE: &[&str] = &[":'", "because", "∵"];
+pub const G_ELDOTS: &[&str] = &["|...|", "|ldots|", "…"];
+pub const G_ECDOTS: &[&str] = &["|cdots|", "⋯"];
+pub const G_VDOTS: &[&str] = &["vdots", "⋮"];
+pub const G_DDOTS: &[&str] = &["ddots", "⋱"];
 pub const G_EPIPES: &[&str] = &["|\\ |"];
 pub const G_QUAD: &[&str] = &["|quad|"];
-pub const G_ANGLE: &[&str] = &["/_", "angle"];
-pub const G_FROWN: &[&str] = &["frown"];
-pub const G_TRIANGLE: &[&str] = &["/_\\", "triangle"];
-pub const G_DIAMOND: &[&str] = &["diamond"];
-pub const G_SQUARE: &[&str] = &["square"];
-pub const G_LFLOOR: &[&str] = &["|__", "lfloor"];
-pub const G_RFLOOR: &[&str] = &["__|", "rfloor"];
-pub const G_LCEILING: &[&str] = &["|~", "lceiling"];
-pub const G_RCEILING: &[&str] = &["~|", "rceiling"];
+pub const G_ANGLE: &[&str] = &["/_", "angle", "∠"];
+pub const G_FROWN: &[&str] = &["frown", "⌢"];
+pub const G_TRIANGLE: &[&str] = &["/_\\", "triangle", "△"];
+pub const G_DIAMOND: &[&str] = &["diamond", "⋄"];
+pub const G_SQUARE: &[&str] = &["square", "□"];
+pub const G_LFLOOR: &[&str] = &["|__", "lfloor", "⌊"];
+pub const G_RFLOOR: &[&str] = &["__|", "rfloor", "⌋"];
+pub const G_LCEILING: &[&str] = &["|~", "lceiling", "⌈"];
+pub const G_RCEILING: &[&str] = &["~|", "rceiling", "⌉"];
 
-pub const G_COMPLEX: &[&str] = &["CC"];
-pub const G_NATURAL: &[&str] = &["NN"];
-pub const G_RATIONAL: &[&str] = &["QQ"];
-pub const G_REAL: &[&str] = &["RR"];
-pub const G_INTEGER: &[&str] = &["ZZ"];
+pub const G_COMPLEX: &[&str] = &["CC", "ℂ"];
+pub const G_NATURAL: &[&str] = &["NN", "ℕ"];
+pub const G_RATIONAL: &[&str] = &["QQ", "ℚ"];
+pub const G_REAL: &[&str] = &["RR", "ℝ"];
+pub const G_INTEGER: &[&str] = &["ZZ", "ℤ"];
 
 pub const A_TEXT: char = '"';
-pub const G_T_TEX: &[&str] = &["text"];
+pub const G_T_TEX: &[&str] = &["text", "mbox"];
+pub const G_T_TEX_GROUP: &[&str] = &["text(", "mbox("];
 pub const A_NUMCOMMA: char = '.';
 pub const A_SCIEXP: char = 'e';
 pub const G_NEWLINE: &[&str] = &["\\\n"];
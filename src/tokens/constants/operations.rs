@@ -1,26 +1,29 @@
 pub const G_PLUS: &[&str] = &["+"];
-pub const G_MINUS: &[&str] = &["-"];
-pub const G_CDOT: &[&str] = &["*", "cdot"];
-pub const G_AST: &[&str] = &["**", "ast"];
-pub const G_STAR: &[&str] = &["***", "star"];
+pub const G_MINUS: &[&str] = &["-", "−"];
+pub const G_CDOT: &[&str] = &["*", "cdot", "⋅"];
+pub const G_AST: &[&str] = &["**", "ast", "∗"];
+pub const G_STAR: &[&str] = &["***", "star", "⋆"];
 pub const G_SLASH: &[&str] = &["//"];
-pub const G_BACKSLASH: &[&str] = &["\\\\", "backslash", "setminus"];
-pub const G_TIMES: &[&str] = &["xx", "times"];
-pub const G_DIV: &[&str] = &["-:", "div"];
-pub const G_LTIMES: &[&str] = &["|><", "ltimes"];
-pub const G_RTIMES: &[&str] = &["><|", "rtimes"];
-pub const G_BOWTIE: &[&str] = &["|><|", "bowtie"];
-pub const G_CIRC: &[&str] = &["@", "circ"];
-pub const G_OPLUS: &[&str] = &["o+", "oplus"];
-pub const G_OTIMES: &[&str] = &["ox", "otimes"];
-pub const G_ODOT: &[&str] = &["o.", "odot"];
-pub const G_SUM: &[&str] = &["sum"];
-pub const G_PROD: &[&str] = &["prod"];
-pub const G_WEDGE: &[&str] = &["^^", "wedge"];
-pub const G_BIDWEDGE: &[&str] = &["^^^", "bidwedge"];
-pub const G_VEE: &[&str] = &["vv", "vee"];
-pub const G_BIGVEE: &[&str] = &["vvv", "bigvee"];
-pub const G_CAP: &[&str] = &["nn", "cap"];
-pub const G_BIGCAP: &[&str] = &["nnn", "bigcap"];
-pub const G_CUP: &[&str] = &["uu", "cup"];
-pub const G_BIGCUP: &[&str] = &["uuu", "bigcup"];
+pub const G_BACKSLASH: &[&str] = &["\\\\", "backslash", "setminus", "∖"];
+pub const G_TIMES: &[&str] = &["xx", "times", "×"];
+pub const G_DIV: &[&str] = &["-:", "div", "÷"];
+pub const G_LTIMES: &[&str] = &["|><", "ltimes", "⋉"];
+pub const G_RTIMES: &[&str] = &["><|", "rtimes", "⋊"];
+pub const G_BOWTIE: &[&str] = &["|><|", "bowtie", "⋈"];
+pub const G_CIRC: &[&str] = &["@", "circ", "∘"];
+pub const G_OPLUS: &[&str] = &["o+", "oplus", "⊕"];
+pub const G_OTIMES: &[&str] = &["ox", "otimes", "⊗"];
+pub const G_ODOT: &[&str] = &["o.", "odot", "⊙"];
+pub const G_SUM: &[&str] = &["sum", "∑"];
+pub const G_PROD: &[&str] = &["prod", "∏"];
+pub const G_LIM: &[&str] = &["lim"];
+pub const G_BIGLIM: &[&str] = &["Lim"];
+pub const G_INF: &[&str] = &["inf"];
+pub const G_WEDGE: &[&str] = &["^^", "wedge", "∧"];
+pub const G_BIDWEDGE: &[&str] = &["^^^", "bidwedge", "⋀"];
+pub const G_VEE: &[&str] = &["vv", "vee", "∨"];
+pub const G_BIGVEE: &[&str] = &["vvv", "bigvee", "⋁"];
+pub const G_CAP: &[&str] = &["nn", "cap", "∩"];
+pub const G_BIGCAP: &[&str] = &["nnn", "bigcap", "⋂"];
+pub const G_CUP: &[&str] = &["uu", "cup", "∪"];
+pub const G_BIGCUP: &[&str] = &["uuu", "bigcup", "⋃"];
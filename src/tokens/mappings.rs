@@ -44,6 +44,9 @@ pub fn get_operation_mappings() -> Vec<HashMap<TokenPattern, Operation>> {
             G_ODOT      => Operation::ODot,
             G_SUM       => Operation::Sum,
             G_PROD      => Operation::Prod,
+            G_LIM       => Operation::Lim,
+            G_BIGLIM    => Operation::BigLim,
+            G_INF       => Operation::Inf,
             G_WEDGE     => Operation::Wedge,
             G_VEE       => Operation::Vee,
             G_CAP       => Operation::Cap,
@@ -161,6 +164,10 @@ pub fn get_grouping_mappings() -> Vec<HashMap<TokenPattern, Grouping>> {
             G_LXPAR         => Grouping::LXPar,
             G_MATRIX_SEP    => Grouping::MSep,
         },
+        hashmap! {
+            G_RBRACE_HIDDEN => Grouping::RBraceHidden,
+            G_LBRACE_HIDDEN => Grouping::LBraceHidden,
+        },
         hashmap! {
             G_RPAREN    => Grouping::RParen,
             G_LPAREN    => Grouping::LParen,
@@ -1,3 +1,4 @@
+use crate::span::Span;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -6,6 +7,8 @@ pub mod constants;
 pub mod mappings;
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Token {
     Operation(Operation),
     Misc(Misc),
@@ -18,10 +21,97 @@ pub enum Token {
     Font(FontCommand),
     Function(Function),
     Text(Text),
+    Custom(CustomSymbol),
+    Macro(Macro),
     End,
 }
 
+/// A token together with the span of the source it was parsed from
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpannedToken {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl From<Token> for SpannedToken {
+    fn from(token: Token) -> Self {
+        Self {
+            token,
+            span: Span::default(),
+        }
+    }
+}
+
+/// A symbol that was registered at runtime in a `SymbolTable`
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CustomSymbol {
+    /// The AsciiMath spelling of the symbol
+    pub name: String,
+    pub category: SymbolCategory,
+    /// The content of the MathML element, entities like `&#x210F;` are allowed
+    pub mathml: String,
+    pub latex: String,
+    pub unicode: String,
+}
+
+impl CustomSymbol {
+    pub fn new<S: ToString>(
+        name: S,
+        category: SymbolCategory,
+        mathml: S,
+        latex: S,
+        unicode: S,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            category,
+            mathml: mathml.to_string(),
+            latex: latex.to_string(),
+            unicode: unicode.to_string(),
+        }
+    }
+}
+
+/// A macro that was registered in a `SymbolTable`. Its invocations like `name(a, b)`
+/// are replaced with the body where every parameter is replaced with its argument.
+/// Arguments are kept together while the body is inserted as it is,
+/// so a body like `1/2` needs to be grouped as `{:1/2:}` to be used as an operand.
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Macro {
+    pub name: String,
+    pub parameters: Vec<String>,
+    /// The AsciiMath source the macro expands to
+    pub body: String,
+}
+
+impl Macro {
+    pub fn new<S: ToString>(name: S, parameters: &[&str], body: S) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters: parameters.iter().map(|p| p.to_string()).collect(),
+            body: body.to_string(),
+        }
+    }
+}
+
+/// The category of a custom symbol decides how it is parsed and rendered
+#[derive(Debug, Clone, Copy, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolCategory {
+    Identifier,
+    Number,
+    Operation,
+    Relation,
+    Arrow,
+    Logical,
+}
+
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Text {
     Number(String),
     Symbol(String),
@@ -31,6 +121,7 @@ pub enum Text {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operation {
     Plus,
     Minus,
@@ -50,6 +141,10 @@ pub enum Operation {
     ODot,
     Sum,
     Prod,
+    Lim,
+    BigLim,
+    Sup,
+    Inf,
     Wedge,
     BidWedge,
     Vee,
@@ -61,6 +156,7 @@ pub enum Operation {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Misc {
     AsciiFrac,
     LatexFrac,
@@ -102,6 +198,7 @@ pub enum Misc {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Relation {
     Eq,
     Ne,
@@ -126,6 +223,7 @@ pub enum Relation {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Logical {
     And,
     Or,
@@ -142,6 +240,7 @@ pub enum Logical {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Grouping {
     RParen,
     LParen,
@@ -149,6 +248,8 @@ pub enum Grouping {
     LBracket,
     RBrace,
     LBrace,
+    RBraceHidden,
+    LBraceHidden,
     LAngle,
     RAngle,
     LXPar,
@@ -161,6 +262,7 @@ pub enum Grouping {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Arrow {
     UpArrow,
     DownArrow,
@@ -178,6 +280,8 @@ pub enum Arrow {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Accent {
     Hat,
     Overline,
@@ -194,6 +298,7 @@ pub enum Accent {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Greek {
     Alpha,
     Beta,
@@ -234,6 +339,7 @@ pub enum Greek {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Function {
     Sin,
     Cos,
@@ -267,6 +373,7 @@ pub enum Function {
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FontCommand {
     Big,
     BigOutline,
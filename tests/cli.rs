@@ -0,0 +1,69 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_asciimath"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn it_converts_arguments_to_mathml() {
+    let output = run(&["a+b"], "");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"inline\">\
+         <mrow><mi>a</mi><mo>&plus;</mo><mi>b</mi></mrow></math>\n"
+    );
+}
+
+#[test]
+fn it_reads_stdin_line_by_line() {
+    let output = run(&["--format", "latex", "--display"], "x^2\n\nalpha\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "\\[{x}^{2}\\]\n\\[\\alpha\\]\n");
+}
+
+#[test]
+fn it_reads_files() {
+    let path = std::env::temp_dir().join("asciimath-cli-test.txt");
+    std::fs::write(&path, "sqrt x\n").unwrap();
+    let output = run(&["-f", "unicode", "-i", path.to_str().unwrap()], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "√x\n");
+}
+
+#[test]
+fn it_fails_on_parse_errors() {
+    let output = run(&["-f", "asciimath", "a", "frac{a}"], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a\n");
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("error in expression 2"));
+}
+
+#[test]
+fn it_rejects_invalid_arguments() {
+    let output = run(&["--format", "html", "a"], "");
+    assert_eq!(output.status.code(), Some(2));
+}